@@ -1,6 +1,10 @@
 use std::collections::{HashMap, hash_map::Keys};
 
-use essential::assets::Asset;
+use anyhow::bail;
+use essential::assets::{
+    Asset,
+    bake::{Bake, BakeReader, BakeWriter},
+};
 use glam::{Quat, Vec3};
 use uuid::Uuid;
 
@@ -152,3 +156,59 @@ impl AnimationClip {
         self.duration
     }
 }
+
+/// Stores every channel's samples as plain arrays.
+impl Bake for AnimationClip {
+    fn bake(&self, writer: &mut BakeWriter) -> anyhow::Result<()> {
+        writer.write_u64(self.channels.len() as u64);
+        for (id, channels) in &self.channels {
+            writer.write_bytes(id.as_bytes());
+            writer.write_u64(channels.len() as u64);
+            for channel in channels {
+                writer.write_slice(&channel.time_samples);
+                match &channel.outputs {
+                    AnimationChanelOutput::Translation(values) => {
+                        writer.write_u32(0);
+                        writer
+                            .write_slice(&values.iter().map(|v| v.to_array()).collect::<Vec<_>>());
+                    }
+                    AnimationChanelOutput::Rotation(values) => {
+                        writer.write_u32(1);
+                        writer
+                            .write_slice(&values.iter().map(|q| q.to_array()).collect::<Vec<_>>());
+                    }
+                    AnimationChanelOutput::Scale(values) => {
+                        writer.write_u32(2);
+                        writer
+                            .write_slice(&values.iter().map(|v| v.to_array()).collect::<Vec<_>>());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn unbake(reader: &mut BakeReader<'_>) -> anyhow::Result<Self> {
+        let mut clip = AnimationClip::default();
+        for _ in 0..reader.read_u64()? {
+            let id = Uuid::from_slice(reader.read_bytes()?)?;
+            for _ in 0..reader.read_u64()? {
+                let time_samples = reader.read_vec()?;
+                let outputs = match reader.read_u32()? {
+                    0 => AnimationChanelOutput::from_translation(
+                        reader.read_vec::<[f32; 3]>()?.into_iter(),
+                    ),
+                    1 => AnimationChanelOutput::from_rotation(
+                        reader.read_vec::<[f32; 4]>()?.into_iter(),
+                    ),
+                    2 => AnimationChanelOutput::from_scale(
+                        reader.read_vec::<[f32; 3]>()?.into_iter(),
+                    ),
+                    kind => bail!("unknown baked animation channel kind {kind}"),
+                };
+                clip.add_channel(id, AnimationChannel::new(time_samples, outputs));
+            }
+        }
+        Ok(clip)
+    }
+}
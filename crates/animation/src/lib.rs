@@ -13,8 +13,13 @@ pub mod transition;
 #[cfg(test)]
 mod tests {
     use essential::assets::{
-        Asset, asset_server::AssetServer, asset_store::AssetStore, handle::AssetHandle,
+        Asset,
+        asset_server::AssetServer,
+        asset_store::AssetStore,
+        bake::{Bake, BakeReader, BakeWriter},
+        handle::AssetHandle,
     };
+    use glam::{Quat, Vec3};
     use uuid::Uuid;
 
     use crate::blackboard::{AnimationBlackboard, AnimationBlackboardValue};
@@ -28,7 +33,7 @@ mod tests {
     use crate::node::{
         AnimationClipNode, AnimationClipNodeInstance, AnimationNode, AnimationNodeInstance,
     };
-    use crate::pose::PosePool;
+    use crate::pose::{JointPose, PosePool};
 
     fn unit_duration_clip() -> AnimationClip {
         let mut clip = AnimationClip::default();
@@ -131,6 +136,39 @@ mod tests {
         clip
     }
 
+    #[test]
+    fn clip_survives_a_bake_round_trip() {
+        let bone = Uuid::new_v4();
+        let mut clip = clip_with_duration(2.0);
+        clip.add_channel(
+            bone,
+            AnimationChannel::new(
+                vec![0.0, 1.0],
+                AnimationChanelOutput::from_rotation(
+                    [[0.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 0.0]].into_iter(),
+                ),
+            ),
+        );
+
+        let mut writer = BakeWriter::default();
+        clip.bake(&mut writer).unwrap();
+        let bytes = writer.into_bytes();
+        let restored = AnimationClip::unbake(&mut BakeReader::new(&bytes)).unwrap();
+
+        assert_eq!(restored.duration(), 2.0);
+        assert_eq!(restored.target_ids().count(), 2);
+        let sample = |clip: &AnimationClip| {
+            let mut pose = JointPose {
+                translation: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+                scale: Vec3::ONE,
+            };
+            clip.get_channels(&bone).unwrap()[0].sample_transform(0.5, &mut pose);
+            pose.rotation
+        };
+        assert_eq!(sample(&restored), sample(&clip));
+    }
+
     fn add_asset<A: Asset + Default + 'static>(
         server: &AssetServer,
         store: &mut AssetStore<A>,
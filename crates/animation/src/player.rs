@@ -5,6 +5,7 @@ use essential::{
     assets::{asset_store::AssetStore, handle::AssetHandle},
     transform::Transform,
 };
+use facet::Facet;
 use glam::Vec2;
 use uuid::Uuid;
 
@@ -186,7 +187,7 @@ impl AnimationPlayer {
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Facet)]
 pub struct AnimationHandleComponent {
     pub handle: AssetHandle<AnimationGraph>,
 }
@@ -4,6 +4,7 @@ use ecs::system::schedule::UpdateGroup;
 use crate::{
     clip::AnimationClip,
     graph::AnimationGraph,
+    player::AnimationHandleComponent,
     target::{animate_targets, initialize_animation_players, update_animation_players},
 };
 
@@ -13,6 +14,7 @@ impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut app::App) {
         app.register_asset::<AnimationClip>();
         app.register_asset::<AnimationGraph>();
+        app.register_reflection::<AnimationHandleComponent>();
 
         app.add_system(UpdateGroup::LateUpdate, animate_targets)
             .add_system(UpdateGroup::LateUpdate, initialize_animation_players)
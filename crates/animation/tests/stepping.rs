@@ -0,0 +1,97 @@
+//! Animation players driven through a headless app: assets arrive through the
+//! asset server, and `App::step` advances the clip by exactly the stepped time.
+
+use std::time::Duration;
+
+use animation::{
+    clip::{AnimationChanelOutput, AnimationChannel, AnimationClip},
+    graph::AnimationGraph,
+    node::{AnimationClipNode, AnimationPlayMode},
+    player::{AnimationHandleComponent, AnimationPlayer},
+    plugin::AnimationPlugin,
+};
+use app::{
+    App,
+    plugins::{AssetManagerPlugin, TimePlugin, TransformPlugin},
+};
+use ecs::Entity;
+use essential::{assets::asset_server::AssetServer, transform::Transform};
+use glam::{Mat4, Vec3};
+use mesh::skeleton::{Skeleton, SkeletonComponent};
+use uuid::Uuid;
+
+/// One bone sliding from the origin to `x = 1` over a second, played once.
+fn animated_bone() -> (App, Entity) {
+    let mut app = App::new();
+    app.register_plugin(TimePlugin)
+        .register_plugin(TransformPlugin)
+        .register_plugin(AssetManagerPlugin)
+        .register_plugin(AnimationPlugin);
+    app.register_asset::<Skeleton>();
+
+    let bone_id = Uuid::new_v4();
+    let mut clip = AnimationClip::default();
+    clip.add_channel(
+        bone_id,
+        AnimationChannel::new(
+            vec![0.0, 1.0],
+            AnimationChanelOutput::from_translation([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]].into_iter()),
+        ),
+    );
+
+    let server = app.get_resource::<AssetServer>().unwrap();
+    let skeleton = server.add(Skeleton::from(vec![Mat4::IDENTITY]));
+    let clip = server.add(clip);
+    let graph = server.add(AnimationGraph::from_node(
+        AnimationClipNode::new(clip).with_play_mode(AnimationPlayMode::PlayOnce),
+    ));
+
+    let world = app.world_mut();
+    let bone = world.spawn(Transform::default());
+    world.spawn((
+        AnimationPlayer::new(1),
+        SkeletonComponent::new(skeleton, vec![bone], vec![bone_id]),
+        AnimationHandleComponent::new(graph),
+    ));
+
+    (app, bone)
+}
+
+fn bone_translation(app: &App, bone: Entity) -> Vec3 {
+    app.world()
+        .get_component_for_entity::<Transform>(bone)
+        .unwrap()
+        .translation
+}
+
+#[test]
+fn stepping_advances_the_clip_by_the_stepped_time() {
+    let (mut app, bone) = animated_bone();
+
+    // Load the assets and start the player without advancing it.
+    app.step(Duration::ZERO);
+    app.step(Duration::ZERO);
+    assert!(bone_translation(&app, bone).abs_diff_eq(Vec3::ZERO, 1e-5));
+
+    // Bones are posed before the players advance in `LateUpdate`, so each
+    // advance shows up one frame later.
+    app.step(Duration::from_millis(250));
+    app.step(Duration::ZERO);
+    assert!(bone_translation(&app, bone).abs_diff_eq(Vec3::X * 0.25, 1e-4));
+
+    app.step(Duration::from_millis(500));
+    app.step(Duration::ZERO);
+    assert!(bone_translation(&app, bone).abs_diff_eq(Vec3::X * 0.75, 1e-4));
+}
+
+#[test]
+fn a_play_once_clip_holds_its_last_pose() {
+    let (mut app, bone) = animated_bone();
+    app.step(Duration::ZERO);
+    for _ in 0..8 {
+        app.step(Duration::from_millis(250));
+    }
+    app.step(Duration::ZERO);
+
+    assert!(bone_translation(&app, bone).abs_diff_eq(Vec3::X, 1e-5));
+}
@@ -1,7 +1,7 @@
 #[cfg(all(feature = "multithreaded", not(target_arch = "wasm32")))]
-use ecs::system::executor::multi_thread::MultiThreadedExecutor;
+use ecs::system::executor::multi_thread::MultiThreadedExecutor as DefaultExecutor;
 #[cfg(not(all(feature = "multithreaded", not(target_arch = "wasm32"))))]
-use ecs::system::executor::single_thread::SingleThreadedExecutor;
+use ecs::system::executor::single_thread::SingleThreadedExecutor as DefaultExecutor;
 use ecs::{
     component::Component,
     events::{
@@ -9,26 +9,42 @@ use ecs::{
         event_writer::EventWriter,
         Event,
     },
+    observer::{IntoObserver, ObserverEvent},
     resource::{ResMut, Resource},
+    state::{
+        apply_state_transition, enter_initial_state, NextState, State, StateScheduleLabel,
+        StateSchedules, States,
+    },
     system::schedule::{CompiledSchedules, Schedules, UpdateGroup},
     world::World,
-    IntoSystemConfig,
+    IntoSystemConfig, IntoSystemSetConfig,
 };
+use std::time::Duration;
+
 use facet::Facet;
 use log::info;
 use runner::AppExit;
 
 use essential::{
     assets::{
-        asset_server::AssetServer, asset_store::AssetStore, handle::AssetLifetimeEvent, Asset,
+        asset_server::AssetServer,
+        asset_store::AssetStore,
+        handle::AssetLifetimeEvent,
+        status::{AssetFailed, AssetLoaded},
+        Asset, LoadableAsset,
     },
-    time::Time,
+    time::{Instant, Time, TimeUpdateStrategy},
 };
 
-use crate::{plugins::PluginsState, runner::run_once};
+use crate::{
+    plugins::PluginsState,
+    runner::run_once,
+    sub_app::{AppLabel, SubApp, SubApps},
+};
 
 pub mod plugins;
 pub mod runner;
+pub mod sub_app;
 
 // Re-export the most commonly needed types so users don't have to know the module layout.
 pub use plugins::Plugin;
@@ -56,21 +72,53 @@ impl Plugin for HokeyPokeyPlugin {
 pub struct App {
     runner: runner::RunnerFn,
     world: World,
-    accumulated_fixed_time: f32,
+    accumulated_fixed_time: Duration,
     plugins: Vec<Box<dyn Plugin>>,
     plugin_state: PluginsState,
+    plugin_ready_timeout: Duration,
+    state_drivers: Vec<StateDriver>,
+    sub_apps: SubApps,
+}
+
+/// Type-erased entry points into the state machine of one [`States`] type.
+struct StateDriver {
+    compile: fn(&mut World),
+    enter_initial: fn(&mut World),
+    apply_transition: fn(&mut World),
+}
+
+impl StateDriver {
+    fn of<S: States>() -> Self {
+        Self {
+            compile: |world| {
+                let schedules = world
+                    .remove_resource::<StateSchedules<S>>()
+                    .expect("State schedules not found!");
+                world.insert_resource(schedules.compile::<DefaultExecutor>());
+            },
+            enter_initial: enter_initial_state::<S>,
+            apply_transition: apply_state_transition::<S>,
+        }
+    }
 }
 
 impl App {
+    /// How long [`step`](App::step) waits for every plugin to be [`ready`](Plugin::ready)
+    /// unless [`set_plugin_ready_timeout`](App::set_plugin_ready_timeout) says otherwise.
+    pub const DEFAULT_PLUGIN_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
     pub fn new() -> App {
         let mut world = World::new();
         world.init_resource::<Schedules>();
         Self {
             runner: Box::new(runner::run_once),
             world,
-            accumulated_fixed_time: 0.0,
+            accumulated_fixed_time: Duration::ZERO,
             plugins: Vec::new(),
             plugin_state: PluginsState::Building,
+            plugin_ready_timeout: Self::DEFAULT_PLUGIN_READY_TIMEOUT,
+            state_drivers: Vec::new(),
+            sub_apps: SubApps::default(),
         }
     }
 
@@ -85,7 +133,8 @@ impl App {
         self
     }
 
-    /// Registers an asset type, creating its [`AssetStore`] and wiring up the tracking system.
+    /// Registers an asset type, creating its [`AssetStore`], wiring up the tracking system and
+    /// registering its [`AssetLoaded`] and [`AssetFailed`] events.
     ///
     /// Requires [`AssetManagerPlugin`](plugins::AssetManagerPlugin) to already be registered.
     pub fn register_asset<A: Asset>(&mut self) -> &mut Self {
@@ -106,6 +155,17 @@ impl App {
         );
 
         self.world.insert_resource(asset_store);
+        self.register_event::<AssetLoaded<A>>()
+            .register_event::<AssetFailed<A>>()
+    }
+
+    /// Like [`register_asset`](Self::register_asset), and also lets saved handles to `A` be
+    /// loaded back from their path.
+    pub fn register_loadable_asset<A: LoadableAsset>(&mut self) -> &mut Self {
+        self.register_asset::<A>();
+        self.get_resource::<AssetServer>()
+            .expect("Asset Server not found")
+            .register_loader::<A>();
         self
     }
 
@@ -121,6 +181,13 @@ impl App {
         self
     }
 
+    /// Sets how long [`step`](App::step) waits for the plugins to be ready before it
+    /// panics, naming the ones that never were.
+    pub fn set_plugin_ready_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.plugin_ready_timeout = timeout;
+        self
+    }
+
     /// Registers a system in the given [`UpdateGroup`].
     pub fn add_system<M>(
         &mut self,
@@ -134,17 +201,109 @@ impl App {
         self
     }
 
-    /// Registers an event type, creating its [`EventChannel`] resource and a flush system.
+    /// Configures ordering and run conditions for a [`SystemSet`](ecs::SystemSet) in the given
+    /// [`UpdateGroup`].
+    pub fn configure_set(
+        &mut self,
+        update_group: UpdateGroup,
+        set: impl IntoSystemSetConfig,
+    ) -> &mut Self {
+        self.get_resource_mut::<Schedules>()
+            .expect("Schedules resource not found!")
+            .configure_set(update_group, set);
+
+        self
+    }
+
+    /// Initialises the state machine `S` in its default state.
+    ///
+    /// See [`insert_state`](App::insert_state).
+    pub fn init_state<S: States + Default>(&mut self) -> &mut Self {
+        self.insert_state(S::default())
+    }
+
+    /// Initialises the state machine `S` in `initial`.
+    ///
+    /// Inserts the [`State<S>`] and [`NextState<S>`] resources.  Transitions requested through
+    /// `NextState<S>` are applied at the start of every frame, before `FixedUpdate`; the
+    /// `OnEnter` schedule of the initial state runs right after `Startup`.
+    ///
+    /// Called once the app is built, the state's schedules are compiled straight away, so
+    /// they stay empty: state systems have to be added before the app is built.
+    pub fn insert_state<S: States>(&mut self, initial: S) -> &mut Self {
+        if self.get_resource::<State<S>>().is_some() {
+            info!("State {} already initialised", std::any::type_name::<S>());
+            return self;
+        }
+
+        self.insert_resource(State::new(initial));
+        self.insert_resource(NextState::<S>::new());
+        self.insert_resource(StateSchedules::<S>::new());
+
+        let driver = StateDriver::of::<S>();
+        if self.plugin_state == PluginsState::Finished {
+            (driver.compile)(&mut self.world);
+            (driver.enter_initial)(&mut self.world);
+        }
+        self.state_drivers.push(driver);
+        self
+    }
+
+    /// Registers a system in one of the [`OnEnter`](ecs::state::OnEnter),
+    /// [`OnExit`](ecs::state::OnExit) or [`OnTransition`](ecs::state::OnTransition) schedules
+    /// of the state machine `S`.
+    ///
+    /// Requires the state to be initialised with [`init_state`](App::init_state) or
+    /// [`insert_state`](App::insert_state).
+    pub fn add_state_system<S: States, M>(
+        &mut self,
+        label: impl StateScheduleLabel<S>,
+        system: impl IntoSystemConfig<M> + 'static,
+    ) -> &mut Self {
+        if self.plugin_state == PluginsState::Finished {
+            panic!(
+                "Can't add a system to the schedules of {} once the app is built",
+                std::any::type_name::<S>()
+            );
+        }
+        self.get_resource_mut::<StateSchedules<S>>()
+            .expect("State schedules not found, was the state initialised?")
+            .add_system(label, system);
+
+        self
+    }
+
+    /// Registers an event type, creating its [`EventChannel`] resource and a system that
+    /// updates it at the end of `LateUpdate`.
     ///
     /// Call this once per event type before any system uses [`EventWriter`] or [`EventReader`].
+    /// Events stay readable for the rest of the frame they are written in and all of the
+    /// next.
     pub fn register_event<T: Event + 'static>(&mut self) -> &mut Self {
-        let event_channel = EventChannel::<T>::new();
-
-        self.insert_resource(event_channel);
+        self.insert_resource(EventChannel::<T>::new());
         self.add_system(UpdateGroup::LateUpdate, update_event_channel::<T>);
         self
     }
 
+    /// Registers an event type that is mostly read from `FixedUpdate`, updating its
+    /// [`EventChannel`] at the end of every `LateFixedUpdate` pass instead of every frame.
+    ///
+    /// Events then stay readable for two fixed steps however many frames those take, so a
+    /// fixed-step reader never misses an event written on a frame with no fixed step.
+    pub fn register_fixed_event<T: Event + 'static>(&mut self) -> &mut Self {
+        self.insert_resource(EventChannel::<T>::new());
+        self.add_system(UpdateGroup::LateFixedUpdate, update_event_channel::<T>);
+        self
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
     /// Inserts a resource into the world (replacing any existing one of the same type).
     pub fn insert_resource<R: Resource>(&mut self, value: R) -> &mut Self {
         self.world.insert_resource(value);
@@ -179,8 +338,49 @@ impl App {
         self.insert_resource(output);
     }
 
-    /// Runs all per-frame schedules: FixedUpdate (as many times as needed), Update,
-    /// LateUpdate, Render, LateRender.  Also advances the world tick at the end.
+    /// Advances time by exactly `delta` and runs one frame, without a runner or window.
+    ///
+    /// Switches [`Time`] to [`TimeUpdateStrategy::Manual`], so the wall clock is ignored and
+    /// a sequence of steps always runs the same number of fixed updates.  The first call
+    /// waits for the plugins to be ready and runs `Startup`, like a runner would.  Meant for
+    /// headless simulation tests.
+    ///
+    /// # Panics
+    /// If some plugin is still not ready after the
+    /// [plugin ready timeout](App::set_plugin_ready_timeout).
+    pub fn step(&mut self, delta: Duration) -> &mut Self {
+        if self.plugin_state() != PluginsState::Finished {
+            self.wait_for_plugins();
+            self.finish_plugin_build();
+        }
+
+        if self.get_resource::<Time>().is_none() {
+            self.insert_resource(Time::new());
+        }
+        let time = self.get_resource_mut::<Time>().unwrap();
+        time.set_update_strategy(TimeUpdateStrategy::Manual);
+        time.advance(delta);
+
+        self.update();
+        self
+    }
+
+    /// Runs `frames` frames of one fixed step each, so `FixedUpdate` runs once per frame
+    /// at a time scale of `1.0`.  See [`step`](App::step).
+    pub fn step_fixed(&mut self, frames: u32) -> &mut Self {
+        for _ in 0..frames {
+            let fixed_delta = self
+                .get_resource::<Time>()
+                .map_or_else(|| Time::new().fixed_delta(), Time::fixed_delta);
+            self.step(fixed_delta);
+        }
+        self
+    }
+
+    /// Applies pending state transitions, then runs all per-frame schedules: FixedUpdate (as
+    /// many times as the virtual clock needs, up to [`Time::max_fixed_steps`]), Update,
+    /// LateUpdate, Render, LateRender.  Then extracts into and updates each [`SubApp`], and
+    /// advances the world tick at the end.
     pub fn update(&mut self) {
         profiling::scope!("App::update");
 
@@ -188,19 +388,42 @@ impl App {
             .get_resource::<Time>()
             .expect("Time resource not found");
 
-        self.accumulated_fixed_time += time.delta().as_secs_f32();
+        let delta = time.delta();
+        let fixed_delta = time.fixed_delta();
+        let max_fixed_steps = time.max_fixed_steps();
+        self.accumulated_fixed_time += delta;
+
+        for driver in &self.state_drivers {
+            profiling::scope!("state_transition");
+            (driver.apply_transition)(&mut self.world);
+        }
 
         let mut schedules = self
             .remove_resource::<CompiledSchedules>()
             .expect("Compiled schedules not found!");
 
-        while self.accumulated_fixed_time >= Time::fixed_delta_time() {
+        let mut fixed_steps = 0;
+        while self.accumulated_fixed_time >= fixed_delta {
+            if fixed_steps == max_fixed_steps {
+                // Catching up any further would only make the next frame longer still.
+                let remainder = Duration::from_nanos(
+                    (self.accumulated_fixed_time.as_nanos() % fixed_delta.as_nanos()) as u64,
+                );
+                log::debug!(
+                    "Dropping {:.3} s of fixed updates after {fixed_steps} steps this frame",
+                    (self.accumulated_fixed_time - remainder).as_secs_f32()
+                );
+                self.accumulated_fixed_time = remainder;
+                break;
+            }
+
             profiling::scope!("fixed_update_step");
             schedules.fixed_update(&mut self.world);
-            self.accumulated_fixed_time -= Time::fixed_delta_time();
+            self.accumulated_fixed_time -= fixed_delta;
+            fixed_steps += 1;
         }
 
-        let fixed_overstep = self.accumulated_fixed_time;
+        let fixed_overstep = self.accumulated_fixed_time.as_secs_f32();
         if let Some(time) = self.get_resource_mut::<Time>() {
             time.set_fixed_overstep(fixed_overstep);
         }
@@ -210,6 +433,11 @@ impl App {
 
         self.world.insert_resource(schedules);
 
+        for sub_app in self.sub_apps.iter_mut() {
+            sub_app.extract(&mut self.world);
+            sub_app.update();
+        }
+
         {
             profiling::scope!("world_tick");
             self.world.tick();
@@ -226,6 +454,40 @@ impl App {
         self
     }
 
+    /// Registers a global observer, run every time its event is triggered.
+    ///
+    /// See [`World::add_observer`].
+    pub fn add_observer<E: ObserverEvent, M>(
+        &mut self,
+        observer: impl IntoObserver<E, M>,
+    ) -> &mut Self {
+        self.world.add_observer(observer);
+        self
+    }
+
+    /// Adds a [`SubApp`] under `label`, replacing any sub-app already there.
+    ///
+    /// Sub-apps run after the main app each frame, in the order they were inserted.
+    pub fn insert_sub_app<L: AppLabel>(&mut self, _label: L, mut sub_app: SubApp) -> &mut Self {
+        if self.plugin_state == PluginsState::Finished {
+            sub_app.finish();
+        }
+        self.sub_apps.insert::<L>(sub_app);
+        self
+    }
+
+    pub fn remove_sub_app<L: AppLabel>(&mut self, _label: L) -> Option<SubApp> {
+        self.sub_apps.remove::<L>()
+    }
+
+    pub fn sub_app<L: AppLabel>(&self, _label: L) -> Option<&SubApp> {
+        self.sub_apps.get::<L>()
+    }
+
+    pub fn sub_app_mut<L: AppLabel>(&mut self, _label: L) -> Option<&mut SubApp> {
+        self.sub_apps.get_mut::<L>()
+    }
+
     /// Polls each plugin's [`ready`](Plugin::ready) method and transitions the state machine.
     ///
     /// Returns the current [`PluginsState`].
@@ -246,6 +508,28 @@ impl App {
         next_state
     }
 
+    /// Polls the plugins until they are all ready, panicking with the names of the
+    /// stragglers once the plugin ready timeout runs out.
+    fn wait_for_plugins(&mut self) {
+        let start = Instant::now();
+        while self.plugin_state() != PluginsState::Ready {
+            if start.elapsed() > self.plugin_ready_timeout {
+                let pending: Vec<&str> = self
+                    .plugins
+                    .iter()
+                    .filter(|plugin| !plugin.ready(self))
+                    .map(|plugin| plugin.name())
+                    .collect();
+                panic!(
+                    "Plugins not ready after {:?}: {}",
+                    self.plugin_ready_timeout,
+                    pending.join(", ")
+                );
+            }
+            std::thread::yield_now();
+        }
+    }
+
     /// Calls [`Plugin::finish`] on every registered plugin, then runs the `Startup` schedule.
     ///
     /// Should be called once after all plugins have been registered and all async work is ready.
@@ -270,6 +554,14 @@ impl App {
         schedules.startup(&mut self.world);
 
         self.insert_resource(schedules);
+
+        for driver in &self.state_drivers {
+            (driver.enter_initial)(&mut self.world);
+        }
+
+        for sub_app in self.sub_apps.iter_mut() {
+            sub_app.finish();
+        }
     }
 
     fn compile_schedules(&mut self) {
@@ -277,12 +569,11 @@ impl App {
             .remove_resource::<Schedules>()
             .expect("Schedules resource not found!");
 
-        #[cfg(all(feature = "multithreaded", not(target_arch = "wasm32")))]
-        let compiled_schedules = schedules.compile::<MultiThreadedExecutor>();
-        #[cfg(not(all(feature = "multithreaded", not(target_arch = "wasm32"))))]
-        let compiled_schedules = schedules.compile::<SingleThreadedExecutor>();
+        self.insert_resource(schedules.compile::<DefaultExecutor>());
 
-        self.insert_resource(compiled_schedules);
+        for driver in &self.state_drivers {
+            (driver.compile)(&mut self.world);
+        }
     }
 }
 
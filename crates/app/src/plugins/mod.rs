@@ -1,6 +1,10 @@
 use ecs::system::schedule::UpdateGroup;
 use essential::assets::asset_server::{handle_asset_load_events, AssetServer};
+#[cfg(not(target_arch = "wasm32"))]
+use essential::assets::bake::BakeCache;
 use essential::assets::handle::AssetLifetimeEvent;
+#[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+use essential::assets::hot_reload::{reload_changed_assets, AssetWatcher};
 use essential::time::{FrameStats, Time};
 
 use ecs::resource::{Res, ResMut};
@@ -57,7 +61,7 @@ fn update_time(mut time: ResMut<Time>) {
 }
 
 fn update_frame_stats(time: Res<Time>, mut stats: ResMut<FrameStats>) {
-    let delta = time.delta();
+    let delta = time.real_delta();
     stats.push(delta);
 
     // Opt-in visibility without any UI: RUST_LOG=info prints a summary once
@@ -83,13 +87,31 @@ impl Plugin for TimePlugin {
 }
 
 /// Plugin that inserts an [`AssetServer`] resource and the asset-event handler.
+///
+/// On native targets, loaders read baked assets from the `bake-cache` directory next to the
+/// executable if there is one.  With the opt-in `hot-reload` feature, it also watches the `res`
+/// directory next to the executable and reloads assets whose source file changed.
 pub struct AssetManagerPlugin;
 
 impl Plugin for AssetManagerPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(AssetServer::new());
+        let asset_server = AssetServer::new();
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(bake_cache) = BakeCache::find() {
+            asset_server.set_bake_cache(bake_cache);
+        }
+        app.insert_resource(asset_server);
         app.register_event::<AssetLifetimeEvent>();
         app.add_system(UpdateGroup::LateUpdate, handle_asset_load_events);
+
+        #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+        match AssetWatcher::for_default_source() {
+            Ok(watcher) => {
+                app.insert_resource(watcher);
+                app.add_system(UpdateGroup::Update, reload_changed_assets);
+            }
+            Err(err) => log::warn!("Asset hot reloading disabled: {err}"),
+        }
     }
 }
 
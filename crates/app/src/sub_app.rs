@@ -0,0 +1,182 @@
+use std::any::TypeId;
+
+#[cfg(all(feature = "multithreaded", not(target_arch = "wasm32")))]
+use ecs::system::executor::multi_thread::MultiThreadedExecutor as DefaultExecutor;
+#[cfg(not(all(feature = "multithreaded", not(target_arch = "wasm32"))))]
+use ecs::system::executor::single_thread::SingleThreadedExecutor as DefaultExecutor;
+use ecs::{
+    resource::Resource,
+    system::{
+        extract::MainWorld,
+        schedule::{CompiledSchedules, Schedules, UpdateGroup},
+    },
+    world::World,
+    IntoSystemConfig, IntoSystemSetConfig,
+};
+
+/// A marker type naming a [`SubApp`], e.g. `struct RenderApp;`.
+pub trait AppLabel: 'static {}
+
+/// An app nested in [`App`](crate::App), with its own [`World`] and schedules.
+///
+/// Each frame, after the main world's `LateRender`, the sub-app runs its `Extract` schedule
+/// with the main world lent to it as a [`MainWorld`] resource, then its own `Update`,
+/// `LateUpdate`, `Render` and `LateRender` against its own world.  Extraction is the only
+/// point where the two worlds meet, so the rest of a sub-app's frame does not need the main
+/// world and could later run on another thread while the main world simulates the next frame.
+///
+/// Its `Startup` runs right after the main app's.  `FixedUpdate` never runs in a sub-app.
+pub struct SubApp {
+    world: World,
+}
+
+impl SubApp {
+    pub fn new() -> Self {
+        let mut world = World::new();
+        world.init_resource::<Schedules>();
+        Self { world }
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Registers a system in the given [`UpdateGroup`] of this sub-app.
+    pub fn add_system<M>(
+        &mut self,
+        update_group: UpdateGroup,
+        system: impl IntoSystemConfig<M> + 'static,
+    ) -> &mut Self {
+        self.world
+            .get_resource_mut::<Schedules>()
+            .expect("Schedules resource not found!")
+            .add_system(update_group, system);
+        self
+    }
+
+    /// Configures ordering and run conditions for a [`SystemSet`](ecs::SystemSet) in the
+    /// given [`UpdateGroup`] of this sub-app.
+    pub fn configure_set(
+        &mut self,
+        update_group: UpdateGroup,
+        set: impl IntoSystemSetConfig,
+    ) -> &mut Self {
+        self.world
+            .get_resource_mut::<Schedules>()
+            .expect("Schedules resource not found!")
+            .configure_set(update_group, set);
+        self
+    }
+
+    /// Inserts a resource into the sub-app's world (replacing any existing one of the same
+    /// type).
+    pub fn insert_resource<R: Resource>(&mut self, value: R) -> &mut Self {
+        self.world.insert_resource(value);
+        self
+    }
+
+    pub fn get_resource<R: Resource>(&self) -> Option<&R> {
+        self.world.get_resource()
+    }
+
+    pub fn get_resource_mut<R: Resource>(&mut self) -> Option<&mut R> {
+        self.world.get_resource_mut()
+    }
+
+    /// Compiles the schedules and runs `Startup`.
+    pub(crate) fn finish(&mut self) {
+        let schedules = self
+            .world
+            .remove_resource::<Schedules>()
+            .expect("Schedules resource not found!");
+        let mut schedules = schedules.compile::<DefaultExecutor>();
+        schedules.startup(&mut self.world);
+        self.world.insert_resource(schedules);
+    }
+
+    /// Runs the `Extract` schedule with `main_world` lent to it.
+    pub fn extract(&mut self, main_world: &mut World) {
+        profiling::scope!("SubApp::extract");
+
+        self.world
+            .insert_resource(MainWorld::new(std::mem::replace(main_world, World::new())));
+        self.with_schedules(|schedules, world| schedules.extract(world));
+        *main_world = self
+            .world
+            .remove_resource::<MainWorld>()
+            .expect("MainWorld removed during extraction")
+            .into_inner();
+    }
+
+    /// Runs the sub-app's own frame, then advances its world tick.
+    pub fn update(&mut self) {
+        profiling::scope!("SubApp::update");
+
+        self.with_schedules(|schedules, world| {
+            schedules.update(world);
+            schedules.render(world);
+        });
+        self.world.tick();
+    }
+
+    fn with_schedules(&mut self, f: impl FnOnce(&mut CompiledSchedules, &mut World)) {
+        let mut schedules = self
+            .world
+            .remove_resource::<CompiledSchedules>()
+            .expect("Compiled schedules not found! (was the sub-app inserted after finish?)");
+        f(&mut schedules, &mut self.world);
+        self.world.insert_resource(schedules);
+    }
+}
+
+impl Default for SubApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The sub-apps of an [`App`](crate::App), in insertion order.
+#[derive(Default)]
+pub(crate) struct SubApps {
+    sub_apps: Vec<(TypeId, SubApp)>,
+}
+
+impl SubApps {
+    pub(crate) fn insert<L: AppLabel>(&mut self, sub_app: SubApp) {
+        let id = TypeId::of::<L>();
+        match self.sub_apps.iter_mut().find(|(label, _)| *label == id) {
+            Some((_, existing)) => *existing = sub_app,
+            None => self.sub_apps.push((id, sub_app)),
+        }
+    }
+
+    pub(crate) fn remove<L: AppLabel>(&mut self) -> Option<SubApp> {
+        let id = TypeId::of::<L>();
+        let index = self.sub_apps.iter().position(|(label, _)| *label == id)?;
+        Some(self.sub_apps.remove(index).1)
+    }
+
+    pub(crate) fn get<L: AppLabel>(&self) -> Option<&SubApp> {
+        let id = TypeId::of::<L>();
+        self.sub_apps
+            .iter()
+            .find(|(label, _)| *label == id)
+            .map(|(_, sub_app)| sub_app)
+    }
+
+    pub(crate) fn get_mut<L: AppLabel>(&mut self) -> Option<&mut SubApp> {
+        let id = TypeId::of::<L>();
+        self.sub_apps
+            .iter_mut()
+            .find(|(label, _)| *label == id)
+            .map(|(_, sub_app)| sub_app)
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut SubApp> {
+        self.sub_apps.iter_mut().map(|(_, sub_app)| sub_app)
+    }
+}
@@ -0,0 +1,115 @@
+//! Manual time stepping: how many fixed and variable updates `App::step` runs, and that the
+//! wall clock never leaks into a headless run.
+
+use std::time::Duration;
+
+use app::{
+    plugins::{Plugin, TimePlugin},
+    App,
+};
+use ecs::resource::{ResMut, Resource};
+use ecs::system::schedule::UpdateGroup;
+use essential::time::Time;
+
+#[derive(Resource, Default)]
+struct Counts {
+    startup: u32,
+    fixed: u32,
+    update: u32,
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.register_plugin(TimePlugin);
+    app.insert_resource(Counts::default());
+    app.add_system(UpdateGroup::Startup, |mut counts: ResMut<Counts>| {
+        counts.startup += 1
+    });
+    app.add_system(UpdateGroup::FixedUpdate, |mut counts: ResMut<Counts>| {
+        counts.fixed += 1
+    });
+    app.add_system(UpdateGroup::Update, |mut counts: ResMut<Counts>| {
+        counts.update += 1
+    });
+    app
+}
+
+fn counts(app: &App) -> (u32, u32, u32) {
+    let counts = app.get_resource::<Counts>().unwrap();
+    (counts.startup, counts.fixed, counts.update)
+}
+
+#[test]
+fn step_fixed_runs_one_fixed_update_per_frame() {
+    let mut app = test_app();
+    app.step_fixed(3);
+
+    assert_eq!(counts(&app), (1, 3, 3));
+
+    let time = app.get_resource::<Time>().unwrap();
+    assert_eq!(time.elapsed(), time.fixed_delta() * 3);
+    assert_eq!(time.fixed_overstep(), 0.0);
+}
+
+#[test]
+fn step_accumulates_partial_fixed_steps() {
+    let mut app = test_app();
+    let time = app.get_resource_mut::<Time>().unwrap();
+    time.set_fixed_delta_time(0.25);
+    let fixed_delta = time.fixed_delta();
+
+    app.step(fixed_delta / 2);
+    assert_eq!(counts(&app), (1, 0, 1));
+
+    app.step(fixed_delta / 2);
+    assert_eq!(counts(&app), (1, 1, 2));
+
+    app.step(fixed_delta * 2);
+    assert_eq!(counts(&app), (1, 3, 3));
+}
+
+#[test]
+fn paused_and_scaled_time_change_the_fixed_step_count() {
+    let mut app = test_app();
+    app.get_resource_mut::<Time>().unwrap().pause();
+    app.step_fixed(4);
+    assert_eq!(counts(&app), (1, 0, 4));
+
+    let time = app.get_resource_mut::<Time>().unwrap();
+    time.unpause();
+    time.set_time_scale(2.0);
+    app.step_fixed(2);
+    assert_eq!(counts(&app), (1, 4, 6));
+}
+
+#[test]
+fn stepping_ignores_the_wall_clock() {
+    let mut app = test_app();
+    app.step(Duration::ZERO);
+    std::thread::sleep(Duration::from_millis(50));
+    app.step(Duration::ZERO);
+
+    let time = app.get_resource::<Time>().unwrap();
+    assert_eq!(time.elapsed(), Duration::ZERO);
+    assert_eq!(time.real_elapsed(), Duration::ZERO);
+    assert_eq!(counts(&app), (1, 0, 2));
+}
+
+struct NeverReady;
+
+impl Plugin for NeverReady {
+    fn build(&self, _app: &mut App) {}
+
+    fn ready(&self, _app: &App) -> bool {
+        false
+    }
+}
+
+#[test]
+#[should_panic(expected = "NeverReady")]
+fn step_gives_up_on_a_plugin_that_never_becomes_ready() {
+    let mut app = test_app();
+    app.register_plugin(NeverReady);
+    app.set_plugin_ready_timeout(Duration::from_millis(10));
+    app.step(Duration::ZERO);
+}
@@ -0,0 +1,49 @@
+//! State machines inserted after the app is built: transitions still run, and the systems
+//! they would need are rejected up front instead of crashing a later frame.
+
+use std::time::Duration;
+
+use app::{plugins::TimePlugin, App};
+use ecs::state::{NextState, OnEnter, State, States};
+
+#[derive(States, Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+enum Phase {
+    #[default]
+    Loading,
+    Playing,
+}
+
+fn built_app() -> App {
+    let mut app = App::new();
+    app.register_plugin(TimePlugin);
+    app.step(Duration::ZERO);
+    app
+}
+
+#[test]
+fn a_state_inserted_after_build_applies_transitions() {
+    let mut app = built_app();
+    app.init_state::<Phase>();
+    app.step(Duration::ZERO);
+    assert_eq!(
+        app.get_resource::<State<Phase>>().unwrap().get(),
+        &Phase::Loading
+    );
+
+    app.get_resource_mut::<NextState<Phase>>()
+        .unwrap()
+        .set(Phase::Playing);
+    app.step(Duration::ZERO);
+    assert_eq!(
+        app.get_resource::<State<Phase>>().unwrap().get(),
+        &Phase::Playing
+    );
+}
+
+#[test]
+#[should_panic(expected = "once the app is built")]
+fn state_systems_cannot_be_added_after_build() {
+    let mut app = built_app();
+    app.init_state::<Phase>();
+    app.add_state_system(OnEnter(Phase::Playing), || {});
+}
@@ -0,0 +1,137 @@
+//! Sub-apps: extraction from the main world into a sub-app's own world, and the order in
+//! which a sub-app's schedules run.
+
+use app::{
+    sub_app::{AppLabel, SubApp},
+    App,
+};
+use ecs::{
+    system::{extract::Extract, schedule::UpdateGroup},
+    Changed, CommandQueue, Component, Entity, Query, ResMut, Resource,
+};
+use essential::time::Time;
+
+struct TestApp;
+impl AppLabel for TestApp {}
+
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+struct Position(f32);
+
+/// Render-side copy of a main-world entity's position.
+#[derive(Component)]
+struct ExtractedPosition {
+    main_entity: Entity,
+    position: Position,
+}
+
+#[derive(Resource, Default)]
+struct Log(Vec<&'static str>);
+
+fn extract_positions(
+    positions: Extract<Query<(Entity, &Position), Changed<Position>>>,
+    mut commands: CommandQueue,
+) {
+    for (main_entity, position) in positions.iter() {
+        commands.spawn(ExtractedPosition {
+            main_entity,
+            position: *position,
+        });
+    }
+}
+
+fn test_app() -> App {
+    let mut app = App::new();
+    app.insert_resource(Time::new())
+        .insert_resource(Log::default());
+
+    let mut sub_app = SubApp::new();
+    sub_app
+        .insert_resource(Log::default())
+        .add_system(UpdateGroup::Startup, |mut log: ResMut<Log>| {
+            log.0.push("startup")
+        })
+        .add_system(UpdateGroup::Extract, extract_positions)
+        .add_system(UpdateGroup::Extract, |mut log: ResMut<Log>| {
+            log.0.push("extract")
+        })
+        .add_system(UpdateGroup::Update, |mut log: ResMut<Log>| {
+            log.0.push("update")
+        })
+        .add_system(UpdateGroup::Render, |mut log: ResMut<Log>| {
+            log.0.push("render")
+        });
+    app.insert_sub_app(TestApp, sub_app);
+    app
+}
+
+fn extracted(app: &App) -> Vec<(Entity, Position)> {
+    let world = app.sub_app(TestApp).unwrap().world();
+    world
+        .archetypes()
+        .iter()
+        .flat_map(|archetype| archetype.entities())
+        .filter_map(|&entity| world.get_component_for_entity::<ExtractedPosition>(entity))
+        .map(|extracted| (extracted.main_entity, extracted.position))
+        .collect()
+}
+
+#[test]
+fn extract_copies_changed_components_into_the_sub_app_world() {
+    let mut app = test_app();
+    app.finish_plugin_build();
+
+    let main_entity = app.world_mut().spawn(Position(1.0));
+    app.update();
+    assert_eq!(extracted(&app), vec![(main_entity, Position(1.0))]);
+
+    // Unchanged components are not extracted again.
+    app.update();
+    assert_eq!(extracted(&app).len(), 1);
+
+    // The main world is handed back intact.
+    assert_eq!(
+        app.world()
+            .get_component_for_entity::<Position>(main_entity),
+        Some(&Position(1.0))
+    );
+    assert!(app.get_resource::<Log>().unwrap().0.is_empty());
+}
+
+#[test]
+fn sub_app_runs_startup_once_then_extract_before_its_own_frame() {
+    let mut app = test_app();
+    app.finish_plugin_build();
+    app.update();
+    app.update();
+
+    let sub_app = app.sub_app(TestApp).unwrap();
+    assert_eq!(
+        sub_app.get_resource::<Log>().unwrap().0,
+        ["startup", "extract", "update", "render", "extract", "update", "render"]
+    );
+}
+
+#[test]
+#[should_panic(expected = "only lent for reading")]
+fn extract_rejects_writes_to_the_main_world() {
+    let mut sub_app = SubApp::new();
+    sub_app.add_system(
+        UpdateGroup::Extract,
+        |_positions: Extract<Query<&mut Position>>| {},
+    );
+    let mut app = App::new();
+    app.insert_resource(Time::new());
+    app.insert_sub_app(TestApp, sub_app);
+    app.finish_plugin_build();
+}
+
+#[test]
+#[should_panic(expected = "never applied")]
+fn extract_rejects_deferred_changes_to_the_main_world() {
+    let mut sub_app = SubApp::new();
+    sub_app.add_system(UpdateGroup::Extract, |_commands: Extract<CommandQueue>| {});
+    let mut app = App::new();
+    app.insert_resource(Time::new());
+    app.insert_sub_app(TestApp, sub_app);
+    app.finish_plugin_build();
+}
@@ -0,0 +1,162 @@
+//! Pre-bakes every asset in a `res` directory into a bake cache.
+//!
+//! Usage: `asset-baker <path/to/res> [path/to/bake-cache]`.  The cache defaults to a
+//! `bake-cache` directory next to `res`; ship it next to the executable, where
+//! `AssetManagerPlugin` picks it up.  Assets are loaded exactly as at runtime, so every loader
+//! that supports baking writes its results on the way.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use animation::clip::AnimationClip;
+use anyhow::{Context, bail, ensure};
+use ecs::world::World;
+use essential::assets::{
+    Asset, AssetId, LoadableAsset,
+    asset_server::{AssetServer, handle_asset_load_events},
+    asset_store::AssetStore,
+    bake::BakeCache,
+    handle::AssetHandle,
+    source::DirectorySource,
+    status::AssetStatus,
+};
+use gltf_loader::loader::GLTFScene;
+use obj_loader::{mtl_loader::MTLMaterial, obj_loader::OBJAsset};
+use render::assets::{
+    material::StandardMaterial, mesh::Mesh, skeleton::Skeleton, texture::Texture,
+};
+
+const USAGE: &str = "usage: asset-baker <path/to/res> [path/to/bake-cache]";
+
+/// Keeps a requested asset alive until it is baked.
+enum Request {
+    Scene(AssetHandle<GLTFScene>),
+    Obj(AssetHandle<OBJAsset>),
+    Texture(AssetHandle<Texture>),
+}
+
+impl Request {
+    fn id(&self) -> AssetId {
+        match self {
+            Request::Scene(handle) => handle.id(),
+            Request::Obj(handle) => handle.id(),
+            Request::Texture(handle) => handle.id(),
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    env_logger::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (res_dir, cache_dir) = match args.as_slice() {
+        [res_dir] => (PathBuf::from(res_dir), None),
+        [res_dir, cache_dir] => (PathBuf::from(res_dir), Some(PathBuf::from(cache_dir))),
+        _ => bail!(USAGE),
+    };
+
+    let res_dir = res_dir
+        .canonicalize()
+        .with_context(|| format!("no asset directory '{}'", res_dir.display()))?;
+    // Asset paths start with `res/`, and bake keys with them.
+    ensure!(
+        res_dir.file_name() == Some("res".as_ref()),
+        "the asset directory must be named 'res'"
+    );
+    let root = res_dir.parent().context("'res' has no parent directory")?;
+    let cache_dir = cache_dir.unwrap_or_else(|| root.join("bake-cache"));
+
+    // Loaders read paths relative to `root`, the way they are relative to the executable's
+    // directory at runtime.  The glTF importer reads its files relative to the working
+    // directory.
+    std::env::set_current_dir(root)
+        .with_context(|| format!("failed to enter '{}'", root.display()))?;
+
+    let mut world = World::new();
+    let mut server = AssetServer::new();
+    server.set_default_source(DirectorySource::new(root));
+    server.set_bake_cache(BakeCache::new(&cache_dir));
+    register::<GLTFScene>(&mut world, &mut server);
+    register::<OBJAsset>(&mut world, &mut server);
+    register::<MTLMaterial>(&mut world, &mut server);
+    register::<Mesh>(&mut world, &mut server);
+    register::<Skeleton>(&mut world, &mut server);
+    register::<Texture>(&mut world, &mut server);
+    register::<StandardMaterial>(&mut world, &mut server);
+    register::<AnimationClip>(&mut world, &mut server);
+    world.insert_resource(server.clone());
+
+    let mut requests = Vec::new();
+    for file in files_in(&res_dir)? {
+        let path = file
+            .strip_prefix(root)
+            .unwrap()
+            .to_str()
+            .with_context(|| format!("asset path '{}' is not valid UTF-8", file.display()))?
+            .to_string();
+        let extension = file
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        let request = match extension.as_deref() {
+            Some("gltf" | "glb") => Request::Scene(load(&server, &path)),
+            Some("obj") => Request::Obj(load(&server, &path)),
+            Some("png" | "jpg" | "jpeg") => Request::Texture(load(&server, &path)),
+            _ => continue,
+        };
+        requests.push((path, request));
+    }
+
+    while requests
+        .iter()
+        .any(|(_, request)| server.status(request.id()) == Some(AssetStatus::Loading))
+    {
+        handle_asset_load_events(&mut world);
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    let mut failed = 0;
+    for (path, request) in &requests {
+        if let Some(AssetStatus::Failed(error)) = server.status(request.id()) {
+            log::error!("Failed to bake '{path}': {error}");
+            failed += 1;
+        }
+    }
+    println!(
+        "Baked {} of {} assets into '{}'",
+        requests.len() - failed,
+        requests.len(),
+        cache_dir.display()
+    );
+    ensure!(failed == 0, "{failed} assets failed to bake");
+    Ok(())
+}
+
+fn register<A: Asset>(world: &mut World, server: &mut AssetServer) {
+    let store = AssetStore::<A>::new();
+    server.register_asset(&store);
+    world.insert_resource(store);
+}
+
+fn load<A: LoadableAsset>(server: &AssetServer, path: &str) -> AssetHandle<A> {
+    log::info!("Baking '{path}'");
+    server.load::<A>(path)
+}
+
+/// Returns every file below `dir`, recursively.
+fn files_in(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("failed to list '{}'", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files_in(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
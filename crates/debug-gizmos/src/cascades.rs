@@ -0,0 +1,71 @@
+use app::Plugin;
+use color::Color;
+use ecs::{query::Query, system::schedule::UpdateGroup};
+use glam::Vec3;
+use render::components::DirectionalCascades;
+
+use crate::gizmos::DebugGizmos;
+
+/// One colour per cascade, nearest first: its frustum slice, then the dimmer
+/// bounds of its shadow map.
+const CASCADE_COLORS: [(Color, Color); 4] = [
+    (
+        Color::rgba(1.0, 0.0, 0.0, 1.0),
+        Color::rgba(0.5, 0.0, 0.0, 1.0),
+    ),
+    (
+        Color::rgba(0.0, 1.0, 0.0, 1.0),
+        Color::rgba(0.0, 0.5, 0.0, 1.0),
+    ),
+    (
+        Color::rgba(0.0, 0.0, 1.0, 1.0),
+        Color::rgba(0.0, 0.0, 0.5, 1.0),
+    ),
+    (
+        Color::rgba(1.0, 1.0, 0.0, 1.0),
+        Color::rgba(0.5, 0.5, 0.0, 1.0),
+    ),
+];
+
+/// Draws the shadow cascades of every shadow-casting directional light.
+///
+/// Each cascade's slice of the camera frustum is outlined in its own colour,
+/// along with the box its shadow map covers. Seen from the camera the cascades
+/// were fitted to, the slices only show as their far edges; the view is most
+/// useful from a second camera, or after moving the camera away and looking
+/// back.
+///
+/// Requires [`DebugGizmosPlugin`](crate::plugin::DebugGizmosPlugin).
+pub struct ShadowCascadeGizmosPlugin;
+
+impl Plugin for ShadowCascadeGizmosPlugin {
+    fn build(&self, app: &mut app::App) {
+        // Runs before `render_gizmos`, which `DebugGizmosPlugin` only adds in
+        // its `finish`.
+        app.add_system(UpdateGroup::Render, draw_shadow_cascades);
+    }
+}
+
+fn draw_shadow_cascades(lights: Query<&DirectionalCascades>, mut gizmos: DebugGizmos) {
+    for cascades in lights.iter() {
+        for (cascade, (slice_color, bounds_color)) in cascades.cascades().iter().zip(CASCADE_COLORS)
+        {
+            gizmos.frustum(&cascade.corners, slice_color);
+
+            // The shadow map covers the whole of its clip-space cube.
+            let world_from_clip = cascade.view_proj.inverse();
+            let bounds = [
+                Vec3::new(-1.0, -1.0, 0.0),
+                Vec3::new(1.0, -1.0, 0.0),
+                Vec3::new(1.0, 1.0, 0.0),
+                Vec3::new(-1.0, 1.0, 0.0),
+                Vec3::new(-1.0, -1.0, 1.0),
+                Vec3::new(1.0, -1.0, 1.0),
+                Vec3::new(1.0, 1.0, 1.0),
+                Vec3::new(-1.0, 1.0, 1.0),
+            ]
+            .map(|corner| world_from_clip.project_point3(corner));
+            gizmos.frustum(&bounds, bounds_color);
+        }
+    }
+}
@@ -162,6 +162,21 @@ impl<'w> DebugGizmos<'w> {
         }
     }
 
+    /// Draws the twelve edges of a hexahedron given its eight corners: the
+    /// near face first, then the far face, each wound the same way.
+    ///
+    /// Fits the corners of a camera frustum slice (see
+    /// [`Camera::frustum_corners`](render::components::Camera::frustum_corners))
+    /// or of any box, however it is skewed.
+    pub fn frustum(&mut self, corners: &[Vec3; 8], color: Color) {
+        for i in 0..4 {
+            let next = (i + 1) % 4;
+            self.line(corners[i], corners[next], color);
+            self.line(corners[i + 4], corners[next + 4], color);
+            self.line(corners[i], corners[i + 4], color);
+        }
+    }
+
     /// Convenience wrapper around [`cuboid`](Self::cuboid): an axis-aligned box
     /// of the given `size` centred at `center`.
     pub fn cuboid_min_max(&mut self, center: Vec3, size: Vec3, color: Color) {
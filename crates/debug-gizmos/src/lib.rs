@@ -16,7 +16,11 @@
 //!
 //! Drawing is immediate mode: shapes must be re-issued every frame to stay
 //! visible.
+//!
+//! [`ShadowCascadeGizmosPlugin`](cascades::ShadowCascadeGizmosPlugin) adds a
+//! debug view of directional lights' shadow cascades on top.
 
+pub mod cascades;
 pub mod gizmos;
 pub mod pipeline;
 pub mod plugin;
@@ -24,6 +28,7 @@ pub mod render;
 pub mod storage;
 pub mod vertex;
 
+pub use cascades::ShadowCascadeGizmosPlugin;
 pub use gizmos::DebugGizmos;
 pub use plugin::DebugGizmosPlugin;
 pub use storage::GizmoStorage;
@@ -17,7 +17,6 @@ pub struct DebugGizmosPlugin;
 impl Plugin for DebugGizmosPlugin {
     fn build(&self, app: &mut app::App) {
         app.insert_resource(GizmoStorage::default());
-        app.add_system(UpdateGroup::Render, render_gizmos);
     }
 
     fn finish(&self, app: &mut app::App) {
@@ -38,5 +37,8 @@ impl Plugin for DebugGizmosPlugin {
         let pipeline = GizmoPipeline::new(device, camera_layout, surface_format);
 
         app.insert_resource(pipeline);
+        // Added here rather than in `build` so gizmos are drawn after
+        // post-processing, whose systems are added in its own `finish`.
+        app.add_system(UpdateGroup::Render, render_gizmos);
     }
 }
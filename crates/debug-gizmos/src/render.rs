@@ -14,8 +14,9 @@ use crate::{pipeline::GizmoPipeline, storage::GizmoStorage};
 /// clears the storage so the next frame starts empty (immediate mode).
 ///
 /// Runs in [`UpdateGroup::Render`](ecs::system::schedule::UpdateGroup::Render).
-/// It records into the shared frame encoder after the material passes, so
-/// gizmos are drawn on top of the scene.
+/// It records into the shared frame encoder after the material passes and
+/// post-processing, so gizmos are drawn on top of the final image, untouched
+/// by tone mapping.
 pub(crate) fn render_gizmos(
     mut storage: ResMut<GizmoStorage>,
     mut device: ResMut<RenderDevice>,
@@ -99,7 +99,7 @@ impl CameraDirector {
     }
 
     /// Slots a camera in above everything of the same priority, so equal
-    /// priorities run in arrival order. Called from [`VirtualCamera`]'s `on_add`.
+    /// priorities run in arrival order. Called from the [`VirtualCamera`] `OnAdd` observer.
     pub(crate) fn stack_insert(&mut self, camera: Entity, priority: i32, enabled: bool) {
         let outgoing = self.live();
         self.detach(camera);
@@ -125,7 +125,7 @@ impl CameraDirector {
         self.stack.iter().position(|entry| entry.camera == camera)
     }
 
-    /// Called from [`VirtualCamera`]'s `on_remove`, which covers despawns.
+    /// Called from the [`VirtualCamera`] `OnRemove` observer, which covers despawns.
     pub(crate) fn stack_remove(&mut self, camera: Entity) {
         let outgoing = self.live();
         self.detach(camera);
@@ -278,12 +278,14 @@ mod tests {
     use ecs::world::World;
     use essential::{time::Time, transform::Transform};
     use glam::Vec3;
-    use render::components::camera::Camera;
+    use render::components::{camera::Camera, projection::Projection};
 
     use super::{CameraDirector, drive_main_camera};
     use crate::{
         main_camera::MainCamera,
-        virtual_camera::{BlendIn, Lens, VirtualCamera},
+        virtual_camera::{
+            BlendIn, Lens, VirtualCamera, virtual_camera_added, virtual_camera_removed,
+        },
     };
 
     struct Fixture {
@@ -298,7 +300,8 @@ mod tests {
             // Inserts the GlobalTransform the director reads poses from.
             world.register_component_lifetimes::<Transform>();
             // Joins/leaves the stack on spawn and despawn.
-            world.register_component_lifetimes::<VirtualCamera>();
+            world.add_observer(virtual_camera_added);
+            world.add_observer(virtual_camera_removed);
             world.insert_resource(Time::new());
             world.insert_resource(CameraDirector::default());
 
@@ -536,7 +539,7 @@ mod tests {
         let mut fixture = Fixture::new();
         fixture.spawn_vcam(
             VirtualCamera::default().with_lens(Lens {
-                fovy: 1.0,
+                projection: Projection::perspective(1.0),
                 znear: 0.5,
                 zfar: 500.0,
             }),
@@ -548,7 +551,7 @@ mod tests {
             .world
             .get_component_for_entity::<Camera>(fixture.main_camera)
             .unwrap();
-        assert_eq!(camera.fovy, 1.0);
+        assert_eq!(camera.projection, Projection::perspective(1.0));
         assert_eq!(camera.zfar, 500.0);
     }
 }
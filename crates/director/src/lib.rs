@@ -53,7 +53,8 @@ impl Plugin for CameraDirectorPlugin {
         app.insert_resource(CameraDirector::default());
         app.register_reflection::<VirtualCamera>();
         // Must precede any VirtualCamera spawn, or it never joins the stack.
-        app.register_component_lifecycle::<VirtualCamera>();
+        app.add_observer(virtual_camera::virtual_camera_added);
+        app.add_observer(virtual_camera::virtual_camera_removed);
 
         app.add_system(UpdateGroup::Startup, main_camera::spawn_main_camera);
 
@@ -1,7 +1,15 @@
-use ecs::component::{Component, ComponentLifecycleCallback};
+use ecs::{
+    component::Component,
+    observer::{OnAdd, OnRemove, Trigger},
+    query::Query,
+    resource::ResMut,
+};
 use facet::Facet;
 use glam::{Quat, Vec3};
-use render::components::camera::Camera;
+use render::components::{
+    camera::Camera,
+    projection::{Projection, ScalingMode},
+};
 
 use crate::director::CameraDirector;
 
@@ -33,35 +41,33 @@ pub struct VirtualCamera {
     pub lens: Option<Lens>,
 }
 
-/// Joining and leaving the director's stack is driven by the component's own
-/// lifecycle, so spawning or despawning a camera is all it takes to enter or
-/// leave the running order — including despawns, which no query can observe.
 impl Component for VirtualCamera {
     fn name() -> &'static str {
         "VirtualCamera"
     }
+}
 
-    fn on_add() -> Option<ComponentLifecycleCallback> {
-        Some(|mut world, context| {
-            let Some(vcam) = world.get_component_for_entity::<VirtualCamera>(context.entity) else {
-                return;
-            };
-            let priority = vcam.priority;
-            let enabled = vcam.enabled;
-
-            if let Some(director) = world.get_resource_mut::<CameraDirector>() {
-                director.stack_insert(context.entity, priority, enabled);
-            }
-        })
-    }
+/// Joining and leaving the director's stack is driven by the component's own
+/// lifecycle, so spawning or despawning a camera is all it takes to enter or
+/// leave the running order — including despawns, which no query can observe.
+pub(crate) fn virtual_camera_added(
+    trigger: Trigger<OnAdd<VirtualCamera>>,
+    vcams: Query<&VirtualCamera>,
+    mut director: ResMut<CameraDirector>,
+) {
+    let Some(vcam) = vcams.get_entity(trigger.entity()) else {
+        return;
+    };
+    director.stack_insert(trigger.entity(), vcam.priority, vcam.enabled);
+}
 
-    fn on_remove() -> Option<ComponentLifecycleCallback> {
-        Some(|mut world, context| {
-            if let Some(director) = world.get_resource_mut::<CameraDirector>() {
-                director.stack_remove(context.entity);
-            }
-        })
-    }
+/// Counterpart of [`virtual_camera_added`]; runs while the component is still
+/// readable, for removals and despawns alike.
+pub(crate) fn virtual_camera_removed(
+    trigger: Trigger<OnRemove<VirtualCamera>>,
+    mut director: ResMut<CameraDirector>,
+) {
+    director.stack_remove(trigger.entity());
 }
 
 impl Default for VirtualCamera {
@@ -104,7 +110,7 @@ impl VirtualCamera {
 /// blended independently of the render target and clear colour.
 #[derive(Clone, Copy, Facet)]
 pub struct Lens {
-    pub fovy: f32,
+    pub projection: Projection,
     pub znear: f32,
     pub zfar: f32,
 }
@@ -112,7 +118,7 @@ pub struct Lens {
 impl Lens {
     pub fn from_camera(camera: &Camera) -> Self {
         Self {
-            fovy: camera.fovy,
+            projection: camera.projection,
             znear: camera.znear,
             zfar: camera.zfar,
         }
@@ -121,20 +127,94 @@ impl Lens {
     /// Writes the lens onto a camera, leaving `aspect` alone — that belongs to
     /// the render crate, which syncs it from the surface.
     pub fn apply_to(&self, camera: &mut Camera) {
-        camera.fovy = self.fovy;
+        camera.projection = self.projection;
         camera.znear = self.znear;
         camera.zfar = self.zfar;
     }
 
     pub fn lerp(self, other: Self, t: f32) -> Self {
         Self {
-            fovy: self.fovy + (other.fovy - self.fovy) * t,
+            projection: lerp_projection(self.projection, other.projection, t),
             znear: self.znear + (other.znear - self.znear) * t,
             zfar: self.zfar + (other.zfar - self.zfar) * t,
         }
     }
 }
 
+// Interpolates the parameters of two projections of the same kind. Anything
+// else — perspective to orthographic, say — has no in-between worth showing,
+// so it cuts halfway through the blend.
+fn lerp_projection(from: Projection, to: Projection, t: f32) -> Projection {
+    let mix = |a: f32, b: f32| a + (b - a) * t;
+    let blended = match (from, to) {
+        (Projection::Perspective { fovy: a }, Projection::Perspective { fovy: b }) => {
+            Some(Projection::Perspective { fovy: mix(a, b) })
+        }
+        (
+            Projection::Orthographic {
+                scaling: scaling_a,
+                scale: a,
+            },
+            Projection::Orthographic {
+                scaling: scaling_b,
+                scale: b,
+            },
+        ) => lerp_scaling(scaling_a, scaling_b, mix).map(|scaling| Projection::Orthographic {
+            scaling,
+            scale: mix(a, b),
+        }),
+        (Projection::Custom { clip_from_view: a }, Projection::Custom { clip_from_view: b }) => {
+            Some(Projection::Custom {
+                clip_from_view: std::array::from_fn(|i| mix(a[i], b[i])),
+            })
+        }
+        _ => None,
+    };
+    blended.unwrap_or(if t < 0.5 { from } else { to })
+}
+
+fn lerp_scaling(
+    from: ScalingMode,
+    to: ScalingMode,
+    mix: impl Fn(f32, f32) -> f32,
+) -> Option<ScalingMode> {
+    Some(match (from, to) {
+        (ScalingMode::FixedVertical { height: a }, ScalingMode::FixedVertical { height: b }) => {
+            ScalingMode::FixedVertical { height: mix(a, b) }
+        }
+        (ScalingMode::FixedHorizontal { width: a }, ScalingMode::FixedHorizontal { width: b }) => {
+            ScalingMode::FixedHorizontal { width: mix(a, b) }
+        }
+        (
+            ScalingMode::Fixed {
+                width: width_a,
+                height: height_a,
+            },
+            ScalingMode::Fixed {
+                width: width_b,
+                height: height_b,
+            },
+        ) => ScalingMode::Fixed {
+            width: mix(width_a, width_b),
+            height: mix(height_a, height_b),
+        },
+        (
+            ScalingMode::AutoMin {
+                min_width: width_a,
+                min_height: height_a,
+            },
+            ScalingMode::AutoMin {
+                min_width: width_b,
+                min_height: height_b,
+            },
+        ) => ScalingMode::AutoMin {
+            min_width: mix(width_a, width_b),
+            min_height: mix(height_a, height_b),
+        },
+        _ => return None,
+    })
+}
+
 /// How a virtual camera takes over from whatever was live before it.
 #[derive(Clone, Copy, Facet)]
 pub struct BlendIn {
@@ -218,7 +298,9 @@ mod tests {
     use ecs::system::schedule::Schedule;
     use ecs::{CommandQueue, Entity, Res, Resource, world::World};
 
-    use super::VirtualCamera;
+    use render::components::projection::{Projection, ScalingMode};
+
+    use super::{Lens, VirtualCamera, virtual_camera_added, virtual_camera_removed};
     use crate::director::CameraDirector;
 
     #[derive(Resource)]
@@ -239,7 +321,8 @@ mod tests {
     /// facet reflection, which has to reach the private fields.
     fn spawn_from_json(json: &'static str) -> (World, Entity) {
         let mut world = World::new();
-        world.register_component_lifetimes::<VirtualCamera>();
+        world.add_observer(virtual_camera_added);
+        world.add_observer(virtual_camera_removed);
         world.register_reflection::<VirtualCamera>();
         world.insert_resource(CameraDirector::default());
 
@@ -277,4 +360,65 @@ mod tests {
         assert!(!director.is_enabled(node));
         assert!(director.live().is_none());
     }
+
+    #[test]
+    fn a_json_authored_lens_can_be_orthographic() {
+        let (world, node) = spawn_from_json(
+            r#"{
+                "priority": 0,
+                "lens": {
+                    "projection": {
+                        "Orthographic": {
+                            "scaling": { "FixedVertical": { "height": 12.0 } },
+                            "scale": 1.0
+                        }
+                    },
+                    "znear": 0.1,
+                    "zfar": 50.0
+                }
+            }"#,
+        );
+
+        let vcam = world
+            .get_component_for_entity::<VirtualCamera>(node)
+            .unwrap();
+        assert_eq!(
+            vcam.lens.unwrap().projection,
+            Projection::orthographic(ScalingMode::FixedVertical { height: 12.0 })
+        );
+    }
+
+    fn lens(projection: Projection) -> Lens {
+        Lens {
+            projection,
+            znear: 0.1,
+            zfar: 100.0,
+        }
+    }
+
+    #[test]
+    fn orthographic_lenses_blend_their_visible_area() {
+        let from = lens(Projection::orthographic(ScalingMode::FixedVertical {
+            height: 10.0,
+        }));
+        let to = lens(Projection::orthographic(ScalingMode::FixedVertical {
+            height: 20.0,
+        }));
+
+        assert_eq!(
+            from.lerp(to, 0.5).projection,
+            Projection::orthographic(ScalingMode::FixedVertical { height: 15.0 })
+        );
+    }
+
+    #[test]
+    fn lenses_of_different_kinds_cut_halfway_through_the_blend() {
+        let from = lens(Projection::perspective(1.0));
+        let to = lens(Projection::orthographic(ScalingMode::FixedVertical {
+            height: 10.0,
+        }));
+
+        assert_eq!(from.lerp(to, 0.49).projection, from.projection);
+        assert_eq!(from.lerp(to, 0.5).projection, to.projection);
+    }
 }
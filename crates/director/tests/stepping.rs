@@ -0,0 +1,84 @@
+//! The director driven through a headless app: `App::step` runs startup, the
+//! observers and the `Update` pose pipeline exactly as a windowed run would.
+
+use std::time::Duration;
+
+use app::{
+    App,
+    plugins::{TimePlugin, TransformPlugin},
+};
+use director::{BlendIn, CameraDirector, CameraDirectorPlugin, VirtualCamera};
+use ecs::Entity;
+use essential::transform::Transform;
+use glam::Vec3;
+
+fn director_app() -> App {
+    let mut app = App::new();
+    app.register_plugin(TimePlugin)
+        .register_plugin(TransformPlugin)
+        .register_plugin(CameraDirectorPlugin);
+    app.step(Duration::ZERO);
+    app
+}
+
+fn main_camera(app: &App) -> Entity {
+    app.get_resource::<CameraDirector>()
+        .unwrap()
+        .main_camera()
+        .expect("startup should spawn the main camera")
+}
+
+fn main_camera_translation(app: &App) -> Vec3 {
+    app.world()
+        .get_component_for_entity::<Transform>(main_camera(app))
+        .unwrap()
+        .translation
+}
+
+#[test]
+fn startup_leaves_the_fallback_camera_live() {
+    let app = director_app();
+    let director = app.get_resource::<CameraDirector>().unwrap();
+    assert_eq!(director.stack().len(), 1);
+    assert!(director.live().is_some());
+    assert_eq!(main_camera_translation(&app), Vec3::ZERO);
+}
+
+#[test]
+fn a_higher_priority_camera_takes_over_with_a_cut() {
+    let mut app = director_app();
+    let target = Vec3::new(0.0, 2.0, 10.0);
+    let vcam = app
+        .world_mut()
+        .spawn((VirtualCamera::new(10), Transform::from_translation(target)));
+
+    // The director reads last frame's global transforms, so the pose lands one
+    // frame after the camera's transform has been propagated.
+    app.step(Duration::from_millis(16));
+    app.step(Duration::from_millis(16));
+
+    assert!(app.get_resource::<CameraDirector>().unwrap().is_live(vcam));
+    assert!(main_camera_translation(&app).abs_diff_eq(target, 1e-5));
+}
+
+#[test]
+fn a_linear_blend_advances_with_the_stepped_time() {
+    let mut app = director_app();
+    app.get_resource_mut::<CameraDirector>()
+        .unwrap()
+        .set_blend(BlendIn::linear(1.0));
+
+    let target = Vec3::new(10.0, 0.0, 0.0);
+    app.world_mut()
+        .spawn((VirtualCamera::new(10), Transform::from_translation(target)));
+
+    // Propagate the new camera's transform without advancing the blend.
+    app.step(Duration::ZERO);
+    app.step(Duration::from_millis(500));
+    assert!(app.get_resource::<CameraDirector>().unwrap().is_blending());
+    assert!(main_camera_translation(&app).abs_diff_eq(target * 0.5, 1e-4));
+
+    app.step(Duration::from_millis(500));
+    assert!(!app.get_resource::<CameraDirector>().unwrap().is_blending());
+    assert!(main_camera_translation(&app).abs_diff_eq(target, 1e-4));
+}
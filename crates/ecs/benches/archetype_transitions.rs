@@ -0,0 +1,59 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use ecs::{Component, Entity, World};
+
+#[derive(Component)]
+struct Position(#[allow(dead_code)] [f32; 3]);
+
+#[derive(Component)]
+struct Velocity(#[allow(dead_code)] [f32; 3]);
+
+#[derive(Component)]
+struct Health(#[allow(dead_code)] f32);
+
+/// Marker toggled on and off, like "grounded" or "in water".
+#[derive(Component)]
+struct Grounded;
+
+const ENTITY_COUNT: usize = 1_000;
+
+fn populated_world() -> (World, Vec<Entity>) {
+    let mut world = World::new();
+    let entities = (0..ENTITY_COUNT)
+        .map(|_| world.spawn((Position([0.0; 3]), Velocity([1.0; 3]), Health(100.0))))
+        .collect();
+    (world, entities)
+}
+
+fn toggle_marker(world: &mut World, entities: &[Entity]) {
+    for entity in entities {
+        world.insert_component(Grounded, *entity);
+    }
+    for entity in entities {
+        world.remove_component::<Grounded>(*entity);
+    }
+}
+
+fn archetype_transitions(c: &mut Criterion) {
+    // Every iteration starts from a fresh world, so the first transition of each kind has
+    // to look up (and create) the target archetype.
+    c.bench_function("toggle marker, fresh world", |b| {
+        b.iter_batched(
+            populated_world,
+            |(mut world, entities)| {
+                toggle_marker(&mut world, &entities);
+                black_box(world)
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    // The archetype edges are already cached, so each transition is a lookup and a row move.
+    let (mut world, entities) = populated_world();
+    toggle_marker(&mut world, &entities);
+    c.bench_function("toggle marker, warm world", |b| {
+        b.iter(|| toggle_marker(&mut world, &entities))
+    });
+}
+
+criterion_group!(benches, archetype_transitions);
+criterion_main!(benches);
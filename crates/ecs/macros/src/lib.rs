@@ -61,3 +61,37 @@ fn impl_event(ast: &syn::DeriveInput) -> TokenStream {
     };
     gen.into()
 }
+
+#[proc_macro_derive(SystemSet)]
+pub fn system_set(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_system_set(&ast)
+}
+
+fn impl_system_set(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+    let gen = quote! {
+        impl #impl_generics SystemSet for #name #type_generics #where_clause  {
+        }
+
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(States)]
+pub fn states(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+    impl_states(&ast)
+}
+
+fn impl_states(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+    let gen = quote! {
+        impl #impl_generics States for #name #type_generics #where_clause  {
+        }
+
+    };
+    gen.into()
+}
@@ -4,11 +4,25 @@ use crate::{
     component::{Component, ComponentId},
     entity::Entity,
     table::{MutableCellAccessor, Table, TableRow, TableRowIndex},
+    utilities::TypeIdMap,
 };
 
 pub struct Archetype {
     data_table: Table,
     component_ids: Vec<ComponentId>,
+    edges: ArchetypeEdges,
+}
+
+/// Cached transitions to neighbouring archetypes in the archetype graph.
+///
+/// Filled lazily the first time an entity moves between two archetypes, so later
+/// insertions/removals of the same component are a single lookup.
+#[derive(Default)]
+struct ArchetypeEdges {
+    /// Index of the archetype reached by inserting a component.
+    insert: TypeIdMap<usize>,
+    /// Index of the archetype reached by removing a component.
+    remove: TypeIdMap<usize>,
 }
 
 impl Archetype {
@@ -16,9 +30,28 @@ impl Archetype {
         Archetype {
             data_table,
             component_ids,
+            edges: ArchetypeEdges::default(),
         }
     }
 
+    /// Returns the index of the archetype reached by inserting `component_id`, if known.
+    pub(crate) fn insert_edge(&self, component_id: ComponentId) -> Option<usize> {
+        self.edges.insert.get(&component_id).copied()
+    }
+
+    /// Returns the index of the archetype reached by removing `component_id`, if known.
+    pub(crate) fn remove_edge(&self, component_id: ComponentId) -> Option<usize> {
+        self.edges.remove.get(&component_id).copied()
+    }
+
+    pub(crate) fn set_insert_edge(&mut self, component_id: ComponentId, archetype_index: usize) {
+        self.edges.insert.insert(component_id, archetype_index);
+    }
+
+    pub(crate) fn set_remove_edge(&mut self, component_id: ComponentId, archetype_index: usize) {
+        self.edges.remove.insert(component_id, archetype_index);
+    }
+
     pub fn add_component<T: Component>(
         &mut self,
         raw_value: AnyValueWrapper<T>,
@@ -3,8 +3,10 @@ use std::marker::PhantomData;
 use log::warn;
 
 use crate::{
-    component::{bundle::ComponentBundle, Component},
+    component::{bundle::ComponentBundle, reflection::deserializing_into, Component},
     entity::{entity_store::EntityStore, Entity},
+    events::Event,
+    observer::{BoxedObserver, IntoObserver, ObserverEvent, ObserverKey},
     resource::Resource,
     system::input::SystemInput,
     world::World,
@@ -43,6 +45,30 @@ impl<'a> EntityCommandQueue<'a> {
     pub fn despawn(mut self) {
         self.command_queue.despawn(self.entity());
     }
+
+    /// Despawns this entity together with all of its descendants.
+    pub fn despawn_recursive(mut self) {
+        self.command_queue.despawn_recursive(self.entity());
+    }
+
+    /// Makes this entity a child of `parent`, removing it from its previous parent.
+    pub fn set_parent(&mut self, parent: Entity) {
+        self.command_queue.set_parent(self.entity, parent);
+    }
+
+    /// Detaches `child` from this entity; `child` becomes a root entity.
+    pub fn remove_child(&mut self, child: Entity) {
+        self.command_queue.remove_child(self.entity, child);
+    }
+
+    /// Registers an observer that only runs for events targeting this entity.
+    pub fn observe<E: ObserverEvent, M>(&mut self, observer: impl IntoObserver<E, M>) {
+        self.command_queue.queue_state.add_command(AddObserver {
+            key: E::observer_key(),
+            target: Some(self.entity),
+            observer: observer.into_observer(),
+        });
+    }
 }
 
 pub struct CommandQueue<'world, 'state> {
@@ -76,6 +102,12 @@ impl<'w, 's> CommandQueue<'w, 's> {
         self.queue_state.add_command(DespawnCommand::new(entity));
     }
 
+    /// Despawns `entity` together with all of its descendants.
+    pub fn despawn_recursive(&mut self, entity: Entity) {
+        self.queue_state
+            .add_command(DespawnRecursiveCommand::new(entity));
+    }
+
     pub fn insert<T: Component>(&mut self, component: T, entity: Entity) {
         self.queue_state
             .add_command(InsertCommand::new(component, entity));
@@ -90,6 +122,42 @@ impl<'w, 's> CommandQueue<'w, 's> {
         self.queue_state.add_command(AddChild::new(parent, child));
     }
 
+    /// Makes `child` a child of `parent`, removing it from its previous parent.
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        self.queue_state.add_command(AddChild::new(parent, child));
+    }
+
+    /// Detaches `child` from `parent`; `child` becomes a root entity.
+    pub fn remove_child(&mut self, parent: Entity, child: Entity) {
+        self.queue_state
+            .add_command(RemoveChild::new(parent, child));
+    }
+
+    /// Registers a global observer.  See [`World::add_observer`].
+    pub fn add_observer<E: ObserverEvent, M>(&mut self, observer: impl IntoObserver<E, M>) {
+        self.queue_state.add_command(AddObserver {
+            key: E::observer_key(),
+            target: None,
+            observer: observer.into_observer(),
+        });
+    }
+
+    /// Triggers `event` for the global observers when the commands are applied.
+    pub fn trigger<E: Event + 'static>(&mut self, event: E) {
+        self.queue_state.add_command(TriggerEvent {
+            event,
+            target: None,
+        });
+    }
+
+    /// Triggers `event` for the observers of `entity` when the commands are applied.
+    pub fn trigger_targets<E: Event + 'static>(&mut self, event: E, entity: Entity) {
+        self.queue_state.add_command(TriggerEvent {
+            event,
+            target: Some(entity),
+        });
+    }
+
     pub fn insert_resource<T: Resource>(&mut self, resource: T) {
         self.queue_state
             .add_command(InsertResource::<T>::new(resource));
@@ -196,6 +264,22 @@ impl Command for DespawnCommand {
     }
 }
 
+pub(crate) struct DespawnRecursiveCommand {
+    entity: Entity,
+}
+
+impl DespawnRecursiveCommand {
+    pub fn new(entity: Entity) -> Self {
+        DespawnRecursiveCommand { entity }
+    }
+}
+
+impl Command for DespawnRecursiveCommand {
+    fn execute(self: Box<Self>, world: &mut World) {
+        world.despawn_recursive(self.entity);
+    }
+}
+
 pub(crate) struct InsertCommand<T: Component> {
     component: T,
     entity: Entity,
@@ -239,37 +323,42 @@ impl Command for InsertErasedCommand {
             return;
         };
 
-        let partial = match reflection.alloc_shape() {
-            Ok(partial) => partial,
-            Err(err) => {
-                warn!(
-                    "Failed to allocate component '{}': {err}",
-                    self.component_name
-                );
-                return;
-            }
-        };
-
-        let partial = match facet_json::from_str_into_borrowed(&self.component_data, partial) {
-            Ok(partial) => partial,
-            Err(err) => {
-                warn!(
-                    "Failed to deserialize component '{}' from `{}`: {err}",
-                    self.component_name, self.component_data
-                );
-                return;
-            }
-        };
-
-        let heap_value = match partial.build() {
-            Ok(heap_value) => heap_value,
-            Err(err) => {
-                warn!(
-                    "Failed to build component '{}' (are all required fields present?): {err}",
-                    self.component_name
-                );
-                return;
+        let heap_value = deserializing_into(world, || {
+            let partial = match reflection.alloc_shape() {
+                Ok(partial) => partial,
+                Err(err) => {
+                    warn!(
+                        "Failed to allocate component '{}': {err}",
+                        self.component_name
+                    );
+                    return None;
+                }
+            };
+
+            let partial = match facet_json::from_str_into_borrowed(&self.component_data, partial) {
+                Ok(partial) => partial,
+                Err(err) => {
+                    warn!(
+                        "Failed to deserialize component '{}' from `{}`: {err}",
+                        self.component_name, self.component_data
+                    );
+                    return None;
+                }
+            };
+
+            match partial.build() {
+                Ok(heap_value) => Some(heap_value),
+                Err(err) => {
+                    warn!(
+                        "Failed to build component '{}' (are all required fields present?): {err}",
+                        self.component_name
+                    );
+                    None
+                }
             }
+        });
+        let Some(heap_value) = heap_value else {
+            return;
         };
 
         if let Err(err) = reflection.insert(heap_value, world, self.entity) {
@@ -318,6 +407,49 @@ impl Command for AddChild {
     }
 }
 
+pub(crate) struct RemoveChild {
+    parent: Entity,
+    child: Entity,
+}
+
+impl RemoveChild {
+    pub fn new(parent: Entity, child: Entity) -> Self {
+        Self { parent, child }
+    }
+}
+
+impl Command for RemoveChild {
+    fn execute(self: Box<Self>, world: &mut World) {
+        world.remove_child(self.parent, self.child);
+    }
+}
+
+pub(crate) struct AddObserver {
+    key: ObserverKey,
+    target: Option<Entity>,
+    observer: BoxedObserver,
+}
+
+impl Command for AddObserver {
+    fn execute(self: Box<Self>, world: &mut World) {
+        world.add_observer_boxed(self.key, self.target, self.observer);
+    }
+}
+
+pub(crate) struct TriggerEvent<E: Event> {
+    event: E,
+    target: Option<Entity>,
+}
+
+impl<E: Event + 'static> Command for TriggerEvent<E> {
+    fn execute(self: Box<Self>, world: &mut World) {
+        match self.target {
+            Some(entity) => world.trigger_targets(self.event, entity),
+            None => world.trigger(self.event),
+        }
+    }
+}
+
 pub(crate) struct InsertResource<T: Resource> {
     resource: T,
 }
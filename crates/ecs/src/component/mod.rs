@@ -35,6 +35,11 @@ pub struct ComponentLifecycleContext {
 /// Override [`on_add`](Component::on_add) or [`on_remove`](Component::on_remove) to
 /// run logic automatically when the component is added to or removed from an entity.
 ///
+/// # Entity references
+/// Components that store [`Entity`] handles should override
+/// [`map_entities`](Component::map_entities), so the handles survive saving and loading a
+/// [scene](crate::scene).
+///
 /// # Example
 /// ```
 /// use ecs::component::Component;
@@ -58,6 +63,12 @@ pub trait Component: Send + Sync + 'static {
     fn on_remove() -> Option<ComponentLifecycleCallback> {
         None
     }
+
+    /// Rewrites every [`Entity`] stored in this component with `mapper`.
+    ///
+    /// Called when a [scene](crate::scene) is loaded, so references to other entities of the
+    /// scene point to the newly spawned entities.  Override it for components holding entities.
+    fn map_entities(&mut self, _mapper: &mut dyn FnMut(Entity) -> Entity) {}
 }
 
 #[allow(dead_code)]
@@ -1,31 +1,87 @@
+use std::{cell::Cell, ptr::NonNull};
+
 use anyhow::Ok;
 use facet::{AllocError, Facet, HeapValue, Partial, Shape};
 
 use crate::{Component, Entity, World};
 
+/// Rewrites an entity reference, e.g. from a saved handle to the one spawned when loading.
+pub(crate) type EntityMapper<'a> = dyn FnMut(Entity) -> Entity + 'a;
+
+thread_local! {
+    static DESERIALIZING_INTO: Cell<Option<NonNull<World>>> = const { Cell::new(None) };
+}
+
+/// Runs `f` with the world the component being deserialized on this thread is inserted into,
+/// or with `None` outside of deserialization.
+///
+/// facet has no way to pass context to a deserializer, so this is how the `TryFrom`
+/// conversion of a facet proxy reaches resources, e.g. to turn a saved asset path back into
+/// a live handle.
+pub fn with_deserializing_world<R>(f: impl FnOnce(Option<&World>) -> R) -> R {
+    let world = DESERIALIZING_INTO.get();
+    // SAFETY: the pointer is only set by `deserializing_into`, which holds a shared borrow of
+    // the world until it resets the pointer.
+    f(world.map(|world| unsafe { world.as_ref() }))
+}
+
+/// Runs `f`, which deserializes a component, with `world` available to
+/// [`with_deserializing_world`].
+pub(crate) fn deserializing_into<R>(world: &World, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<NonNull<World>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            DESERIALIZING_INTO.set(self.0);
+        }
+    }
+
+    let _restore = Restore(DESERIALIZING_INTO.replace(Some(NonNull::from(world))));
+    f()
+}
+
 #[derive(Clone)]
 pub(crate) struct ComponentReflection {
+    name: &'static str,
     shape: &'static Shape,
-    insert_fn: fn(HeapValue, &mut World, Entity) -> anyhow::Result<()>,
+    insert_fn: fn(HeapValue, &mut World, Entity, &mut EntityMapper) -> anyhow::Result<()>,
+    serialize_fn: fn(&World, Entity) -> Option<anyhow::Result<String>>,
 }
 
 fn insertion_typed<T: Component + for<'a> Facet<'a>>(
     heap: HeapValue,
     world: &mut World,
     entity: Entity,
+    mapper: &mut EntityMapper,
 ) -> anyhow::Result<()> {
-    world.insert_component(heap.materialize::<T>()?, entity);
+    let mut component = heap.materialize::<T>()?;
+    component.map_entities(mapper);
+    world.insert_component(component, entity);
     Ok(())
 }
 
+fn serialization_typed<T: Component + for<'a> Facet<'a>>(
+    world: &World,
+    entity: Entity,
+) -> Option<anyhow::Result<String>> {
+    let component = world.get_component_for_entity::<T>(entity)?;
+    Some(facet_json::to_string(component).map_err(|err| anyhow::anyhow!("{err}")))
+}
+
 impl ComponentReflection {
     pub(crate) fn from_type<T: Component + for<'a> Facet<'a>>() -> Self {
         Self {
+            name: T::name(),
             shape: T::SHAPE,
             insert_fn: insertion_typed::<T>,
+            serialize_fn: serialization_typed::<T>,
         }
     }
 
+    pub(crate) fn name(&self) -> &'static str {
+        self.name
+    }
+
     pub(crate) fn alloc_shape(&self) -> Result<Partial<'_>, AllocError> {
         // This is safe, if the shape exists it is valid
         unsafe { Partial::alloc_shape(self.shape) }
@@ -37,6 +93,48 @@ impl ComponentReflection {
         world: &mut World,
         entity: Entity,
     ) -> anyhow::Result<()> {
-        (self.insert_fn)(value, world, entity)
+        self.insert_mapped(value, world, entity, &mut |entity| entity)
+    }
+
+    /// Inserts the component after passing its entity references through `mapper`.
+    pub(crate) fn insert_mapped(
+        &self,
+        value: HeapValue,
+        world: &mut World,
+        entity: Entity,
+        mapper: &mut EntityMapper,
+    ) -> anyhow::Result<()> {
+        (self.insert_fn)(value, world, entity, mapper)
+    }
+
+    /// Serializes the component of `entity` to JSON, or returns `None` if it has none.
+    pub(crate) fn serialize(
+        &self,
+        world: &World,
+        entity: Entity,
+    ) -> Option<anyhow::Result<String>> {
+        (self.serialize_fn)(world, entity)
+    }
+
+    /// Deserializes the component from `json` and inserts it on `entity`, after passing its
+    /// entity references through `mapper`.
+    pub(crate) fn insert_json(
+        &self,
+        json: &str,
+        world: &mut World,
+        entity: Entity,
+        mapper: &mut EntityMapper,
+    ) -> anyhow::Result<()> {
+        let heap_value = deserializing_into(world, || {
+            let partial = self
+                .alloc_shape()
+                .map_err(|err| anyhow::anyhow!("failed to allocate: {err}"))?;
+            let partial = facet_json::from_str_into_borrowed(json, partial)
+                .map_err(|err| anyhow::anyhow!("failed to deserialize from `{json}`: {err}"))?;
+            partial.build().map_err(|err| {
+                anyhow::anyhow!("failed to build (are all required fields present?): {err}")
+            })
+        })?;
+        self.insert_mapped(heap_value, world, entity, mapper)
     }
 }
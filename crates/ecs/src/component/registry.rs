@@ -23,6 +23,7 @@ pub(crate) struct ComponentIndex(usize);
 pub(crate) struct ComponentRegistry {
     component_map: HashMap<ComponentId, ComponentIndex>,
     reflection_map: HashMap<&'static str, ComponentReflection>,
+    reflection_names: HashMap<ComponentId, &'static str>,
     component_info: Vec<ComponentInfo>,
 }
 
@@ -52,9 +53,16 @@ impl ComponentRegistry {
 
         self.reflection_map
             .insert(T::name(), ComponentReflection::from_type::<T>());
+        self.reflection_names.insert(TypeId::of::<T>(), T::name());
     }
 
     pub(crate) fn get_reflection(&self, name: &str) -> Option<&ComponentReflection> {
         self.reflection_map.get(name)
     }
+
+    pub(crate) fn get_reflection_by_id(&self, id: ComponentId) -> Option<&ComponentReflection> {
+        self.reflection_names
+            .get(&id)
+            .and_then(|name| self.reflection_map.get(name))
+    }
 }
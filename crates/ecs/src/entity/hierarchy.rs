@@ -1,6 +1,6 @@
-use std::ops::Deref;
+use std::{collections::VecDeque, ops::Deref};
 
-use crate::{component::Component, entity::Entity};
+use crate::{component::Component, entity::Entity, world::World};
 
 #[derive(Component)]
 pub struct Children {
@@ -13,7 +13,29 @@ impl Children {
     }
 
     pub(crate) fn add_child(&mut self, child: Entity) {
-        self.children.push(child);
+        if !self.children.contains(&child) {
+            self.children.push(child);
+        }
+    }
+
+    /// Removes `child` from the list, keeping the order of the remaining children.
+    /// Returns `false` if it was not in the list.
+    pub(crate) fn remove_child(&mut self, child: Entity) -> bool {
+        match self.children.iter().position(|entity| *entity == child) {
+            Some(index) => {
+                self.children.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Deref for Children {
+    type Target = [Entity];
+
+    fn deref(&self) -> &Self::Target {
+        &self.children
     }
 }
 
@@ -46,6 +68,10 @@ impl ChildOf {
     pub fn new(parent: Entity) -> Self {
         Self { parent }
     }
+
+    pub fn parent(&self) -> Entity {
+        self.parent
+    }
 }
 
 impl Deref for ChildOf {
@@ -55,3 +81,98 @@ impl Deref for ChildOf {
         &self.parent
     }
 }
+
+/// Iterator over the ancestors of an entity, from its parent up to the root.
+///
+/// Created with [`World::ancestors`].
+pub struct AncestorIter<'w> {
+    world: &'w World,
+    next: Option<Entity>,
+}
+
+impl<'w> AncestorIter<'w> {
+    pub(crate) fn new(world: &'w World, entity: Entity) -> Self {
+        Self {
+            world,
+            next: parent_of(world, entity),
+        }
+    }
+}
+
+impl Iterator for AncestorIter<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = parent_of(self.world, current);
+        Some(current)
+    }
+}
+
+/// Breadth-first iterator over the descendants of an entity, not including the entity itself.
+///
+/// Created with [`World::descendants`].
+pub struct DescendantIter<'w> {
+    world: &'w World,
+    queue: VecDeque<Entity>,
+}
+
+impl<'w> DescendantIter<'w> {
+    pub(crate) fn new(world: &'w World, entity: Entity) -> Self {
+        Self {
+            world,
+            queue: children_of(world, entity).iter().copied().collect(),
+        }
+    }
+}
+
+impl Iterator for DescendantIter<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.queue.pop_front()?;
+        self.queue.extend(children_of(self.world, current));
+        Some(current)
+    }
+}
+
+/// Depth-first, pre-order iterator over the descendants of an entity, not including the
+/// entity itself.  Children are visited in the order they were added.
+///
+/// Created with [`World::descendants_depth_first`].
+pub struct DepthFirstIter<'w> {
+    world: &'w World,
+    stack: Vec<Entity>,
+}
+
+impl<'w> DepthFirstIter<'w> {
+    pub(crate) fn new(world: &'w World, entity: Entity) -> Self {
+        Self {
+            world,
+            stack: children_of(world, entity).iter().rev().copied().collect(),
+        }
+    }
+}
+
+impl Iterator for DepthFirstIter<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.stack.pop()?;
+        self.stack
+            .extend(children_of(self.world, current).iter().rev().copied());
+        Some(current)
+    }
+}
+
+fn parent_of(world: &World, entity: Entity) -> Option<Entity> {
+    world
+        .get_component_for_entity::<ChildOf>(entity)
+        .map(ChildOf::parent)
+}
+
+fn children_of(world: &World, entity: Entity) -> &[Entity] {
+    world
+        .get_component_for_entity::<Children>(entity)
+        .map_or(&[], |children| &children.children)
+}
@@ -3,6 +3,8 @@ use std::{
     num::NonZero,
 };
 
+use facet::Facet;
+
 use crate::table::TableRowIndex;
 
 pub mod entity_store;
@@ -13,7 +15,7 @@ pub mod hierarchy;
 /// Entities are created with [`World::spawn`](crate::world::World::spawn) and destroyed with
 /// [`World::despawn`](crate::world::World::despawn).  An entity is just an `(index, generation)`
 /// pair — the generation is bumped each time a slot is reused so stale handles can be detected.
-#[derive(Eq, Hash, PartialEq, Clone, Copy)]
+#[derive(Eq, Hash, PartialEq, Clone, Copy, Facet)]
 pub struct Entity {
     index: u32,
     generation: NonZero<u32>,
@@ -5,30 +5,72 @@ use crate::{
 
 /// Internal storage for a single event type.
 ///
-/// Events are buffered here and flushed once per frame by [`update_event_channel`].
+/// Events are kept in two buffers: the ones written since the last
+/// [`update`](EventChannel::update) and the ones written the update before.  Each update drops
+/// the older buffer, so an event stays readable for two updates.  That way a reader that runs
+/// before the writer in a frame, or a `FixedUpdate` reader that skips a frame, still sees it.
+///
+/// Every event gets a sequential id; each [`EventReader`](super::event_reader::EventReader)
+/// remembers the id it has read up to, so it sees every event exactly once.
+///
 /// Prefer the higher-level [`EventWriter`](super::event_writer::EventWriter) and
 /// [`EventReader`](super::event_reader::EventReader) in system code.
 #[derive(Resource)]
 pub struct EventChannel<T: Event + 'static> {
-    pub(crate) event_queue: Vec<T>,
+    /// Events written before the last update.
+    previous: Vec<T>,
+    /// Events written since the last update.
+    current: Vec<T>,
+    /// Id of `previous[0]`.
+    previous_start: usize,
+    /// Id of `current[0]`.
+    current_start: usize,
 }
 
 impl<T: Event + 'static> EventChannel<T> {
     /// Creates an empty channel.
     pub fn new() -> Self {
         EventChannel {
-            event_queue: Vec::new(),
+            previous: Vec::new(),
+            current: Vec::new(),
+            previous_start: 0,
+            current_start: 0,
         }
     }
 
-    /// Enqueues `event` to be read by [`EventReader`](super::event_reader::EventReader)s this frame.
+    /// Enqueues `event` to be read by [`EventReader`](super::event_reader::EventReader)s
+    /// until the next update after this one.
     pub fn push_event(&mut self, event: T) {
-        self.event_queue.push(event);
+        self.current.push(event);
+    }
+
+    /// Drops the events written before the last update and starts a new buffer.
+    pub fn update(&mut self) {
+        std::mem::swap(&mut self.previous, &mut self.current);
+        self.current.clear();
+        self.previous_start = self.current_start;
+        self.current_start += self.previous.len();
     }
 
-    /// Clears all buffered events.
+    /// Clears all buffered events, including those not read yet.
     pub fn flush_events(&mut self) {
-        self.event_queue.clear();
+        self.update();
+        self.update();
+    }
+
+    /// Returns the id the next written event will get.
+    pub(crate) fn event_count(&self) -> usize {
+        self.current_start + self.current.len()
+    }
+
+    /// Returns the buffered events with an id of at least `cursor`, oldest first.
+    pub(crate) fn events_since(&self, cursor: usize) -> (&[T], &[T]) {
+        let previous_skip = cursor.saturating_sub(self.previous_start);
+        let current_skip = cursor.saturating_sub(self.current_start);
+        (
+            self.previous.get(previous_skip..).unwrap_or_default(),
+            self.current.get(current_skip..).unwrap_or_default(),
+        )
     }
 }
 
@@ -38,9 +80,10 @@ impl<T: Event + 'static> Default for EventChannel<T> {
     }
 }
 
-/// System that flushes all events in a channel at the end of `LateUpdate`.
+/// System that swaps the buffers of a channel, dropping events older than two updates.
 ///
-/// Registered automatically by [`App::register_event`].
+/// Registered automatically by `App::register_event` at the end of `LateUpdate`, or by
+/// `App::register_fixed_event` at the end of every `LateFixedUpdate` pass.
 pub fn update_event_channel<T: Event + 'static>(mut channel: ResMut<EventChannel<T>>) {
-    channel.flush_events();
+    channel.update();
 }
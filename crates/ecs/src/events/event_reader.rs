@@ -1,4 +1,4 @@
-use std::slice::Iter;
+use std::{iter::Chain, slice::Iter};
 
 use crate::{
     events::{event_channel::EventChannel, Event},
@@ -9,47 +9,75 @@ use crate::{
 
 /// System parameter for reading events of type `T`.
 ///
-/// Call [`read`](EventReader::read) to iterate over all events that were written this frame.
+/// Call [`read`](EventReader::read) to iterate over the events written since this system last
+/// read them.  Each system keeps its own cursor, so every reader sees every event exactly
+/// once, as long as it runs within two updates of the channel after the event is written.
 ///
 /// # Example
 /// ```ignore
-/// fn on_player_died(reader: EventReader<PlayerDied>) {
+/// fn on_player_died(mut reader: EventReader<PlayerDied>) {
 ///     for event in reader.read() {
 ///         println!("Player died with score {}", event.score);
 ///     }
 /// }
 /// ```
-pub struct EventReader<'world, T: Event + 'static> {
+pub struct EventReader<'world, 'state, T: Event + 'static> {
     channel: Res<'world, EventChannel<T>>,
+    cursor: &'state mut usize,
 }
 
-impl<'w, T: Event> EventReader<'w, T> {
-    pub fn new(world: UnsafeWorldCell<'w>) -> Self {
+impl<'w, 's, T: Event> EventReader<'w, 's, T> {
+    pub fn new(world: UnsafeWorldCell<'w>, cursor: &'s mut usize) -> Self {
         Self {
             channel: Res::new(world),
+            cursor,
         }
     }
 
-    /// Returns an iterator over all events buffered this frame.
-    pub fn read(&self) -> EventIterator<'_, T> {
-        EventIterator::new(&self.channel)
+    /// Returns an iterator over the events this reader has not seen yet, oldest first, and
+    /// marks them as read.
+    pub fn read(&mut self) -> EventIterator<'_, T> {
+        let (previous, current) = self.channel.events_since(*self.cursor);
+        *self.cursor = self.channel.event_count();
+        EventIterator {
+            iter: previous.iter().chain(current.iter()),
+        }
+    }
+
+    /// Returns the number of events this reader has not seen yet.
+    pub fn len(&self) -> usize {
+        let (previous, current) = self.channel.events_since(*self.cursor);
+        previous.len() + current.len()
+    }
+
+    /// Returns `true` if there are no events this reader has not seen yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Marks every buffered event as read without visiting them.
+    pub fn clear(&mut self) {
+        *self.cursor = self.channel.event_count();
     }
 }
 
-impl<'w, T> SystemInput for EventReader<'w, T>
+impl<T> SystemInput for EventReader<'_, '_, T>
 where
     T: Event,
 {
-    type State = ();
-    type Data<'world, 'state> = EventReader<'world, T>;
+    /// Id of the next event this reader will see.
+    type State = usize;
+    type Data<'world, 'state> = EventReader<'world, 'state, T>;
 
-    fn init_state() -> Self::State {}
+    fn init_state() -> Self::State {
+        0
+    }
 
     fn get_data<'world, 'state>(
-        _state: &'state mut Self::State,
+        state: &'state mut Self::State,
         world: crate::world::UnsafeWorldCell<'world>,
     ) -> Self::Data<'world, 'state> {
-        EventReader::new(world)
+        EventReader::new(world, state)
     }
 
     fn fill_access(access: &mut crate::system::access::SystemAccess) {
@@ -58,15 +86,7 @@ where
 }
 
 pub struct EventIterator<'a, T: Event> {
-    iter: Iter<'a, T>,
-}
-
-impl<'a, T: Event> EventIterator<'a, T> {
-    pub fn new(channel: &'a EventChannel<T>) -> Self {
-        Self {
-            iter: channel.event_queue.iter(),
-        }
-    }
+    iter: Chain<Iter<'a, T>, Iter<'a, T>>,
 }
 
 impl<'a, T: Event> Iterator for EventIterator<'a, T> {
@@ -75,4 +95,10 @@ impl<'a, T: Event> Iterator for EventIterator<'a, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.iter.next()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
+
+impl<T: Event> ExactSizeIterator for EventIterator<'_, T> {}
@@ -9,7 +9,7 @@ use crate::{
 ///
 /// Obtain one as a system parameter and call [`write`](EventWriter::write) to enqueue an event.
 /// The event will be available to all [`EventReader`](super::event_reader::EventReader)s until
-/// the end of the current frame.
+/// the channel has been updated twice, i.e. for the rest of this frame and all of the next.
 ///
 /// # Example
 /// ```ignore
@@ -29,7 +29,7 @@ impl<'w, T: Event> EventWriter<'w, T> {
     }
 
     /// Enqueues `event` so that [`EventReader`](super::event_reader::EventReader)s can read it
-    /// this frame.
+    /// this frame and the next.
     pub fn write(&mut self, event: T) {
         self.channel.push_event(event);
     }
@@ -12,7 +12,9 @@ pub use ecs_macros::Event;
 /// Send events from systems with [`EventWriter`](event_writer::EventWriter) and
 /// read them with [`EventReader`](event_reader::EventReader).
 ///
-/// Events are buffered for one frame and flushed at the end of `LateUpdate`.
+/// Events are double-buffered: they stay readable for two updates of their channel, which
+/// happen at the end of `LateUpdate` (or of `LateFixedUpdate` for events registered with
+/// `register_fixed_event`).  Each reader sees each event once.
 ///
 /// # Example
 /// ```ignore
@@ -9,7 +9,10 @@
 //! - [`Resource`] — trait for globally-shared data; derive with `#[derive(Resource)]`.
 //! - [`Query`] — type-safe iterator over entities matching a set of components.
 //! - [`Event`] — trait for messages passed between systems; derive with `#[derive(Event)]`.
+//! - [`observer`] — callbacks triggered by component lifecycle changes and custom events.
+//! - [`scene`] — saving entities and their reflected components to JSON, and loading them back.
 //! - [`Schedule`] — ordered collection of systems run each frame.
+//! - [`SystemSet`] — named group of systems ordered and gated as a whole.
 
 pub mod archetype;
 pub mod command;
@@ -17,8 +20,11 @@ pub mod common;
 pub mod component;
 pub mod entity;
 pub mod events;
+pub mod observer;
 pub mod query;
 pub mod resource;
+pub mod scene;
+pub mod state;
 pub mod system;
 pub mod table;
 pub mod utilities;
@@ -34,7 +40,12 @@ pub use query::{
     Query,
 };
 pub use resource::{Res, ResMut, Resource};
-pub use system::{schedule::Schedule, IntoSystem, IntoSystemConfig, System, SystemConfig};
+pub use system::{
+    local::Local,
+    schedule::Schedule,
+    set::{IntoSystemSetConfig, SystemSet},
+    IntoSystem, IntoSystemConfig, System, SystemConfig,
+};
 pub use world::World;
 
 #[cfg(test)]
@@ -42,14 +53,20 @@ mod tests {
     use crate::{
         command::CommandQueue,
         component::Component,
-        entity::Entity,
+        entity::{
+            hierarchy::{ChildOf, Children},
+            Entity,
+        },
         events::{event_channel::EventChannel, Event},
         query::{
-            query_filter::{Added, Changed, Or, With},
+            query_filter::{Added, Changed, Or, With, Without},
             Query,
         },
         resource::{Res, ResMut, Resource},
-        system::{executor::single_thread::SingleThreadedExecutor, schedule::Schedule},
+        system::{
+            executor::single_thread::SingleThreadedExecutor, local::Local, param_set::ParamSet,
+            schedule::Schedule, IntoSystemConfig,
+        },
         world::World,
     };
 
@@ -113,6 +130,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn empty_entities_get_their_own_rows() {
+        let mut world = World::new();
+        let entities = [world.spawn(()), world.spawn(()), world.spawn(())];
+
+        for (x, entity) in entities.iter().enumerate() {
+            world.insert_component(
+                Position {
+                    x: x as f32,
+                    y: 0.0,
+                },
+                *entity,
+            );
+        }
+
+        let xs = entities.map(|entity| {
+            world
+                .get_component_for_entity::<Position>(entity)
+                .unwrap()
+                .x
+        });
+        assert_eq!(xs, [0.0, 1.0, 2.0]);
+    }
+
     #[test]
     fn test_query() {
         let mut world = World::new();
@@ -285,6 +326,105 @@ mod tests {
         world.add_child(entity_parent, entity_child_2);
     }
 
+    // ----- hierarchy maintenance tests -----
+
+    /// Builds `root -> (a -> (a1, a2), b)`.
+    fn spawn_tree(world: &mut World) -> [Entity; 5] {
+        let [root, a, a1, a2, b] = std::array::from_fn(|_| world.spawn(Health));
+        world.add_child(root, a);
+        world.add_child(a, a1);
+        world.add_child(a, a2);
+        world.add_child(root, b);
+        [root, a, a1, a2, b]
+    }
+
+    fn children_of(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get_component_for_entity::<Children>(entity)
+            .map(|children| children.to_vec())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn hierarchy_traversal_orders() {
+        let mut world = World::new();
+        let [root, a, a1, a2, b] = spawn_tree(&mut world);
+
+        assert_eq!(world.ancestors(a2).collect::<Vec<_>>(), vec![a, root]);
+        assert_eq!(
+            world.descendants(root).collect::<Vec<_>>(),
+            vec![a, b, a1, a2]
+        );
+        assert_eq!(
+            world.descendants_depth_first(root).collect::<Vec<_>>(),
+            vec![a, a1, a2, b]
+        );
+    }
+
+    #[test]
+    fn despawn_recursive_removes_subtree_and_detaches_it() {
+        let mut world = World::new();
+        let [root, a, a1, a2, b] = spawn_tree(&mut world);
+
+        world.despawn_recursive(a);
+
+        for entity in [a, a1, a2] {
+            assert!(world.entity_store().find_location(entity).is_none());
+        }
+        assert_eq!(children_of(&world, root), vec![b]);
+    }
+
+    #[test]
+    fn despawn_turns_children_into_roots() {
+        let mut world = World::new();
+        let [root, a, a1, a2, b] = spawn_tree(&mut world);
+
+        world.despawn(a);
+
+        assert_eq!(children_of(&world, root), vec![b]);
+        assert!(world.get_component_for_entity::<ChildOf>(a1).is_none());
+        assert!(world.get_component_for_entity::<ChildOf>(a2).is_none());
+    }
+
+    #[test]
+    fn set_parent_and_remove_child_keep_both_sides_consistent() {
+        let mut world = World::new();
+        let [root, a, a1, a2, b] = spawn_tree(&mut world);
+
+        world.set_parent(a1, b);
+        assert_eq!(children_of(&world, a), vec![a2]);
+        assert_eq!(children_of(&world, b), vec![a1]);
+        assert_eq!(**world.get_component_for_entity::<ChildOf>(a1).unwrap(), b);
+
+        // The last child leaving removes the `Children` component.
+        world.remove_child(b, a1);
+        assert!(world.get_component_for_entity::<Children>(b).is_none());
+        assert!(world.get_component_for_entity::<ChildOf>(a1).is_none());
+
+        // An entity cannot become a child of its own descendant.
+        world.set_parent(root, a2);
+        assert!(world.get_component_for_entity::<ChildOf>(root).is_none());
+    }
+
+    fn despawn_roots(query: Query<Entity, Without<ChildOf>>, mut cmd: CommandQueue) {
+        for entity in query.iter() {
+            cmd.despawn_recursive(entity);
+        }
+    }
+
+    #[test]
+    fn despawn_recursive_command() {
+        let mut world = World::new();
+        spawn_tree(&mut world);
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(despawn_roots);
+        schedule.compile::<SingleThreadedExecutor>().run(&mut world);
+
+        let q = Query::<Entity>::new(world.as_unsafe_world_cell_mut());
+        assert_eq!(q.iter().count(), 0);
+    }
+
     #[derive(Resource)]
     struct Score(u32);
 
@@ -345,7 +485,7 @@ mod tests {
     }
 
     fn count_deaths(
-        reader: crate::events::event_reader::EventReader<PlayerDied>,
+        mut reader: crate::events::event_reader::EventReader<PlayerDied>,
         mut counter: ResMut<Score>,
     ) {
         for event in reader.read() {
@@ -368,29 +508,78 @@ mod tests {
     }
 
     #[test]
-    fn events_flushed_next_frame() {
+    fn events_survive_one_update_and_are_read_once() {
         let mut world = World::new();
         world.insert_resource(EventChannel::<PlayerDied>::new());
         world.insert_resource(Score(0));
 
-        let mut frame1 = Schedule::new();
-        frame1.add_system(send_death);
-        let mut frame1 = frame1.compile::<SingleThreadedExecutor>();
+        // The reader runs before the writer, so it only sees the event next frame.  `after`
+        // adds `count_deaths` to the schedule as well.
+        let mut frame = Schedule::new();
+        frame.add_system(send_death.after(count_deaths));
+        let mut frame = frame.compile::<SingleThreadedExecutor>();
 
-        let mut flush = Schedule::new();
-        flush.add_system(crate::events::event_channel::update_event_channel::<PlayerDied>);
-        let mut flush = flush.compile::<SingleThreadedExecutor>();
+        let mut update = Schedule::new();
+        update.add_system(crate::events::event_channel::update_event_channel::<PlayerDied>);
+        let mut update = update.compile::<SingleThreadedExecutor>();
 
-        let mut frame2 = Schedule::new();
-        frame2.add_system(count_deaths);
-        let mut frame2 = frame2.compile::<SingleThreadedExecutor>();
+        frame.run(&mut world);
+        update.run(&mut world);
+        assert_eq!(world.get_resource::<Score>().unwrap().0, 0);
 
-        frame1.run(&mut world);
-        flush.run(&mut world);
-        frame2.run(&mut world);
+        frame.run(&mut world);
+        update.run(&mut world);
+        assert_eq!(world.get_resource::<Score>().unwrap().0, 77);
 
-        // After flushing, count_deaths should see 0 events.
-        assert_eq!(world.get_resource::<Score>().unwrap().0, 0);
+        let mut read_only = Schedule::new();
+        read_only.add_system(count_deaths);
+        let mut read_only = read_only.compile::<SingleThreadedExecutor>();
+
+        // A fresh reader still sees the event written last frame, but not the one before.
+        read_only.run(&mut world);
+        assert_eq!(world.get_resource::<Score>().unwrap().0, 154);
+
+        update.run(&mut world);
+        read_only.run(&mut world);
+        assert_eq!(world.get_resource::<Score>().unwrap().0, 154);
+    }
+
+    #[derive(Resource, Default)]
+    struct ReaderLens(Vec<usize>);
+
+    fn skim_deaths(
+        mut reader: crate::events::event_reader::EventReader<PlayerDied>,
+        mut lens: ResMut<ReaderLens>,
+    ) {
+        lens.0.push(reader.len());
+        reader.clear();
+        lens.0.push(reader.len());
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn event_reader_len_and_clear() {
+        let mut world = World::new();
+        world.insert_resource(EventChannel::<PlayerDied>::new());
+        world.init_resource::<ReaderLens>();
+
+        let channel = world
+            .get_resource_mut::<EventChannel<PlayerDied>>()
+            .unwrap();
+        channel.push_event(PlayerDied { score: 1 });
+        channel.update();
+        channel.push_event(PlayerDied { score: 2 });
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(skim_deaths);
+        let mut schedule = schedule.compile::<SingleThreadedExecutor>();
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            world.get_resource::<ReaderLens>().unwrap().0,
+            vec![2, 0, 0, 0]
+        );
     }
 
     // ----- remove_component tests -----
@@ -495,4 +684,184 @@ mod tests {
         assert!(q.get_entity(e_with).is_some());
         assert!(q.get_entity(e_without).is_none());
     }
+
+    // ----- archetype graph tests -----
+
+    #[test]
+    fn toggling_a_component_reuses_cached_archetypes() {
+        let mut world = World::new();
+        let first = world.spawn(Position { x: 1.0, y: 0.0 });
+        let second = world.spawn(Position { x: 2.0, y: 0.0 });
+
+        for _ in 0..3 {
+            world.insert_component(Health, first);
+            world.insert_component(Health, second);
+            world.remove_component::<Health>(first);
+        }
+
+        // Position and (Position, Health) are the only archetypes ever created.
+        assert_eq!(world.archetypes().len(), 2);
+        assert!(world.get_component_for_entity::<Health>(first).is_none());
+        assert!(world.get_component_for_entity::<Health>(second).is_some());
+        assert_eq!(
+            world.get_component_for_entity::<Position>(first).unwrap().x,
+            1.0
+        );
+        assert_eq!(
+            world
+                .get_component_for_entity::<Position>(second)
+                .unwrap()
+                .x,
+            2.0
+        );
+    }
+
+    // ----- cached query state tests -----
+
+    #[derive(Component)]
+    struct Shield;
+
+    #[test]
+    fn query_state_picks_up_new_archetypes() {
+        use crate::query::QueryState;
+
+        let mut world = World::new();
+        world.spawn(Position { x: 1.0, y: 0.0 });
+
+        let mut state = QueryState::default();
+        state.update_archetypes::<&Position, ()>(&world);
+        assert_eq!(state.matched_archetypes().len(), 1);
+
+        // Only the archetype created since the last update is tested.
+        world.spawn((Position { x: 2.0, y: 0.0 }, Health));
+        world.spawn(Health);
+        state.update_archetypes::<&Position, ()>(&world);
+        assert_eq!(state.matched_archetypes().len(), 2);
+    }
+
+    #[test]
+    fn query_state_starts_over_for_a_different_world() {
+        use crate::query::QueryState;
+
+        let mut first = World::new();
+        first.spawn(Position { x: 1.0, y: 0.0 });
+
+        let mut state = QueryState::default();
+        state.update_archetypes::<&Position, ()>(&first);
+        assert_eq!(state.matched_archetypes(), &[0]);
+
+        // As many archetypes as the first world, but the match is elsewhere.
+        let mut second = World::new();
+        second.spawn(Health);
+        second.spawn(Position { x: 1.0, y: 0.0 });
+        state.update_archetypes::<&Position, ()>(&second);
+        assert_eq!(state.matched_archetypes(), &[1]);
+        assert!(!state.matches_archetype(0));
+    }
+
+    #[test]
+    fn or_and_not_filters_mix_archetype_and_change_checks() {
+        use crate::query::query_filter::Not;
+
+        let mut world = World::new();
+        let shielded = world.spawn((Position { x: 1.0, y: 0.0 }, Shield));
+        world.spawn((Position { x: 2.0, y: 0.0 }, Health));
+
+        world.tick();
+        let fresh = world.spawn((Position { x: 3.0, y: 0.0 }, Health));
+
+        let q = Query::<Entity, Or<(With<Shield>, Added<Health>)>>::new(
+            world.as_unsafe_world_cell_mut(),
+        );
+        let matched: Vec<_> = q.iter().collect();
+        assert_eq!(matched.len(), 2);
+        assert!(matched.contains(&shielded) && matched.contains(&fresh));
+        assert!(q.get_entity(fresh).is_some());
+
+        let q = Query::<Entity, Not<Added<Health>>>::new(world.as_unsafe_world_cell_mut());
+        assert_eq!(q.iter().count(), 2);
+        assert!(q.get_entity(fresh).is_none());
+        assert!(q.get_entity(shielded).is_some());
+    }
+
+    // ----- Local / ParamSet tests -----
+
+    #[derive(Resource, Default)]
+    struct Seen(Vec<u32>);
+
+    fn count_runs(mut runs: Local<u32>, mut seen: ResMut<Seen>) {
+        *runs += 1;
+        seen.0.push(*runs);
+    }
+
+    #[test]
+    fn local_persists_between_runs_per_system_instance() {
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(count_runs);
+        schedule.add_system(count_runs);
+        let mut schedule = schedule.compile::<SingleThreadedExecutor>();
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        // Each instance has its own counter: 1, 1 on the first run, 2, 2 on the second.
+        assert_eq!(world.get_resource::<Seen>().unwrap().0, vec![1, 1, 2, 2]);
+    }
+
+    fn move_to_furthest(mut set: ParamSet<(Query<&Position>, Query<&mut Position>)>) {
+        let furthest = set
+            .p0()
+            .iter()
+            .map(|position| position.x)
+            .fold(0.0, f32::max);
+        for mut position in set.p1().iter() {
+            position.x = furthest;
+        }
+    }
+
+    #[test]
+    fn param_set_hands_out_conflicting_queries_in_turn() {
+        let mut world = World::new();
+        world.spawn(Position { x: 1.0, y: 0.0 });
+        world.spawn(Position { x: 5.0, y: 0.0 });
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(move_to_furthest);
+        schedule.compile::<SingleThreadedExecutor>().run(&mut world);
+
+        let q = Query::<&Position>::new(world.as_unsafe_world_cell_mut());
+        assert!(q.iter().all(|position| position.x == 5.0));
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn push_through_last(
+        mut set: ParamSet<(
+            Res<Seen>,
+            Res<Seen>,
+            Res<Seen>,
+            Res<Seen>,
+            Res<Seen>,
+            ResMut<Seen>,
+        )>,
+    ) {
+        let len = set.p0().0.len() as u32;
+        set.p5().0.push(len);
+    }
+
+    #[test]
+    fn param_set_reaches_every_position_of_the_largest_set() {
+        let mut world = World::new();
+        world.init_resource::<Seen>();
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(push_through_last);
+        let mut schedule = schedule.compile::<SingleThreadedExecutor>();
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(world.get_resource::<Seen>().unwrap().0, vec![0, 1]);
+    }
 }
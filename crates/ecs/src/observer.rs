@@ -0,0 +1,342 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    marker::PhantomData,
+};
+
+use typle::typle;
+
+use crate::{
+    component::{Component, ComponentId},
+    entity::Entity,
+    events::Event,
+    system::input::SystemInput,
+    world::World,
+};
+
+/// Triggered right after a component of type `T` is added to an entity that did not have one.
+///
+/// Observe it with a [`Trigger<OnAdd<T>>`](Trigger) parameter; the entity is
+/// [`Trigger::entity`].
+pub struct OnAdd<T: Component> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Triggered right after a component of type `T` is inserted, whether it is new or replaces
+/// an existing value.  Fires after [`OnAdd`] for new components.
+pub struct OnInsert<T: Component> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Triggered right before a component of type `T` is removed from an entity, including when
+/// the entity is despawned.  The component can still be read from the observer, which runs
+/// before the component's `on_remove` hook.
+pub struct OnRemove<T: Component> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Identifies which observers receive a triggered event.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ObserverKey {
+    Add(ComponentId),
+    Insert(ComponentId),
+    Remove(ComponentId),
+    Event(TypeId),
+}
+
+/// An event that [observers](World::add_observer) can react to.
+///
+/// Implemented for every [`Event`] type, which can be triggered with
+/// [`World::trigger`], and for the component lifecycle events [`OnAdd`], [`OnInsert`] and
+/// [`OnRemove`], which the world triggers itself.
+pub trait ObserverEvent: Send + Sync + 'static {
+    /// Returns the key the observers of this event are registered under.
+    fn observer_key() -> ObserverKey;
+
+    /// Recovers the event from the type-erased payload passed to the observers.
+    fn from_payload(payload: &dyn Any) -> Option<&Self>;
+}
+
+impl<E: Event + 'static> ObserverEvent for E {
+    fn observer_key() -> ObserverKey {
+        ObserverKey::Event(TypeId::of::<E>())
+    }
+
+    fn from_payload(payload: &dyn Any) -> Option<&Self> {
+        payload.downcast_ref()
+    }
+}
+
+impl<T: Component> ObserverEvent for OnAdd<T> {
+    fn observer_key() -> ObserverKey {
+        ObserverKey::Add(ComponentId::of::<T>())
+    }
+
+    fn from_payload(_payload: &dyn Any) -> Option<&Self> {
+        Some(&OnAdd {
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: Component> ObserverEvent for OnInsert<T> {
+    fn observer_key() -> ObserverKey {
+        ObserverKey::Insert(ComponentId::of::<T>())
+    }
+
+    fn from_payload(_payload: &dyn Any) -> Option<&Self> {
+        Some(&OnInsert {
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: Component> ObserverEvent for OnRemove<T> {
+    fn observer_key() -> ObserverKey {
+        ObserverKey::Remove(ComponentId::of::<T>())
+    }
+
+    fn from_payload(_payload: &dyn Any) -> Option<&Self> {
+        Some(&OnRemove {
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// First parameter of every observer: the event that triggered it and its target entity.
+pub struct Trigger<'a, E> {
+    event: &'a E,
+    target: Option<Entity>,
+}
+
+impl<'a, E> Trigger<'a, E> {
+    /// Returns the triggered event.
+    pub fn event(&self) -> &'a E {
+        self.event
+    }
+
+    /// Returns the entity the event was triggered for, or `None` for an untargeted
+    /// [`World::trigger`].
+    pub fn target(&self) -> Option<Entity> {
+        self.target
+    }
+
+    /// Returns the entity the event was triggered for.
+    ///
+    /// # Panics
+    /// Panics if the event has no target.  Lifecycle events always have one.
+    pub fn entity(&self) -> Entity {
+        self.target
+            .expect("Trigger::entity called for an event triggered without a target")
+    }
+}
+
+/// Handle to a registered observer, used to remove it with [`World::remove_observer`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ObserverId(u64);
+
+/// A type-erased observer, run synchronously by the world whenever its event is triggered.
+pub trait Observer: Send + Sync + 'static {
+    /// Returns the fully-qualified name of the underlying function or type.
+    fn name(&self) -> &'static str;
+
+    /// Runs the observer for `payload`, then applies its deferred commands.
+    fn run(&mut self, payload: &dyn Any, target: Option<Entity>, world: &mut World);
+}
+
+/// Type alias for a boxed, type-erased observer.
+pub type BoxedObserver = Box<dyn Observer>;
+
+/// Wraps a function whose first parameter is a [`Trigger`] into an [`Observer`].
+pub(crate) struct FunctionObserver<F, E, Input: SystemInput> {
+    func: F,
+    observer_state: Input::State,
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<F, E, Input> FunctionObserver<F, E, Input>
+where
+    Input: SystemInput + 'static,
+{
+    pub fn new(func: F) -> Self {
+        Self {
+            func,
+            observer_state: Input::init_state(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[allow(unused_variables, unused_mut)]
+#[typle(Tuple for 0..=12)]
+impl<F, E, T> Observer for FunctionObserver<F, E, T>
+where
+    F: Send + Sync + 'static,
+    E: ObserverEvent,
+    T: Tuple,
+    T<_>: SystemInput + 'static,
+    for<'w, 's, 'e> F: FnMut(Trigger<E>, typle_args!(i in .. => T<{i}>))
+        + FnMut(Trigger<'e, E>, typle_args!(i in .. => T<{i}>::Data<'w, 's>)),
+{
+    fn name(&self) -> &'static str {
+        std::any::type_name::<F>()
+    }
+
+    fn run(&mut self, payload: &dyn Any, target: Option<Entity>, world: &mut World) {
+        let Some(event) = E::from_payload(payload) else {
+            return;
+        };
+        let trigger = Trigger { event, target };
+
+        let world_cell = world.as_unsafe_world_cell_mut();
+        (self.func)(
+            trigger,
+            typle_args!(i in .. => <T<{i}>>::get_data(&mut self.observer_state[[i]], world_cell)),
+        );
+
+        for typle_index!(i) in 0..T::LEN {
+            <T<{ i }>>::apply(&mut self.observer_state[[i]], world);
+        }
+    }
+}
+
+/// Conversion trait that turns a compatible function or closure into a [`BoxedObserver`].
+///
+/// Implemented for functions whose first parameter is a [`Trigger<E>`](Trigger) and whose
+/// other parameters implement [`SystemInput`], and for any type that already implements
+/// [`Observer`].
+///
+/// # Example
+/// ```
+/// use ecs::{Component, Entity, Query, World, observer::{OnAdd, Trigger}};
+///
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// fn announce(trigger: Trigger<OnAdd<Health>>, health: Query<&Health>) {
+///     let health = health.get_entity(trigger.entity()).unwrap();
+///     println!("spawned with {} hp", health.0);
+/// }
+///
+/// let mut world = World::new();
+/// world.add_observer(announce);
+/// world.spawn(Health(100.0));
+/// ```
+pub trait IntoObserver<E, Marker> {
+    /// Wraps `self` in a [`BoxedObserver`].
+    fn into_observer(self) -> BoxedObserver;
+}
+
+/// Marker used by the blanket [`IntoObserver`] impl for types that already implement
+/// [`Observer`].
+pub struct AlreadyObserver;
+
+impl<E, O: Observer> IntoObserver<E, AlreadyObserver> for O {
+    fn into_observer(self) -> BoxedObserver {
+        Box::new(self)
+    }
+}
+
+#[typle(Tuple for 0..=12)]
+impl<F, E, T> IntoObserver<E, T> for F
+where
+    F: Send + Sync + 'static,
+    E: ObserverEvent,
+    T: Tuple,
+    T<_>: SystemInput + 'static,
+    for<'w, 's, 'e> F: FnMut(Trigger<E>, typle_args!(i in .. => T<{i}>))
+        + FnMut(Trigger<'e, E>, typle_args!(i in .. => T<{i}>::Data<'w, 's>)),
+{
+    fn into_observer(self) -> BoxedObserver {
+        Box::new(FunctionObserver::<F, E, T>::new(self))
+    }
+}
+
+struct ObserverEntry {
+    id: ObserverId,
+    /// `None` for global observers, which see every trigger of their event.
+    target: Option<Entity>,
+    /// Taken out while the observer runs, so it can be handed `&mut World`.
+    observer: Option<BoxedObserver>,
+}
+
+/// Every observer registered in a [`World`], grouped by the event they react to.
+#[derive(Default)]
+pub(crate) struct Observers {
+    next_id: u64,
+    entries: HashMap<ObserverKey, Vec<ObserverEntry>>,
+}
+
+impl Observers {
+    pub(crate) fn add(
+        &mut self,
+        key: ObserverKey,
+        target: Option<Entity>,
+        observer: BoxedObserver,
+    ) -> ObserverId {
+        let id = ObserverId(self.next_id);
+        self.next_id += 1;
+        self.entries.entry(key).or_default().push(ObserverEntry {
+            id,
+            target,
+            observer: Some(observer),
+        });
+        id
+    }
+
+    pub(crate) fn remove(&mut self, id: ObserverId) {
+        for entries in self.entries.values_mut() {
+            entries.retain(|entry| entry.id != id);
+        }
+    }
+
+    /// Removes every observer scoped to `entity`.
+    pub(crate) fn remove_for_entity(&mut self, entity: Entity) {
+        for entries in self.entries.values_mut() {
+            entries.retain(|entry| entry.target != Some(entity));
+        }
+    }
+
+    /// Returns `true` if any observer is registered for `key`, so triggers can be skipped
+    /// cheaply.
+    pub(crate) fn has_observers(&self, key: ObserverKey) -> bool {
+        self.entries
+            .get(&key)
+            .is_some_and(|entries| !entries.is_empty())
+    }
+
+    /// Returns the observers of `key` that should run for a trigger targeting `target`.
+    pub(crate) fn matching(&self, key: ObserverKey, target: Option<Entity>) -> Vec<ObserverId> {
+        self.entries
+            .get(&key)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|entry| entry.target.is_none() || entry.target == target)
+                    .map(|entry| entry.id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn take(&mut self, key: ObserverKey, id: ObserverId) -> Option<BoxedObserver> {
+        self.entries
+            .get_mut(&key)?
+            .iter_mut()
+            .find(|entry| entry.id == id)?
+            .observer
+            .take()
+    }
+
+    /// Puts back an observer taken with [`take`](Observers::take), unless it was removed
+    /// while running.
+    pub(crate) fn put_back(&mut self, key: ObserverKey, id: ObserverId, observer: BoxedObserver) {
+        if let Some(entry) = self
+            .entries
+            .get_mut(&key)
+            .and_then(|entries| entries.iter_mut().find(|entry| entry.id == id))
+        {
+            entry.observer = Some(observer);
+        }
+    }
+}
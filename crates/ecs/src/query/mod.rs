@@ -1,16 +1,20 @@
-use std::{any::TypeId, marker::PhantomData};
+use std::{any::TypeId, borrow::Cow, marker::PhantomData};
 
+use fixedbitset::FixedBitSet;
 use typle::typle;
 
 pub mod change_detection;
+pub mod par_iter;
 pub mod query_filter;
 
 use crate::{
+    archetype::Archetype,
     component::{Component, ComponentId},
     entity::Entity,
-    query::{change_detection::Mut, query_filter::QueryFilter},
+    query::{change_detection::Mut, par_iter::QueryParIter, query_filter::QueryFilter},
     system::{access::SystemAccess, input::SystemInput},
-    world::UnsafeWorldCell,
+    table::TableRowIndex,
+    world::{UnsafeWorldCell, World, WorldId},
 };
 
 /// A type-safe view over all entities in a [`World`](crate::world::World) that
@@ -28,13 +32,60 @@ use crate::{
 ///     }
 /// }
 /// ```
-pub struct Query<'world, T: QueryData, F: QueryFilter = ()> {
+pub struct Query<'world, 'state, T: QueryData, F: QueryFilter = ()> {
     world: UnsafeWorldCell<'world>,
-    matched_indices: Vec<usize>,
+    state: Cow<'state, QueryState>,
     _marker_data: PhantomData<T>,
     _marker_filter: PhantomData<F>,
 }
 
+/// Cached archetype matches of a [`Query`], kept between runs of the same system.
+///
+/// Archetypes are only ever appended to a [`World`](crate::world::World), so the state only
+/// has to test the archetypes created since it was last updated.
+#[derive(Clone, Default)]
+pub struct QueryState {
+    world_id: Option<WorldId>,
+    matched_archetypes: Vec<usize>,
+    // The same matches, for constant-time lookups in `Query::get_entity`.
+    matched_set: FixedBitSet,
+    archetypes_seen: usize,
+}
+
+impl QueryState {
+    /// Tests the archetypes created since the last update against `T` and `F`.
+    pub fn update_archetypes<T: QueryData, F: QueryFilter>(&mut self, world: &World) {
+        if self.world_id != Some(world.id()) {
+            // A different world was passed in; start over.
+            *self = Self {
+                world_id: Some(world.id()),
+                ..Self::default()
+            };
+        }
+
+        let archetypes = world.archetypes();
+        self.matched_set.grow(archetypes.len());
+        let component_ids = T::component_ids();
+        for (index, archetype) in archetypes.iter().enumerate().skip(self.archetypes_seen) {
+            if archetype.contains_all(component_ids.clone()) && F::matches_archetype(archetype) {
+                self.matched_archetypes.push(index);
+                self.matched_set.insert(index);
+            }
+        }
+        self.archetypes_seen = archetypes.len();
+    }
+
+    /// Returns the indices of the archetypes matched so far.
+    pub fn matched_archetypes(&self) -> &[usize] {
+        &self.matched_archetypes
+    }
+
+    /// Returns `true` if the archetype at `index` was matched.
+    pub fn matches_archetype(&self, index: usize) -> bool {
+        self.matched_set.contains(index)
+    }
+}
+
 /// Describes what data a [`Query`] fetches from each matching entity.
 ///
 /// Implementations are provided for `&T`, `&mut T`, `Entity`, `Option<&T>`,
@@ -53,26 +104,18 @@ pub trait QueryData {
     fn fill_access(access: &mut SystemAccess);
 }
 
-impl<'world, T: QueryData, F: QueryFilter> Query<'world, T, F> {
-    /// Constructs a new query by scanning the world's archetypes for matches.
+impl<'world, T: QueryData, F: QueryFilter> Query<'world, '_, T, F> {
+    /// Constructs a new query by scanning all of the world's archetypes for matches.
+    ///
+    /// Systems get their queries from a cached [`QueryState`] instead, which only scans
+    /// archetypes created since the previous run.
     pub fn new(world: UnsafeWorldCell<'world>) -> Self {
-        let matched_indices: Vec<usize> = world
-            .world()
-            .archetypes()
-            .iter()
-            .enumerate()
-            .filter_map(|(index, archetype)| {
-                if archetype.contains_all(T::component_ids()) {
-                    Some(index)
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let mut state = QueryState::default();
+        state.update_archetypes::<T, F>(world.world());
 
         Self {
             world,
-            matched_indices,
+            state: Cow::Owned(state),
             _marker_data: PhantomData,
             _marker_filter: PhantomData,
         }
@@ -82,10 +125,30 @@ impl<'world, T: QueryData, F: QueryFilter> Query<'world, T, F> {
     pub fn iter<'s>(&'s self) -> QueryIter<'world, 's, T, F> {
         QueryIter {
             world: self.world,
-            matched_archetypes: self.matched_indices.iter(),
-            current_entities: &[],
+            matched_archetypes: self.state.matched_archetypes.iter(),
+            current_archetype: None,
             current_row: 0,
             current_len: 0,
+            current_tick: *self.world.world().current_tick(),
+            _marker_data: PhantomData,
+            _marker_filter: PhantomData,
+        }
+    }
+
+    /// Returns a parallel iterator over all matching entities.
+    ///
+    /// ```ignore
+    /// fn integrate(bodies: Query<(&mut Transform, &Velocity)>) {
+    ///     bodies.par_iter().for_each(|(mut transform, velocity)| {
+    ///         transform.translation += velocity.linear;
+    ///     });
+    /// }
+    /// ```
+    pub fn par_iter<'s>(&'s self) -> QueryParIter<'world, 's, T, F> {
+        QueryParIter {
+            world: self.world,
+            matched_archetypes: &self.state.matched_archetypes,
+            batch_size: None,
             _marker_data: PhantomData,
             _marker_filter: PhantomData,
         }
@@ -93,9 +156,20 @@ impl<'world, T: QueryData, F: QueryFilter> Query<'world, T, F> {
 
     /// Fetches the query data for a specific entity, returning `None` if it doesn't match.
     pub fn get_entity(&self, entity: Entity) -> Option<T::Item<'world>> {
-        if !F::filter(self.world, entity) {
+        let location = self.world.world().entity_store().find_location(entity)?;
+        if !self
+            .state
+            .matches_archetype(location.archetype_index as usize)
+        {
             return None;
         }
+        if !F::is_archetypal() {
+            let world = self.world.world();
+            let archetype = &world.archetypes()[location.archetype_index as usize];
+            if !F::filter_row(archetype, location.row, *world.current_tick()) {
+                return None;
+            }
+        }
         T::fetch(self.world, entity)
     }
 
@@ -108,9 +182,10 @@ impl<'world, T: QueryData, F: QueryFilter> Query<'world, T, F> {
 pub struct QueryIter<'world, 'a, T, F> {
     world: UnsafeWorldCell<'world>,
     matched_archetypes: core::slice::Iter<'a, usize>,
-    current_entities: &'world [Entity],
+    current_archetype: Option<&'world Archetype>,
     current_row: usize,
     current_len: usize,
+    current_tick: u32,
     _marker_data: PhantomData<T>,
     _marker_filter: PhantomData<F>,
 }
@@ -123,48 +198,56 @@ where
     type Item = T::Item<'world>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let archetypes = self.world.world().archetypes();
         loop {
             if self.current_row == self.current_len {
                 let archetype_index = self.matched_archetypes.next()?;
+                let archetype = &self.world.world().archetypes()[*archetype_index];
 
-                let archetype = &archetypes[*archetype_index];
-
+                self.current_archetype = Some(archetype);
                 self.current_row = 0;
                 self.current_len = archetype.len();
-                self.current_entities = archetype.entities();
-            }
-
-            if self.current_entities.is_empty() {
                 continue;
             }
 
-            let entity = self.current_entities[self.current_row];
+            let archetype = self.current_archetype?;
+            let row = self.current_row;
             self.current_row += 1;
-            if !F::filter(self.world, entity) {
+
+            if !F::is_archetypal()
+                && !F::filter_row(archetype, TableRowIndex::new(row), self.current_tick)
+            {
                 continue;
             }
 
-            return T::fetch(self.world, entity);
+            return T::fetch(self.world, archetype.entities()[row]);
         }
     }
 }
 
-impl<T, F> SystemInput for Query<'_, T, F>
+impl<T, F> SystemInput for Query<'_, '_, T, F>
 where
     T: QueryData,
     F: QueryFilter,
 {
-    type State = ();
-    type Data<'world, 'state> = Query<'world, T, F>;
+    type State = QueryState;
+    type Data<'world, 'state> = Query<'world, 'state, T, F>;
 
-    fn init_state() -> Self::State {}
+    fn init_state() -> Self::State {
+        QueryState::default()
+    }
 
     fn get_data<'world, 'state>(
-        _state: &'state mut Self::State,
+        state: &'state mut Self::State,
         world: UnsafeWorldCell<'world>,
     ) -> Self::Data<'world, 'state> {
-        Query::new(world)
+        state.update_archetypes::<T, F>(world.world());
+
+        Query {
+            world,
+            state: Cow::Borrowed(state),
+            _marker_data: PhantomData,
+            _marker_filter: PhantomData,
+        }
     }
 
     fn fill_access(access: &mut crate::system::access::SystemAccess) {
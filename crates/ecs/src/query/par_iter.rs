@@ -0,0 +1,118 @@
+use std::{marker::PhantomData, ops::Range};
+
+use crate::{
+    query::{query_filter::QueryFilter, QueryData},
+    table::TableRowIndex,
+    world::UnsafeWorldCell,
+};
+
+/// Number of batches handed to each worker thread when no batch size is set, so threads that
+/// finish early can pick up more work.
+#[cfg(all(feature = "multithreaded", not(target_arch = "wasm32")))]
+const BATCHES_PER_THREAD: usize = 4;
+
+/// Smallest batch size picked automatically; smaller batches cost more to schedule than to run.
+#[cfg(all(feature = "multithreaded", not(target_arch = "wasm32")))]
+const MIN_BATCH_SIZE: usize = 64;
+
+/// A parallel iterator over the entities matched by a [`Query`](super::Query).
+///
+/// Created with [`Query::par_iter`](super::Query::par_iter).  The rows of the matched
+/// archetypes are split into batches that run on the `ComputeTaskPool`.  Without the
+/// `multithreaded` feature, or on wasm, all rows are visited serially on the calling thread.
+pub struct QueryParIter<'world, 's, T, F> {
+    pub(super) world: UnsafeWorldCell<'world>,
+    pub(super) matched_archetypes: &'s [usize],
+    pub(super) batch_size: Option<usize>,
+    pub(super) _marker_data: PhantomData<T>,
+    pub(super) _marker_filter: PhantomData<F>,
+}
+
+impl<'world, T, F> QueryParIter<'world, '_, T, F>
+where
+    T: QueryData,
+    F: QueryFilter,
+{
+    /// Sets how many rows each task visits.
+    ///
+    /// By default the rows are split evenly across the pool's threads, a few batches per
+    /// thread, with at least 64 rows per batch.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size.max(1));
+        self
+    }
+
+    /// Calls `func` on every matching entity, possibly from several threads at once.
+    pub fn for_each<Func>(self, func: Func)
+    where
+        Func: Fn(T::Item<'world>) + Send + Sync,
+    {
+        #[cfg(all(feature = "multithreaded", not(target_arch = "wasm32")))]
+        {
+            use tasks::{compute_pool::ComputeTaskPool, task_pool::TaskPool};
+
+            let pool = ComputeTaskPool::get_or_init(|| TaskPool::with_name("compute"));
+            let thread_num = pool.thread_num();
+            if thread_num > 1 {
+                let archetypes = self.world.world().archetypes();
+                let batch_size = self.batch_size.unwrap_or_else(|| {
+                    let row_count: usize = self
+                        .matched_archetypes
+                        .iter()
+                        .map(|index| archetypes[*index].entities().len())
+                        .sum();
+                    row_count
+                        .div_ceil(thread_num * BATCHES_PER_THREAD)
+                        .max(MIN_BATCH_SIZE)
+                });
+
+                let world = self.world;
+                let func = &func;
+                pool.scope(|scope| {
+                    for &archetype_index in self.matched_archetypes {
+                        let row_count = archetypes[archetype_index].entities().len();
+                        for start in (0..row_count).step_by(batch_size) {
+                            let rows = start..(start + batch_size).min(row_count);
+                            scope.spawn(async move {
+                                // SAFETY: batches never share a row, so no two tasks fetch
+                                // the same component.
+                                Self::for_each_in_rows(world, archetype_index, rows, func);
+                            });
+                        }
+                    }
+                });
+                return;
+            }
+        }
+
+        for &archetype_index in self.matched_archetypes {
+            let row_count = self.world.world().archetypes()[archetype_index]
+                .entities()
+                .len();
+            Self::for_each_in_rows(self.world, archetype_index, 0..row_count, &func);
+        }
+    }
+
+    fn for_each_in_rows<Func>(
+        world: UnsafeWorldCell<'world>,
+        archetype_index: usize,
+        rows: Range<usize>,
+        func: &Func,
+    ) where
+        Func: Fn(T::Item<'world>),
+    {
+        let archetype = &world.world().archetypes()[archetype_index];
+        let current_tick = *world.world().current_tick();
+
+        for row in rows {
+            if !F::is_archetypal()
+                && !F::filter_row(archetype, TableRowIndex::new(row), current_tick)
+            {
+                continue;
+            }
+            if let Some(item) = T::fetch(world, archetype.entities()[row]) {
+                func(item);
+            }
+        }
+    }
+}
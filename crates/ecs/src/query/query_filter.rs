@@ -1,24 +1,67 @@
 use std::marker::PhantomData;
 
-use crate::{component::bundle::ComponentBundle, entity::Entity, world::UnsafeWorldCell};
+use crate::{
+    archetype::Archetype, component::bundle::ComponentBundle, entity::Entity, table::TableRowIndex,
+    world::UnsafeWorldCell,
+};
 use typle::typle;
 
 /// Restricts which entities a [`Query`](super::Query) visits.
 ///
 /// Multiple filters can be combined in a tuple: `(With<A>, Without<B>)` matches
 /// entities that have `A` but not `B`.  [`Or`] can be used for disjunctions.
+///
+/// A filter is evaluated in two steps.  [`matches_archetype`](QueryFilter::matches_archetype)
+/// is checked once per archetype and cached by the query; [`filter_row`](QueryFilter::filter_row)
+/// is only checked per entity, within matching archetypes, for filters that are not
+/// [archetypal](QueryFilter::is_archetypal) (i.e. change-detection filters).  An entity matches
+/// when both return `true`.
 pub trait QueryFilter {
-    fn filter<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
-        Self::filter_and(world, entity)
+    /// Returns `true` if [`matches_archetype`](QueryFilter::matches_archetype) alone decides
+    /// the filter, so [`filter_row`](QueryFilter::filter_row) never needs to run.
+    fn is_archetypal() -> bool {
+        true
+    }
+
+    /// Returns `false` if no entity of `archetype` can match the filter.
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        Self::matches_archetype_and(archetype)
     }
 
-    fn filter_and<'w>(_world: UnsafeWorldCell<'w>, _entity: Entity) -> bool {
+    fn matches_archetype_and(_archetype: &Archetype) -> bool {
         true
     }
 
-    fn filter_or<'w>(_world: UnsafeWorldCell<'w>, _entity: Entity) -> bool {
+    fn matches_archetype_or(_archetype: &Archetype) -> bool {
+        true
+    }
+
+    /// Checks the entity at `row` of an archetype accepted by
+    /// [`matches_archetype`](QueryFilter::matches_archetype).
+    fn filter_row(archetype: &Archetype, row: TableRowIndex, current_tick: u32) -> bool {
+        Self::filter_row_and(archetype, row, current_tick)
+    }
+
+    fn filter_row_and(_archetype: &Archetype, _row: TableRowIndex, _current_tick: u32) -> bool {
         true
     }
+
+    fn filter_row_or(_archetype: &Archetype, _row: TableRowIndex, _current_tick: u32) -> bool {
+        true
+    }
+
+    /// Evaluates the whole filter for a single entity.
+    fn filter<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
+        let world = world.world();
+        let Some(location) = world.entity_store().find_location(entity) else {
+            return false;
+        };
+        let archetype = &world.archetypes()[location.archetype_index as usize];
+
+        Self::matches_archetype(archetype)
+            && (Self::is_archetypal()
+                || Self::filter_row(archetype, location.row, *world.current_tick()))
+    }
 }
 
 #[allow(unused_mut)]
@@ -29,18 +72,48 @@ where
     T: Tuple,
     T<_>: QueryFilter,
 {
-    fn filter_and<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
+    fn is_archetypal() -> bool {
+        for typle_index!(i) in 0..T::LEN {
+            if !T::<{ i }>::is_archetypal() {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_archetype_and(archetype: &Archetype) -> bool {
+        for typle_index!(i) in 0..T::LEN {
+            if !T::<{ i }>::matches_archetype(archetype) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_archetype_or(archetype: &Archetype) -> bool {
+        for typle_index!(i) in 0..T::LEN {
+            if T::<{ i }>::matches_archetype(archetype) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn filter_row_and(archetype: &Archetype, row: TableRowIndex, current_tick: u32) -> bool {
         for typle_index!(i) in 0..T::LEN {
-            if !T::<{ i }>::filter(world, entity) {
+            if !T::<{ i }>::filter_row(archetype, row, current_tick) {
                 return false;
             }
         }
         true
     }
 
-    fn filter_or<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
+    fn filter_row_or(archetype: &Archetype, row: TableRowIndex, current_tick: u32) -> bool {
+        // The archetype only had to match one branch, so each branch is re-checked here.
         for typle_index!(i) in 0..T::LEN {
-            if T::<{ i }>::filter_or(world, entity) {
+            if T::<{ i }>::matches_archetype(archetype)
+                && T::<{ i }>::filter_row(archetype, row, current_tick)
+            {
                 return true;
             }
         }
@@ -57,13 +130,18 @@ impl<T> QueryFilter for Added<T>
 where
     T: ComponentBundle,
 {
-    fn filter<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
-        for component_id in T::get_component_ids() {
-            if !world.world().was_component_added(entity, component_id) {
-                return false;
-            }
-        }
-        true
+    fn is_archetypal() -> bool {
+        false
+    }
+
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains_all(T::get_component_ids())
+    }
+
+    fn filter_row(archetype: &Archetype, row: TableRowIndex, current_tick: u32) -> bool {
+        T::get_component_ids()
+            .into_iter()
+            .all(|component_id| archetype.was_entity_added(component_id, row, current_tick))
     }
 }
 
@@ -76,13 +154,18 @@ impl<T> QueryFilter for Changed<T>
 where
     T: ComponentBundle,
 {
-    fn filter<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
-        for component_id in T::get_component_ids() {
-            if !world.world().was_component_changed(entity, component_id) {
-                return false;
-            }
-        }
-        true
+    fn is_archetypal() -> bool {
+        false
+    }
+
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains_all(T::get_component_ids())
+    }
+
+    fn filter_row(archetype: &Archetype, row: TableRowIndex, current_tick: u32) -> bool {
+        T::get_component_ids()
+            .into_iter()
+            .all(|component_id| archetype.was_entity_changed(component_id, row, current_tick))
     }
 }
 
@@ -95,20 +178,8 @@ impl<T> QueryFilter for With<T>
 where
     T: ComponentBundle,
 {
-    fn filter<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
-        let world = world.world();
-
-        match world.entity_store().find_location(entity) {
-            Some(location) => {
-                let archetypes = world.archetypes();
-                let archetype = &archetypes[location.archetype_index as usize];
-                let archetype_ids = archetype.component_ids();
-                let component_ids = T::get_component_ids();
-
-                component_ids.iter().all(|id| archetype_ids.contains(id))
-            }
-            None => false,
-        }
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        archetype.contains_all(T::get_component_ids())
     }
 }
 
@@ -121,8 +192,18 @@ impl<T> QueryFilter for Not<T>
 where
     T: QueryFilter,
 {
-    fn filter<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
-        !T::filter(world, entity)
+    fn is_archetypal() -> bool {
+        T::is_archetypal()
+    }
+
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        // A row-level filter may reject some rows of any archetype, so its negation can only
+        // be decided per row.
+        !T::is_archetypal() || !T::matches_archetype(archetype)
+    }
+
+    fn filter_row(archetype: &Archetype, row: TableRowIndex, current_tick: u32) -> bool {
+        !(T::matches_archetype(archetype) && T::filter_row(archetype, row, current_tick))
     }
 }
 
@@ -144,7 +225,15 @@ impl<T> QueryFilter for Or<T>
 where
     T: QueryFilter,
 {
-    fn filter<'w>(world: UnsafeWorldCell<'w>, entity: Entity) -> bool {
-        T::filter_or(world, entity)
+    fn is_archetypal() -> bool {
+        T::is_archetypal()
+    }
+
+    fn matches_archetype(archetype: &Archetype) -> bool {
+        T::matches_archetype_or(archetype)
+    }
+
+    fn filter_row(archetype: &Archetype, row: TableRowIndex, current_tick: u32) -> bool {
+        T::filter_row_or(archetype, row, current_tick)
     }
 }
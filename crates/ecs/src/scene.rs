@@ -0,0 +1,172 @@
+//! Saving a [`World`]'s entities to a JSON scene document and spawning them back.
+//!
+//! Only components registered with [`World::register_reflection`] are saved; an entity with
+//! none of them is left out of the scene.  The hierarchy is saved as each entity's list of
+//! children, rather than as [`ChildOf`]/[`Children`] components, and rebuilt with
+//! [`World::add_child`] on load.
+//!
+//! Entities are saved under their handle at the time of saving.  When the scene is loaded,
+//! every entity gets a fresh handle and references to saved entities — in the hierarchy and in
+//! components implementing [`Component::map_entities`] — are rewritten to match.
+//!
+//! # Example
+//! ```
+//! use ecs::{Component, World, scene::Scene};
+//! use facet::Facet;
+//!
+//! #[derive(Component, Facet)]
+//! struct Health(f32);
+//!
+//! let mut world = World::new();
+//! world.register_reflection::<Health>();
+//! world.spawn(Health(100.0));
+//!
+//! let json = Scene::from_world(&world).to_json().unwrap();
+//!
+//! let mut loaded = World::new();
+//! loaded.register_reflection::<Health>();
+//! Scene::from_json(&json).unwrap().spawn_into(&mut loaded);
+//! ```
+//!
+//! [`ChildOf`]: crate::entity::hierarchy::ChildOf
+//! [`Children`]: crate::entity::hierarchy::Children
+//! [`Component::map_entities`]: crate::component::Component::map_entities
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use facet::Facet;
+use facet_json::RawJson;
+use log::warn;
+
+use crate::{entity::hierarchy::Children, entity::Entity, world::World};
+
+/// A serializable snapshot of the reflected entities of a [`World`].
+#[derive(Facet, Default)]
+pub struct Scene {
+    pub entities: Vec<SceneEntity>,
+}
+
+/// One saved entity.
+#[derive(Facet)]
+pub struct SceneEntity {
+    /// The entity's handle when the scene was saved.  Only used to resolve references
+    /// within the scene.
+    pub entity: Entity,
+    /// Components by their [`Component::name`](crate::component::Component::name), as JSON.
+    pub components: BTreeMap<String, RawJson<'static>>,
+    /// Children that are part of the scene, in order.
+    pub children: Vec<Entity>,
+}
+
+impl Scene {
+    /// Saves every entity of `world` that has at least one reflected component.
+    pub fn from_world(world: &World) -> Self {
+        let mut entities = Vec::new();
+
+        for archetype in world.archetypes() {
+            let reflections: Vec<_> = archetype
+                .component_ids()
+                .iter()
+                .filter_map(|id| world.get_reflection_by_id(*id))
+                .collect();
+            if reflections.is_empty() {
+                continue;
+            }
+
+            for &entity in archetype.entities() {
+                let mut components = BTreeMap::new();
+                for reflection in &reflections {
+                    match reflection.serialize(world, entity) {
+                        Some(Ok(json)) => {
+                            components
+                                .insert(reflection.name().to_string(), RawJson::from_owned(json));
+                        }
+                        Some(Err(err)) => warn!(
+                            "Failed to serialize component '{}' of {entity}: {err}",
+                            reflection.name()
+                        ),
+                        None => {}
+                    }
+                }
+                entities.push(SceneEntity {
+                    entity,
+                    components,
+                    children: Vec::new(),
+                });
+            }
+        }
+
+        // Fill in the children once every saved entity is known, so links to entities
+        // left out of the scene are dropped.
+        let saved: HashSet<Entity> = entities
+            .iter()
+            .map(|scene_entity| scene_entity.entity)
+            .collect();
+        for scene_entity in &mut entities {
+            if let Some(children) = world.get_component_for_entity::<Children>(scene_entity.entity)
+            {
+                scene_entity.children = children
+                    .iter()
+                    .copied()
+                    .filter(|child| saved.contains(child))
+                    .collect();
+            }
+        }
+
+        Self { entities }
+    }
+
+    /// Parses a scene written by [`to_json`](Scene::to_json).
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        facet_json::from_str(json).map_err(|err| anyhow::anyhow!("failed to parse scene: {err}"))
+    }
+
+    /// Writes the scene as pretty-printed JSON.
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        facet_json::to_string_pretty(self)
+            .map_err(|err| anyhow::anyhow!("failed to serialize scene: {err}"))
+    }
+
+    /// Spawns the scene's entities into `world` and returns the saved handle of each, mapped
+    /// to its newly spawned entity.
+    ///
+    /// Components whose name is not registered with [`World::register_reflection`], or whose
+    /// data does not match the registered type, are skipped with a warning.  References to
+    /// entities outside the scene are left as they are.
+    pub fn spawn_into(&self, world: &mut World) -> HashMap<Entity, Entity> {
+        let entity_map: HashMap<Entity, Entity> = self
+            .entities
+            .iter()
+            .map(|scene_entity| (scene_entity.entity, world.spawn(())))
+            .collect();
+        let mut mapper = |entity: Entity| entity_map.get(&entity).copied().unwrap_or(entity);
+
+        for scene_entity in &self.entities {
+            let entity = entity_map[&scene_entity.entity];
+            for (name, json) in &scene_entity.components {
+                let Some(reflection) = world.get_reflection(name).cloned() else {
+                    warn!(
+                        "Skipping component '{name}': no type registered under that name (register it with register_reflection)"
+                    );
+                    continue;
+                };
+                if let Err(err) = reflection.insert_json(json.as_str(), world, entity, &mut mapper)
+                {
+                    warn!("Failed to load component '{name}': {err}");
+                }
+            }
+        }
+
+        for scene_entity in &self.entities {
+            let parent = entity_map[&scene_entity.entity];
+            for child in &scene_entity.children {
+                match entity_map.get(child) {
+                    Some(&child) => world.add_child(parent, child),
+                    None => warn!("Skipping child {child} of {parent}: not part of the scene"),
+                }
+            }
+        }
+
+        entity_map
+    }
+}
@@ -0,0 +1,434 @@
+//! Finite state machines driving which systems run (main menu → loading → in-game → paused).
+//!
+//! A state type implements [`States`]; the current value lives in the [`State<S>`] resource and
+//! transitions are requested through [`NextState<S>`].  When a transition is applied, the
+//! [`OnExit`], [`OnTransition`] and [`OnEnter`] schedules registered for the involved values run
+//! in that order.  Use [`in_state`] to gate regular systems on the current state.
+
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+pub use ecs_macros::States;
+
+use crate::{
+    resource::Resource,
+    system::{
+        access::SystemAccess,
+        condition::Condition,
+        executor::SystemExecutor,
+        schedule::{CompiledSchedule, Schedule},
+        IntoSystemConfig,
+    },
+    world::{UnsafeWorldCell, World},
+};
+
+/// Marker trait for the values of a state machine.
+///
+/// Derive with `#[derive(States)]`, usually on a field-less enum.
+///
+/// # Example
+/// ```
+/// use ecs::state::States;
+///
+/// #[derive(States, Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+///     Paused,
+/// }
+/// ```
+pub trait States: Clone + Debug + Hash + Eq + Send + Sync + 'static {}
+
+/// Resource holding the current value of the state machine `S`.
+///
+/// Read-only from systems: request a change through [`NextState<S>`] instead.
+#[derive(Resource)]
+pub struct State<S: States> {
+    current: S,
+}
+
+impl<S: States> State<S> {
+    pub fn new(current: S) -> Self {
+        Self { current }
+    }
+
+    /// Returns the current state.
+    pub fn get(&self) -> &S {
+        &self.current
+    }
+}
+
+/// Resource used to request a transition of the state machine `S`.
+///
+/// The transition is applied once per frame, before the `FixedUpdate` phase.  If several
+/// transitions are requested in the same frame, the last one wins.
+#[derive(Resource)]
+pub struct NextState<S: States> {
+    next: Option<S>,
+}
+
+impl<S: States> NextState<S> {
+    pub fn new() -> Self {
+        Self { next: None }
+    }
+
+    /// Requests a transition to `state`.
+    pub fn set(&mut self, state: S) {
+        self.next = Some(state);
+    }
+
+    /// Returns the pending transition, if any.
+    pub fn pending(&self) -> Option<&S> {
+        self.next.as_ref()
+    }
+}
+
+impl<S: States> Default for NextState<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Schedule label for systems that run once when the state machine enters the given state.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct OnEnter<S: States>(pub S);
+
+/// Schedule label for systems that run once when the state machine leaves the given state.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct OnExit<S: States>(pub S);
+
+/// Schedule label for systems that run once on a specific `from → to` transition.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct OnTransition<S: States> {
+    pub from: S,
+    pub to: S,
+}
+
+/// A label identifying one of the schedules of a state machine.
+///
+/// Implemented by [`OnEnter`], [`OnExit`] and [`OnTransition`].
+pub trait StateScheduleLabel<S: States> {
+    /// Returns the schedule this label refers to, creating it if needed.
+    fn schedule_mut(self, schedules: &mut StateSchedules<S>) -> &mut Schedule;
+}
+
+impl<S: States> StateScheduleLabel<S> for OnEnter<S> {
+    fn schedule_mut(self, schedules: &mut StateSchedules<S>) -> &mut Schedule {
+        schedules.on_enter.entry(self.0).or_default()
+    }
+}
+
+impl<S: States> StateScheduleLabel<S> for OnExit<S> {
+    fn schedule_mut(self, schedules: &mut StateSchedules<S>) -> &mut Schedule {
+        schedules.on_exit.entry(self.0).or_default()
+    }
+}
+
+impl<S: States> StateScheduleLabel<S> for OnTransition<S> {
+    fn schedule_mut(self, schedules: &mut StateSchedules<S>) -> &mut Schedule {
+        schedules
+            .on_transition
+            .entry((self.from, self.to))
+            .or_default()
+    }
+}
+
+/// The enter/exit/transition schedules of the state machine `S`, before compilation.
+#[derive(Resource)]
+pub struct StateSchedules<S: States> {
+    on_enter: HashMap<S, Schedule>,
+    on_exit: HashMap<S, Schedule>,
+    on_transition: HashMap<(S, S), Schedule>,
+}
+
+impl<S: States> StateSchedules<S> {
+    pub fn new() -> Self {
+        Self {
+            on_enter: HashMap::new(),
+            on_exit: HashMap::new(),
+            on_transition: HashMap::new(),
+        }
+    }
+
+    /// Registers a system in the schedule identified by `label`.
+    pub fn add_system<M>(
+        &mut self,
+        label: impl StateScheduleLabel<S>,
+        system: impl IntoSystemConfig<M> + 'static,
+    ) {
+        label.schedule_mut(self).add_system(system);
+    }
+
+    pub fn compile<T: SystemExecutor + 'static>(self) -> CompiledStateSchedules<S> {
+        CompiledStateSchedules {
+            on_enter: compile_all::<_, T>(self.on_enter),
+            on_exit: compile_all::<_, T>(self.on_exit),
+            on_transition: compile_all::<_, T>(self.on_transition),
+        }
+    }
+}
+
+impl<S: States> Default for StateSchedules<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn compile_all<K: Eq + Hash, T: SystemExecutor + 'static>(
+    schedules: HashMap<K, Schedule>,
+) -> HashMap<K, CompiledSchedule> {
+    schedules
+        .into_iter()
+        .map(|(key, schedule)| (key, schedule.compile::<T>()))
+        .collect()
+}
+
+/// The compiled enter/exit/transition schedules of the state machine `S`.
+#[derive(Resource)]
+pub struct CompiledStateSchedules<S: States> {
+    on_enter: HashMap<S, CompiledSchedule>,
+    on_exit: HashMap<S, CompiledSchedule>,
+    on_transition: HashMap<(S, S), CompiledSchedule>,
+}
+
+impl<S: States> CompiledStateSchedules<S> {
+    /// Runs the [`OnEnter`] schedule of `state`, if any.
+    pub fn enter(&mut self, state: &S, world: &mut World) {
+        if let Some(schedule) = self.on_enter.get_mut(state) {
+            profiling::scope!("schedule::on_enter");
+            schedule.run(world);
+        }
+    }
+
+    /// Runs the [`OnExit`] schedule of `state`, if any.
+    pub fn exit(&mut self, state: &S, world: &mut World) {
+        if let Some(schedule) = self.on_exit.get_mut(state) {
+            profiling::scope!("schedule::on_exit");
+            schedule.run(world);
+        }
+    }
+
+    /// Runs the [`OnTransition`] schedule of `from → to`, if any.
+    pub fn transition(&mut self, from: &S, to: &S, world: &mut World) {
+        if let Some(schedule) = self.on_transition.get_mut(&(from.clone(), to.clone())) {
+            profiling::scope!("schedule::on_transition");
+            schedule.run(world);
+        }
+    }
+}
+
+/// Applies the transition pending in [`NextState<S>`], if any.
+///
+/// Runs `OnExit(from)`, updates [`State<S>`], then runs `OnTransition { from, to }` and
+/// `OnEnter(to)`.  Requesting the current state again is a no-op.
+///
+/// # Panics
+/// Panics if the state machine `S` was not initialised in `world`.
+pub fn apply_state_transition<S: States>(world: &mut World) {
+    let Some(next) = world
+        .get_resource_mut::<NextState<S>>()
+        .expect("NextState resource not found, was the state initialised?")
+        .next
+        .take()
+    else {
+        return;
+    };
+
+    let current = world
+        .get_resource::<State<S>>()
+        .expect("State resource not found, was the state initialised?")
+        .current
+        .clone();
+
+    if current == next {
+        return;
+    }
+
+    let mut schedules = world
+        .remove_resource::<CompiledStateSchedules<S>>()
+        .expect("Compiled state schedules not found!");
+
+    schedules.exit(&current, world);
+    world
+        .get_resource_mut::<State<S>>()
+        .expect("State resource not found, was the state initialised?")
+        .current = next.clone();
+    schedules.transition(&current, &next, world);
+    schedules.enter(&next, world);
+
+    world.insert_resource(schedules);
+}
+
+/// Runs the [`OnEnter`] schedule of the current value of `S`.
+///
+/// Used once, after the startup schedule, so that the initial state is entered like any other.
+pub fn enter_initial_state<S: States>(world: &mut World) {
+    let current = world
+        .get_resource::<State<S>>()
+        .expect("State resource not found, was the state initialised?")
+        .current
+        .clone();
+
+    let mut schedules = world
+        .remove_resource::<CompiledStateSchedules<S>>()
+        .expect("Compiled state schedules not found!");
+
+    schedules.enter(&current, world);
+
+    world.insert_resource(schedules);
+}
+
+/// Condition that is `true` while the state machine `S` is in a given state.  Created with
+/// [`in_state`].
+pub struct InState<S: States> {
+    state: S,
+}
+
+impl<S: States> Condition for InState<S> {
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn access(&self) -> SystemAccess {
+        let mut access = SystemAccess::default();
+        access.read_resource::<State<S>>();
+        access
+    }
+
+    unsafe fn evaluate_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        world
+            .world()
+            .get_resource::<State<S>>()
+            .is_some_and(|current| current.current == self.state)
+    }
+}
+
+/// Returns a condition that is `true` while the state machine `S` is in `state`.
+///
+/// ```
+/// use ecs::{Schedule, IntoSystemConfig, state::{in_state, States}};
+///
+/// #[derive(States, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum GameState {
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// fn move_players() {}
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add_system(move_players.run_if(in_state(GameState::InGame)));
+/// ```
+pub fn in_state<S: States>(state: S) -> InState<S> {
+    InState { state }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        resource::{Res, ResMut},
+        system::executor::single_thread::SingleThreadedExecutor,
+    };
+
+    #[derive(States, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    enum GameState {
+        Menu,
+        InGame,
+    }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn world_in(state: GameState, schedules: StateSchedules<GameState>) -> World {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.insert_resource(State::new(state));
+        world.insert_resource(NextState::<GameState>::new());
+        world.insert_resource(schedules.compile::<SingleThreadedExecutor>());
+        world
+    }
+
+    fn request(world: &mut World, state: GameState) {
+        world
+            .get_resource_mut::<NextState<GameState>>()
+            .unwrap()
+            .set(state);
+    }
+
+    #[test]
+    fn transition_runs_exit_transition_and_enter_in_order() {
+        let mut schedules = StateSchedules::<GameState>::new();
+        schedules.add_system(OnExit(GameState::Menu), |mut log: ResMut<Log>| {
+            log.0.push("exit menu")
+        });
+        schedules.add_system(
+            OnTransition {
+                from: GameState::Menu,
+                to: GameState::InGame,
+            },
+            |mut log: ResMut<Log>| log.0.push("menu -> game"),
+        );
+        schedules.add_system(OnEnter(GameState::InGame), |mut log: ResMut<Log>| {
+            log.0.push("enter game")
+        });
+        schedules.add_system(OnEnter(GameState::Menu), |mut log: ResMut<Log>| {
+            log.0.push("enter menu")
+        });
+
+        let mut world = world_in(GameState::Menu, schedules);
+        request(&mut world, GameState::InGame);
+        apply_state_transition::<GameState>(&mut world);
+
+        assert_eq!(
+            world.get_resource::<Log>().unwrap().0,
+            vec!["exit menu", "menu -> game", "enter game"]
+        );
+        assert_eq!(
+            *world.get_resource::<State<GameState>>().unwrap().get(),
+            GameState::InGame
+        );
+        assert!(world
+            .get_resource::<NextState<GameState>>()
+            .unwrap()
+            .pending()
+            .is_none());
+    }
+
+    #[test]
+    fn requesting_current_state_is_a_noop() {
+        let mut schedules = StateSchedules::<GameState>::new();
+        schedules.add_system(OnEnter(GameState::Menu), |mut log: ResMut<Log>| {
+            log.0.push("enter menu")
+        });
+
+        let mut world = world_in(GameState::Menu, schedules);
+        request(&mut world, GameState::Menu);
+        apply_state_transition::<GameState>(&mut world);
+
+        assert!(world.get_resource::<Log>().unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn in_state_gates_systems() {
+        fn record(mut log: ResMut<Log>, state: Res<State<GameState>>) {
+            log.0.push(match state.get() {
+                GameState::Menu => "menu",
+                GameState::InGame => "game",
+            });
+        }
+
+        let mut world = world_in(GameState::Menu, StateSchedules::new());
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(record.run_if(in_state(GameState::InGame)));
+        let mut schedule = schedule.compile::<SingleThreadedExecutor>();
+
+        schedule.run(&mut world);
+        request(&mut world, GameState::InGame);
+        apply_state_transition::<GameState>(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(world.get_resource::<Log>().unwrap().0, vec!["game"]);
+    }
+}
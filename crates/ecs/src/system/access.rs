@@ -46,6 +46,11 @@ impl SystemAccess {
         self.needs_apply
     }
 
+    /// Whether this access writes to anything in the world.
+    pub fn has_writes(&self) -> bool {
+        self.writes_all || !self.component_writes.is_empty() || !self.resource_writes.is_empty()
+    }
+
     pub fn are_disjoint(a: &Self, b: &Self) -> bool {
         if a.writes_all || b.writes_all {
             return false;
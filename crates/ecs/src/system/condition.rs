@@ -0,0 +1,205 @@
+use typle::typle;
+
+use crate::{
+    resource::Resource,
+    system::{access::SystemAccess, input::SystemInput},
+    world::{UnsafeWorldCell, World},
+};
+
+/// Type alias for a boxed, type-erased run condition.
+pub type BoxedCondition = Box<dyn Condition>;
+
+/// A predicate evaluated by the executor to decide whether a gated system runs this frame.
+///
+/// Like [`System`](crate::system::System), you normally don't implement this directly: any
+/// function whose parameters implement [`SystemInput`] and that returns `bool` implements
+/// [`IntoCondition`].
+///
+/// Conditions should only read from the world. Deferred work (e.g. commands queued through
+/// a [`CommandQueue`](crate::command::CommandQueue)) is never applied.
+pub trait Condition: Send + Sync + 'static {
+    /// Returns the fully-qualified name of the underlying function or type.
+    fn name(&self) -> &str;
+
+    /// Describes which components and resources this condition reads.
+    fn access(&self) -> SystemAccess;
+
+    /// Evaluates the condition against the world.
+    fn evaluate(&mut self, world: &mut World) -> bool {
+        let world_cell = world.as_unsafe_world_cell_mut();
+        unsafe { self.evaluate_unsafe(world_cell) }
+    }
+
+    /// Unsafe version of [`evaluate`](Condition::evaluate) used by the executors.
+    /// # Safety
+    ///
+    /// It is up to the caller to ensure that no system conflicting with this condition's
+    /// access is running simultaneously on this world.
+    unsafe fn evaluate_unsafe(&mut self, world: UnsafeWorldCell) -> bool;
+}
+
+/// Wraps a `bool`-returning function and its cached input state into a [`Condition`].
+pub(crate) struct FunctionCondition<F, Input: SystemInput> {
+    func: F,
+    condition_state: Input::State,
+}
+
+impl<F, Input> FunctionCondition<F, Input>
+where
+    Input: SystemInput + 'static,
+{
+    pub fn new(func: F) -> Self {
+        Self {
+            func,
+            condition_state: Input::init_state(),
+        }
+    }
+}
+
+#[allow(unused_variables, unused_mut)]
+#[typle(Tuple for 0..=12)]
+impl<F, T> Condition for FunctionCondition<F, T>
+where
+    F: Send + Sync + 'static,
+    T: Tuple,
+    T<_>: SystemInput + 'static,
+    for<'w, 's> F: FnMut(typle_args!(i in .. => T<{i}>)) -> bool
+        + FnMut(typle_args!(i in .. => T<{i}>::Data<'w, 's>)) -> bool,
+{
+    fn name(&self) -> &str {
+        std::any::type_name::<F>()
+    }
+
+    unsafe fn evaluate_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        (self.func)(
+            typle_args!(i in .. =>  <T<{i}>>::get_data(&mut self.condition_state[[i]], world) ),
+        )
+    }
+
+    fn access(&self) -> SystemAccess {
+        let mut access: SystemAccess = SystemAccess::default();
+        for typle_index!(i) in 0..T::LEN {
+            <T<{ i }>>::fill_access(&mut access);
+        }
+        access
+    }
+}
+
+/// Conversion trait that turns a compatible function or closure into a [`BoxedCondition`].
+///
+/// Implemented automatically for `bool`-returning functions whose parameters implement
+/// [`SystemInput`], and for any type that already implements [`Condition`].
+pub trait IntoCondition<Marker> {
+    /// Wraps `self` in a [`BoxedCondition`] ready to gate a system or a system set.
+    fn into_condition(self) -> BoxedCondition;
+}
+
+/// Marker used by the blanket [`IntoCondition`] impl for types that already implement
+/// [`Condition`].
+pub struct AlreadyCondition;
+
+impl<C: Condition> IntoCondition<AlreadyCondition> for C {
+    fn into_condition(self) -> BoxedCondition {
+        Box::new(self)
+    }
+}
+
+#[typle(Tuple for 0..=12)]
+impl<F, T> IntoCondition<T> for F
+where
+    F: Send + Sync + 'static,
+    T: Tuple,
+    T<_>: SystemInput + 'static,
+    for<'w, 's> F: FnMut(typle_args!(i in .. => T<{i}>)) -> bool
+        + FnMut(typle_args!(i in .. => T<{i}>::Data<'w, 's>)) -> bool,
+{
+    fn into_condition(self) -> BoxedCondition {
+        Box::new(FunctionCondition::new(self))
+    }
+}
+
+/// Condition that inverts another condition.  Created with [`not`].
+pub struct NotCondition {
+    inner: BoxedCondition,
+    // `not(<inner name>)`, so diagnostics tell the two apart.
+    name: String,
+}
+
+impl Condition for NotCondition {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn access(&self) -> SystemAccess {
+        self.inner.access()
+    }
+
+    unsafe fn evaluate_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        !self.inner.evaluate_unsafe(world)
+    }
+}
+
+/// Returns a condition that is `true` whenever `condition` is `false`.
+///
+/// ```
+/// use ecs::{Schedule, IntoSystemConfig, Res, Resource, system::condition::not};
+///
+/// #[derive(Resource)]
+/// struct Paused(bool);
+///
+/// fn is_paused(paused: Res<Paused>) -> bool { paused.0 }
+/// fn move_players() {}
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add_system(move_players.run_if(not(is_paused)));
+/// ```
+pub fn not<M>(condition: impl IntoCondition<M>) -> NotCondition {
+    let inner = condition.into_condition();
+    let name = format!("not({})", inner.name());
+    NotCondition { inner, name }
+}
+
+/// Condition that is `true` while a resource of type `T` is present in the world.
+pub struct ResourceExists<T: Resource> {
+    _marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: Resource> Condition for ResourceExists<T> {
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    fn access(&self) -> SystemAccess {
+        let mut access = SystemAccess::default();
+        access.read_resource::<T>();
+        access
+    }
+
+    unsafe fn evaluate_unsafe(&mut self, world: UnsafeWorldCell) -> bool {
+        world.world().get_resource::<T>().is_some()
+    }
+}
+
+/// Returns a condition that is `true` while a resource of type `T` is present in the world.
+pub fn resource_exists<T: Resource>() -> ResourceExists<T> {
+    ResourceExists {
+        _marker: std::marker::PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource)]
+    struct Enabled;
+
+    #[test]
+    fn not_names_itself_after_the_condition_it_inverts() {
+        let inner = resource_exists::<Enabled>();
+        let inner_name = inner.name().to_string();
+        let inverted = not(inner);
+
+        assert_eq!(inverted.name(), format!("not({inner_name})"));
+    }
+}
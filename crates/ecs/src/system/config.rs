@@ -1,4 +1,8 @@
-use crate::system::{BoxedSystem, IntoSystem};
+use crate::system::{
+    condition::{BoxedCondition, IntoCondition},
+    set::{SystemSet, SystemSetId},
+    BoxedSystem, IntoSystem,
+};
 
 /// A system bundled with explicit ordering constraints.
 ///
@@ -28,6 +32,14 @@ pub struct SystemConfig {
     /// Systems that must run *after* this one. Each is registered into the schedule
     /// when this config is added, and a `this → dep` edge is inserted in the graph.
     pub(crate) before: Vec<SystemConfig>,
+    /// Sets this system belongs to.
+    pub(crate) sets: Vec<SystemSetId>,
+    /// Sets whose systems must all run before this one.
+    pub(crate) after_sets: Vec<SystemSetId>,
+    /// Sets whose systems must all run after this one.
+    pub(crate) before_sets: Vec<SystemSetId>,
+    /// Conditions that must all hold for this system to run.
+    pub(crate) conditions: Vec<BoxedCondition>,
 }
 
 impl SystemConfig {
@@ -48,6 +60,33 @@ impl SystemConfig {
         self.before.push(dep.into_config());
         self
     }
+
+    /// Adds this system to `set`, so it inherits the set's ordering and run conditions.
+    pub fn in_set(mut self, set: impl SystemSet) -> Self {
+        self.sets.push(set.id());
+        self
+    }
+
+    /// Declares that every system in `set` must run before this system.
+    pub fn after_set(mut self, set: impl SystemSet) -> Self {
+        self.after_sets.push(set.id());
+        self
+    }
+
+    /// Declares that every system in `set` must run after this system.
+    pub fn before_set(mut self, set: impl SystemSet) -> Self {
+        self.before_sets.push(set.id());
+        self
+    }
+
+    /// Gates this system behind `condition`: the system is skipped for the current run of
+    /// the schedule whenever the condition returns `false`.
+    ///
+    /// Multiple conditions can be chained; all of them must hold.
+    pub fn run_if<M>(mut self, condition: impl IntoCondition<M>) -> Self {
+        self.conditions.push(condition.into_condition());
+        self
+    }
 }
 
 /// Converts a system function or [`SystemConfig`] into a [`SystemConfig`].
@@ -77,6 +116,26 @@ pub trait IntoSystemConfig<Marker>: Sized {
     fn before<M>(self, dep: impl IntoSystemConfig<M>) -> SystemConfig {
         self.into_config().before(dep)
     }
+
+    /// Adds this system to `set`.
+    fn in_set(self, set: impl SystemSet) -> SystemConfig {
+        self.into_config().in_set(set)
+    }
+
+    /// Declares that every system in `set` must run before this system.
+    fn after_set(self, set: impl SystemSet) -> SystemConfig {
+        self.into_config().after_set(set)
+    }
+
+    /// Declares that every system in `set` must run after this system.
+    fn before_set(self, set: impl SystemSet) -> SystemConfig {
+        self.into_config().before_set(set)
+    }
+
+    /// Gates this system behind `condition`.
+    fn run_if<M>(self, condition: impl IntoCondition<M>) -> SystemConfig {
+        self.into_config().run_if(condition)
+    }
 }
 
 /// Blanket impl: any function that implements [`IntoSystem`] can be turned into a [`SystemConfig`].
@@ -86,6 +145,10 @@ impl<M, F: IntoSystem<M> + 'static> IntoSystemConfig<M> for F {
             system: self.into_system(),
             after: Vec::new(),
             before: Vec::new(),
+            sets: Vec::new(),
+            after_sets: Vec::new(),
+            before_sets: Vec::new(),
+            conditions: Vec::new(),
         }
     }
 }
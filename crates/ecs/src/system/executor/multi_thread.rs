@@ -19,6 +19,7 @@ pub struct MultiThreadedExecutor {
     unapplied_systems: FixedBitSet,
     dependency_count: Vec<usize>,
     dependants: Vec<Vec<usize>>,
+    set_results: Vec<Option<bool>>,
 }
 
 impl SystemExecutor for MultiThreadedExecutor {
@@ -39,6 +40,7 @@ impl SystemExecutor for MultiThreadedExecutor {
             unapplied_systems,
             dependency_count: compiled_data.dependency_count.clone(),
             dependants: compiled_data.dependants.clone(),
+            set_results: vec![None; compiled_data.conditions.set_count()],
         }
     }
 
@@ -52,12 +54,14 @@ impl SystemExecutor for MultiThreadedExecutor {
         let systems =
             SyncUnsafeCell::from_mut(compiled_data.systems.as_mut_slice()).as_slice_of_cells();
         let world_cell = world.as_unsafe_world_cell_mut();
+        let conditions = &mut compiled_data.conditions;
         let queue = ConcurrentQueue::bounded(sys_count);
 
         self.pending_systems.set_range(.., true);
         self.ready_systems.clear();
         self.running_systems.clear();
         self.unapplied_systems.clear();
+        self.set_results.fill(None);
         self.dependency_count
             .clone_from(&compiled_data.dependency_count);
 
@@ -66,6 +70,20 @@ impl SystemExecutor for MultiThreadedExecutor {
                 self.update_ready_systems();
 
                 for ready_system_idx in self.ready_systems.ones() {
+                    // Conditions are evaluated here, on the scheduling thread: every system
+                    // conflicting with them is ordered before this one and has finished.
+                    // A skipped system never runs, so it completes immediately and holds
+                    // no access while its dependants are scheduled.
+                    let should_run = unsafe {
+                        conditions.should_run(ready_system_idx, &mut self.set_results, world_cell)
+                    };
+                    if !should_run {
+                        for &dependant in &self.dependants[ready_system_idx] {
+                            self.dependency_count[dependant] -= 1;
+                        }
+                        continue;
+                    }
+
                     self.running_systems.set(ready_system_idx, true);
                     let sys = unsafe { &mut *systems[ready_system_idx].get() };
                     let queue = &queue;
@@ -131,9 +149,9 @@ mod tests {
         component::Component,
         entity::Entity,
         query::Query,
-        resource::ResMut,
+        resource::{Res, ResMut},
         system::{executor::multi_thread::MultiThreadedExecutor, schedule::Schedule},
-        Changed, Resource, World,
+        Changed, IntoSystemConfig, Resource, World,
     };
 
     #[derive(Component)]
@@ -170,4 +188,72 @@ mod tests {
 
         assert_eq!(world.get_resource::<Counter>().unwrap().0, 1);
     }
+
+    #[derive(Resource)]
+    struct Enabled(bool);
+
+    fn is_enabled(enabled: Res<Enabled>) -> bool {
+        enabled.0
+    }
+
+    fn increment(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    #[test]
+    fn skipped_system_does_not_block_dependants() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        world.insert_resource(Enabled(false));
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(increment.run_if(is_enabled));
+        schedule.add_system(increment);
+        schedule.add_system(increment.run_if(is_enabled));
+        schedule.add_system(increment);
+
+        schedule.compile::<MultiThreadedExecutor>().run(&mut world);
+
+        assert_eq!(world.get_resource::<Counter>().unwrap().0, 2);
+    }
+
+    #[derive(Component)]
+    struct Value(usize);
+
+    fn double_values(query: Query<&mut Value>) {
+        query
+            .par_iter()
+            .batch_size(8)
+            .for_each(|mut value| value.0 *= 2);
+    }
+
+    fn sum_values(query: Query<&Value>, mut counter: ResMut<Counter>) {
+        let sum = std::sync::atomic::AtomicUsize::new(0);
+        query.par_iter().batch_size(8).for_each(|value| {
+            sum.fetch_add(value.0, std::sync::atomic::Ordering::Relaxed);
+        });
+        counter.0 = sum.into_inner();
+    }
+
+    #[test]
+    fn par_iter_inside_parallel_systems() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        for i in 0..1000 {
+            world.spawn(Value(i));
+            world.spawn(TagComponent);
+        }
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(sum_values.after(double_values));
+        // Unrelated systems running alongside keep the workers busy with nested scopes.
+        for _ in 0..8 {
+            schedule.add_system(|query: Query<&TagComponent>| {
+                query.par_iter().batch_size(1).for_each(|_| {});
+            });
+        }
+        schedule.compile::<MultiThreadedExecutor>().run(&mut world);
+
+        assert_eq!(world.get_resource::<Counter>().unwrap().0, 999 * 1000);
+    }
 }
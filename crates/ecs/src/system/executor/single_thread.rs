@@ -3,18 +3,35 @@ use crate::{
     World,
 };
 
-pub struct SingleThreadedExecutor {}
+pub struct SingleThreadedExecutor {
+    set_results: Vec<Option<bool>>,
+}
 
 impl SystemExecutor for SingleThreadedExecutor {
-    fn init(_compiled_data: &CompiledScheduleData) -> Self
+    fn init(compiled_data: &CompiledScheduleData) -> Self
     where
         Self: Sized,
     {
-        Self {}
+        Self {
+            set_results: vec![None; compiled_data.conditions.set_count()],
+        }
     }
 
     fn run(&mut self, compiled_data: &mut CompiledScheduleData, world: &mut World) {
+        self.set_results.fill(None);
+
         for &idx in &compiled_data.sorted_systems {
+            let should_run = unsafe {
+                compiled_data.conditions.should_run(
+                    idx,
+                    &mut self.set_results,
+                    world.as_unsafe_world_cell_mut(),
+                )
+            };
+            if !should_run {
+                continue;
+            }
+
             profiling::scope!("system", compiled_data.systems[idx].name());
             compiled_data.systems[idx].run_and_apply(world);
         }
@@ -25,8 +42,13 @@ impl SystemExecutor for SingleThreadedExecutor {
 mod tests {
     use super::*;
     use crate::{
-        resource::{Res, Resource},
-        system::{config::IntoSystemConfig, executor::single_thread::SingleThreadedExecutor},
+        resource::{Res, ResMut, Resource},
+        system::{
+            condition::not,
+            config::IntoSystemConfig,
+            executor::single_thread::SingleThreadedExecutor,
+            set::{IntoSystemSetConfig, SystemSet},
+        },
         Schedule,
     };
     use std::sync::{Arc, Mutex};
@@ -122,4 +144,120 @@ mod tests {
 
         assert_eq!(*shared.lock().unwrap(), vec![1, 2, 3]);
     }
+
+    // ── Run condition and system set tests ────────────────────────────────────
+
+    #[derive(Resource)]
+    struct Enabled(bool);
+
+    #[derive(Resource, Default)]
+    struct Evaluations(usize);
+
+    #[derive(SystemSet, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    enum TestSet {
+        First,
+        Second,
+    }
+
+    fn is_enabled(enabled: Res<Enabled>) -> bool {
+        enabled.0
+    }
+
+    fn push_1(log: Res<ExecLog>) {
+        log.0.lock().unwrap().push(1);
+    }
+
+    fn push_2(log: Res<ExecLog>) {
+        log.0.lock().unwrap().push(2);
+    }
+
+    fn push_3(log: Res<ExecLog>) {
+        log.0.lock().unwrap().push(3);
+    }
+
+    fn world_with_log(enabled: bool) -> (World, Arc<Mutex<Vec<u8>>>) {
+        let shared = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let mut world = World::new();
+        world.insert_resource(ExecLog(Arc::clone(&shared)));
+        world.insert_resource(Enabled(enabled));
+        (world, shared)
+    }
+
+    #[test]
+    fn run_if_skips_system_when_false() {
+        let (mut world, shared) = world_with_log(false);
+
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(push_1.run_if(is_enabled))
+            .add_system(push_2.run_if(not(is_enabled)));
+        schedule.compile::<SingleThreadedExecutor>().run(&mut world);
+
+        assert_eq!(*shared.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn run_if_is_reevaluated_each_run() {
+        let (mut world, shared) = world_with_log(false);
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(push_1.run_if(is_enabled));
+        let mut schedule = schedule.compile::<SingleThreadedExecutor>();
+
+        schedule.run(&mut world);
+        world.get_resource_mut::<Enabled>().unwrap().0 = true;
+        schedule.run(&mut world);
+
+        assert_eq!(*shared.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn set_condition_gates_every_member_and_is_evaluated_once() {
+        fn counted_is_enabled(enabled: Res<Enabled>, mut evaluations: ResMut<Evaluations>) -> bool {
+            evaluations.0 += 1;
+            enabled.0
+        }
+
+        let (mut world, shared) = world_with_log(false);
+        world.init_resource::<Evaluations>();
+
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(TestSet::First.run_if(counted_is_enabled))
+            .add_system(push_1.in_set(TestSet::First))
+            .add_system(push_2.in_set(TestSet::First))
+            .add_system(push_3);
+        schedule.compile::<SingleThreadedExecutor>().run(&mut world);
+
+        assert_eq!(*shared.lock().unwrap(), vec![3]);
+        assert_eq!(world.get_resource::<Evaluations>().unwrap().0, 1);
+    }
+
+    #[test]
+    fn set_ordering_overrides_registration_order() {
+        let (mut world, shared) = world_with_log(true);
+
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(push_2.in_set(TestSet::Second))
+            .add_system(push_1.in_set(TestSet::First))
+            .configure_set(TestSet::Second.after(TestSet::First));
+        schedule.compile::<SingleThreadedExecutor>().run(&mut world);
+
+        assert_eq!(*shared.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn system_can_be_ordered_against_a_set() {
+        let (mut world, shared) = world_with_log(true);
+
+        let mut schedule = Schedule::new();
+        schedule
+            .add_system(push_3.after_set(TestSet::First))
+            .add_system(push_1.before_set(TestSet::First))
+            .add_system(push_2.in_set(TestSet::First));
+        schedule.compile::<SingleThreadedExecutor>().run(&mut world);
+
+        assert_eq!(*shared.lock().unwrap(), vec![1, 2, 3]);
+    }
 }
@@ -0,0 +1,110 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    resource::Resource,
+    system::{
+        access::SystemAccess,
+        input::{SystemInput, SystemInputData},
+    },
+    world::{UnsafeWorldCell, World},
+};
+
+/// The main world, lent to a sub-app's world while its `Extract` schedule runs.
+///
+/// Only present during extraction; read it through [`Extract`] rather than directly.
+#[derive(Resource)]
+pub struct MainWorld(World);
+
+impl MainWorld {
+    pub fn new(world: World) -> Self {
+        Self(world)
+    }
+
+    pub fn into_inner(self) -> World {
+        self.0
+    }
+}
+
+impl Deref for MainWorld {
+    type Target = World;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// System parameter that fetches `P` from the [`MainWorld`] instead of the world the system
+/// runs in.
+///
+/// Used by systems in a sub-app's `Extract` schedule to copy data out of the main world.
+/// The main world is only lent for reading: a `P` that writes to it, or that defers changes
+/// to be applied later (like a [`CommandQueue`](crate::CommandQueue)), panics when the system
+/// is added to a schedule.
+///
+/// # Example
+/// ```
+/// use ecs::{Component, CommandQueue, Entity, Query, system::extract::Extract};
+///
+/// #[derive(Component, Clone)]
+/// struct Health(f32);
+///
+/// fn extract_health(healths: Extract<Query<(Entity, &Health)>>, mut commands: CommandQueue) {
+///     for (_entity, health) in healths.iter() {
+///         commands.spawn(health.clone());
+///     }
+/// }
+/// ```
+pub struct Extract<'w, 's, P: SystemInput + 'static>(SystemInputData<'w, 's, P>);
+
+impl<'w, 's, P: SystemInput + 'static> Deref for Extract<'w, 's, P> {
+    type Target = SystemInputData<'w, 's, P>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<P: SystemInput + 'static> DerefMut for Extract<'_, '_, P> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<P: SystemInput + 'static> SystemInput for Extract<'_, '_, P> {
+    type State = P::State;
+    type Data<'world, 'state> = Extract<'world, 'state, P>;
+
+    fn init_state() -> Self::State {
+        P::init_state()
+    }
+
+    fn get_data<'world, 'state>(
+        state: &'state mut Self::State,
+        world: UnsafeWorldCell<'world>,
+    ) -> Self::Data<'world, 'state> {
+        let main_world = world
+            .world()
+            .get_resource::<MainWorld>()
+            .expect("Extract can only be used in a sub-app's Extract schedule");
+        Extract(P::get_data(state, main_world.as_unsafe_world_cell()))
+    }
+
+    fn fill_access(access: &mut SystemAccess) {
+        let mut main_world_access = SystemAccess::default();
+        P::fill_access(&mut main_world_access);
+        assert!(
+            !main_world_access.has_writes(),
+            "Extract<{}> writes to the main world, which is only lent for reading",
+            std::any::type_name::<P>()
+        );
+        // `apply` would get the sub-app's world, not the main world `P` was fetched from.
+        assert!(
+            !main_world_access.needs_apply(),
+            "Extract<{}> defers changes to the main world, which are never applied",
+            std::any::type_name::<P>()
+        );
+
+        // `P` reads from the main world, which nothing else borrows during extraction.
+        access.read_resource::<MainWorld>();
+    }
+}
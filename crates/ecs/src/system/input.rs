@@ -27,7 +27,7 @@ pub trait SystemInput {
 impl<T> SystemInput for T
 where
     T: Tuple,
-    T<_>: SystemInput + 'static,
+    T<_>: SystemInput,
 {
     type State = typle_for!(i in .. => T<{i}>::State);
     type Data<'world, 'state> = typle_for!(i in .. => T<{i}>::Data<'world, 'state>);
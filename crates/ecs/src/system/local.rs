@@ -0,0 +1,66 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    system::{access::SystemAccess, input::SystemInput},
+    world::UnsafeWorldCell,
+};
+
+/// System parameter holding a value private to one system instance.
+///
+/// The value is created with [`Default`] when the system is built and persists between runs.
+/// Two systems (or two instances of the same function added twice) each get their own value,
+/// and since a `Local` touches no world data it never creates an access conflict.
+///
+/// # Example
+/// ```
+/// use ecs::system::local::Local;
+///
+/// fn log_every_hundred_frames(mut frames: Local<u32>) {
+///     *frames += 1;
+///     if *frames % 100 == 0 {
+///         println!("{} frames", *frames);
+///     }
+/// }
+/// ```
+pub struct Local<'state, T: Default + Send + Sync + 'static>(&'state mut T);
+
+impl<T> Deref for Local<'_, T>
+where
+    T: Default + Send + Sync + 'static,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<T> DerefMut for Local<'_, T>
+where
+    T: Default + Send + Sync + 'static,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+impl<T> SystemInput for Local<'_, T>
+where
+    T: Default + Send + Sync + 'static,
+{
+    type State = T;
+    type Data<'world, 'state> = Local<'state, T>;
+
+    fn init_state() -> Self::State {
+        T::default()
+    }
+
+    fn get_data<'world, 'state>(
+        state: &'state mut Self::State,
+        _world: UnsafeWorldCell<'world>,
+    ) -> Self::Data<'world, 'state> {
+        Local(state)
+    }
+
+    fn fill_access(_access: &mut SystemAccess) {}
+}
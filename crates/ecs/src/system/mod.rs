@@ -1,9 +1,14 @@
 pub mod access;
+pub mod condition;
 pub mod config;
 pub mod executor;
+pub mod extract;
 mod graph;
 pub mod input;
+pub mod local;
+pub mod param_set;
 pub mod schedule;
+pub mod set;
 mod sync_point;
 
 use std::any::TypeId;
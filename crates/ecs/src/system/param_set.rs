@@ -0,0 +1,140 @@
+use typle::typle;
+
+use crate::{
+    system::{access::SystemAccess, input::SystemInput},
+    world::{UnsafeWorldCell, World},
+};
+
+/// System parameter giving access to several parameters whose accesses may conflict, one at
+/// a time.
+///
+/// Each parameter of the tuple `T` is fetched on demand through `p0()`, `p1()`, ... and
+/// borrows the `ParamSet` mutably, so two conflicting parameters (e.g. a `Query<&mut A>` and a
+/// `Query<&A>`) are never alive simultaneously.  The scheduler sees the union of all accesses.
+///
+/// # Example
+/// ```
+/// use ecs::{Component, Entity, Query, system::param_set::ParamSet};
+///
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// fn heal_lowest(mut set: ParamSet<(Query<(Entity, &Health)>, Query<&mut Health>)>) {
+///     let lowest = set
+///         .p0()
+///         .iter()
+///         .min_by(|(_, a), (_, b)| a.0.total_cmp(&b.0))
+///         .map(|(entity, _)| entity);
+///
+///     if let Some(entity) = lowest {
+///         if let Some(mut health) = set.p1().get_entity(entity) {
+///             health.0 += 10.0;
+///         }
+///     }
+/// }
+/// ```
+pub struct ParamSet<'world, 'state, T: SystemInput> {
+    world: UnsafeWorldCell<'world>,
+    state: &'state mut T::State,
+}
+
+impl<T> SystemInput for ParamSet<'_, '_, T>
+where
+    T: SystemInput,
+{
+    type State = T::State;
+    type Data<'world, 'state> = ParamSet<'world, 'state, T>;
+
+    fn init_state() -> Self::State {
+        T::init_state()
+    }
+
+    fn get_data<'world, 'state>(
+        state: &'state mut Self::State,
+        world: UnsafeWorldCell<'world>,
+    ) -> Self::Data<'world, 'state> {
+        ParamSet { world, state }
+    }
+
+    fn apply(state: &mut Self::State, world: &mut World) {
+        T::apply(state, world);
+    }
+
+    fn fill_access(access: &mut SystemAccess) {
+        T::fill_access(access);
+    }
+}
+
+// One impl per accessor: `typle` can't name methods after an index, so each
+// `pN` is implemented for every set long enough to have an `N`th parameter.
+#[typle(Tuple for 2..=6)]
+impl<'w, 's, T> ParamSet<'w, 's, T>
+where
+    T: Tuple,
+    T<_>: SystemInput,
+{
+    /// Fetches the first parameter of the set.
+    pub fn p0(&mut self) -> <T<0>>::Data<'_, '_> {
+        <T<0>>::get_data(&mut self.state[[0]], self.world)
+    }
+}
+
+#[typle(Tuple for 2..=6)]
+impl<'w, 's, T> ParamSet<'w, 's, T>
+where
+    T: Tuple,
+    T<_>: SystemInput,
+{
+    /// Fetches the second parameter of the set.
+    pub fn p1(&mut self) -> <T<1>>::Data<'_, '_> {
+        <T<1>>::get_data(&mut self.state[[1]], self.world)
+    }
+}
+
+#[typle(Tuple for 3..=6)]
+impl<'w, 's, T> ParamSet<'w, 's, T>
+where
+    T: Tuple,
+    T<_>: SystemInput,
+{
+    /// Fetches the third parameter of the set.
+    pub fn p2(&mut self) -> <T<2>>::Data<'_, '_> {
+        <T<2>>::get_data(&mut self.state[[2]], self.world)
+    }
+}
+
+#[typle(Tuple for 4..=6)]
+impl<'w, 's, T> ParamSet<'w, 's, T>
+where
+    T: Tuple,
+    T<_>: SystemInput,
+{
+    /// Fetches the fourth parameter of the set.
+    pub fn p3(&mut self) -> <T<3>>::Data<'_, '_> {
+        <T<3>>::get_data(&mut self.state[[3]], self.world)
+    }
+}
+
+#[typle(Tuple for 5..=6)]
+impl<'w, 's, T> ParamSet<'w, 's, T>
+where
+    T: Tuple,
+    T<_>: SystemInput,
+{
+    /// Fetches the fifth parameter of the set.
+    pub fn p4(&mut self) -> <T<4>>::Data<'_, '_> {
+        <T<4>>::get_data(&mut self.state[[4]], self.world)
+    }
+}
+
+#[typle(Tuple for 6..=6)]
+impl<'w, 's, T> ParamSet<'w, 's, T>
+where
+    T: Tuple,
+    T<_>: SystemInput,
+{
+    /// Fetches the sixth parameter of the set.
+    pub fn p5(&mut self) -> <T<5>>::Data<'_, '_> {
+        <T<5>>::get_data(&mut self.state[[5]], self.world)
+    }
+}
@@ -1,15 +1,17 @@
-use std::{any::TypeId, fmt};
+use std::{any::TypeId, collections::HashMap, fmt};
 
 use crate::{
     system::{
         access::SystemAccess,
+        condition::BoxedCondition,
         config::{IntoSystemConfig, SystemConfig},
         executor::SystemExecutor,
         graph::{SystemDependencyGraph, SystemNode},
+        set::{IntoSystemSetConfig, SystemSetId},
         sync_point::SyncPoint,
         BoxedSystem,
     },
-    world::World,
+    world::{UnsafeWorldCell, World},
     Resource, System,
 };
 use derive_more::{Deref, From};
@@ -49,15 +51,38 @@ pub struct SystemIndex(usize);
 pub struct Schedule {
     system_ids: Vec<SystemNodeIndex>,
     systems: Vec<BoxedSystem>,
+    system_meta: Vec<SystemMeta>,
+    sets: Vec<SystemSetInfo>,
+    set_indices: HashMap<SystemSetId, usize>,
     graph: SystemDependencyGraph,
 }
 
+/// Per-system configuration that is only resolved when the schedule is compiled.
+#[derive(Default)]
+struct SystemMeta {
+    conditions: Vec<BoxedCondition>,
+    sets: Vec<SystemSetId>,
+    after_sets: Vec<SystemSetId>,
+    before_sets: Vec<SystemSetId>,
+}
+
+/// Accumulated configuration of a system set within a single schedule.
+#[derive(Default)]
+struct SystemSetInfo {
+    after: Vec<SystemSetId>,
+    before: Vec<SystemSetId>,
+    conditions: Vec<BoxedCondition>,
+}
+
 impl Schedule {
     /// Creates an empty schedule.
     pub fn new() -> Schedule {
         Self {
             system_ids: Vec::new(),
             systems: Vec::new(),
+            system_meta: Vec::new(),
+            sets: Vec::new(),
+            set_indices: HashMap::new(),
             graph: SystemDependencyGraph::new(),
         }
     }
@@ -81,6 +106,45 @@ impl Schedule {
         self
     }
 
+    /// Configures ordering constraints and run conditions for a [`SystemSet`](crate::system::set::SystemSet).
+    ///
+    /// Can be called before or after the set's systems are added, and more than once for the
+    /// same set: constraints and conditions accumulate.
+    ///
+    /// ```
+    /// # use ecs::{Schedule, IntoSystemConfig, Res, Resource};
+    /// # use ecs::system::set::{IntoSystemSetConfig, SystemSet};
+    /// #[derive(SystemSet, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    /// struct Gameplay;
+    ///
+    /// #[derive(Resource)]
+    /// struct Paused(bool);
+    ///
+    /// fn is_running(paused: Res<Paused>) -> bool { !paused.0 }
+    /// # fn move_players() {}
+    ///
+    /// let mut schedule = Schedule::new();
+    /// schedule
+    ///     .configure_set(Gameplay.run_if(is_running))
+    ///     .add_system(move_players.in_set(Gameplay));
+    /// ```
+    pub fn configure_set(&mut self, set: impl IntoSystemSetConfig) -> &mut Self {
+        let config = set.into_set_config();
+        let info = self.set_info_mut(config.set);
+        info.after.extend(config.after);
+        info.before.extend(config.before);
+        info.conditions.extend(config.conditions);
+        self
+    }
+
+    fn set_info_mut(&mut self, set: SystemSetId) -> &mut SystemSetInfo {
+        let index = *self.set_indices.entry(set).or_insert_with(|| {
+            self.sets.push(SystemSetInfo::default());
+            self.sets.len() - 1
+        });
+        &mut self.sets[index]
+    }
+
     /// Registers a [`SystemConfig`] into the graph, recursively registering owned dep
     /// systems first, then wiring explicit ordering edges.  Returns the [`NodeIndex`] of
     /// the newly registered system (used internally for edge wiring in recursive calls).
@@ -98,7 +162,12 @@ impl Schedule {
             .collect();
 
         let name = config.system.name();
-        let access = config.system.access();
+        let mut access = config.system.access();
+        // A condition reads the world right before its system runs, so it must be
+        // ordered against conflicting systems just like the system itself.
+        for condition in &config.conditions {
+            access.combine(condition.access());
+        }
 
         let node_idx: SystemNodeIndex = self
             .graph
@@ -128,8 +197,18 @@ impl Schedule {
 
         let needs_sync = access.needs_apply() && !is_sync_point(&config.system);
 
+        for set in &config.sets {
+            self.set_info_mut(*set);
+        }
+
         self.system_ids.push(node_idx);
         self.systems.push(config.system);
+        self.system_meta.push(SystemMeta {
+            conditions: config.conditions,
+            sets: config.sets,
+            after_sets: config.after_sets,
+            before_sets: config.before_sets,
+        });
 
         if needs_sync {
             self.add_sync_point();
@@ -138,7 +217,77 @@ impl Schedule {
         node_idx
     }
 
-    pub fn compile<T: SystemExecutor + 'static>(self) -> CompiledSchedule {
+    /// Wires the edges implied by system sets: set-to-set and system-to-set ordering, and
+    /// access conflicts introduced by set run conditions.
+    fn add_set_edges(&mut self) {
+        let mut set_members: Vec<Vec<usize>> = vec![Vec::new(); self.sets.len()];
+        for (system, meta) in self.system_meta.iter().enumerate() {
+            for set in &meta.sets {
+                set_members[self.set_indices[set]].push(system);
+            }
+        }
+
+        let members_of = |set: &SystemSetId| -> &[usize] {
+            self.set_indices
+                .get(set)
+                .map(|index| set_members[*index].as_slice())
+                .unwrap_or(&[])
+        };
+
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for (set, info) in self
+            .set_indices
+            .iter()
+            .map(|(id, index)| (id, &self.sets[*index]))
+        {
+            for after in &info.after {
+                for &from in members_of(after) {
+                    edges.extend(members_of(set).iter().map(|&to| (from, to)));
+                }
+            }
+            for before in &info.before {
+                for &to in members_of(before) {
+                    edges.extend(members_of(set).iter().map(|&from| (from, to)));
+                }
+            }
+        }
+        for (system, meta) in self.system_meta.iter().enumerate() {
+            for after in &meta.after_sets {
+                edges.extend(members_of(after).iter().map(|&from| (from, system)));
+            }
+            for before in &meta.before_sets {
+                edges.extend(members_of(before).iter().map(|&to| (system, to)));
+            }
+        }
+
+        // Set conditions run right before the set's systems, so they are ordered against
+        // conflicting systems following registration order, like implicit system edges.
+        for (set_index, info) in self.sets.iter().enumerate() {
+            let mut access = SystemAccess::default();
+            for condition in &info.conditions {
+                access.combine(condition.access());
+            }
+            for &member in &set_members[set_index] {
+                for (other, other_id) in self.system_ids.iter().enumerate() {
+                    let other_access = self.graph.node_weight(**other_id).unwrap().access();
+                    if other != member && !SystemAccess::are_disjoint(&access, other_access) {
+                        edges.push((member.min(other), member.max(other)));
+                    }
+                }
+            }
+        }
+
+        for (from, to) in edges {
+            if from != to {
+                self.graph
+                    .update_edge(*self.system_ids[from], *self.system_ids[to], ());
+            }
+        }
+    }
+
+    pub fn compile<T: SystemExecutor + 'static>(mut self) -> CompiledSchedule {
+        self.add_set_edges();
+
         let dependency_count: Vec<usize> = self
             .system_ids
             .iter()
@@ -167,17 +316,39 @@ impl Schedule {
             .collect();
 
         let sorted_systems = toposort(&self.graph, None)
-            .expect("Cycle detected in schedule — check your .after()/.before() constraints")
+            .expect("Cycle detected in schedule — check your .after()/.before() and set ordering constraints")
             .into_iter()
             .map(|node_index| *self.graph.node_weight(node_index).unwrap().index())
             .collect::<Vec<_>>();
 
+        let set_conditions: Vec<Vec<BoxedCondition>> =
+            self.sets.into_iter().map(|info| info.conditions).collect();
+
+        let mut system_conditions = Vec::with_capacity(self.system_meta.len());
+        let mut system_sets = Vec::with_capacity(self.system_meta.len());
+        for meta in self.system_meta {
+            system_conditions.push(meta.conditions);
+            // Only sets that actually gate something need to be evaluated at run time.
+            system_sets.push(
+                meta.sets
+                    .iter()
+                    .map(|set| self.set_indices[set])
+                    .filter(|index| !set_conditions[*index].is_empty())
+                    .collect(),
+            );
+        }
+
         let compiled_data = CompiledScheduleData {
             systems: self.systems,
             sorted_systems,
             dependency_count,
             dependants,
             system_access,
+            conditions: ScheduleConditions {
+                system_conditions,
+                system_sets,
+                set_conditions,
+            },
         };
 
         CompiledSchedule {
@@ -231,6 +402,54 @@ pub struct CompiledScheduleData {
     pub dependency_count: Vec<usize>,
     pub dependants: Vec<Vec<usize>>,
     pub system_access: Vec<SystemAccess>,
+    pub conditions: ScheduleConditions,
+}
+
+/// Run conditions of a compiled schedule, indexed by system and by system set.
+pub struct ScheduleConditions {
+    system_conditions: Vec<Vec<BoxedCondition>>,
+    /// For each system, the sets it belongs to that carry at least one condition.
+    system_sets: Vec<Vec<usize>>,
+    set_conditions: Vec<Vec<BoxedCondition>>,
+}
+
+impl ScheduleConditions {
+    /// Number of system sets referenced by the schedule, i.e. the length of the
+    /// `set_results` cache expected by [`should_run`](Self::should_run).
+    pub fn set_count(&self) -> usize {
+        self.set_conditions.len()
+    }
+
+    /// Evaluates every condition gating `system`, including those inherited from its sets.
+    ///
+    /// Set conditions are evaluated at most once per schedule run: their result is cached in
+    /// `set_results`, which executors reset to `None` at the start of each run.
+    ///
+    /// # Safety
+    ///
+    /// Same contract as [`Condition::evaluate_unsafe`](crate::system::condition::Condition::evaluate_unsafe).
+    pub unsafe fn should_run(
+        &mut self,
+        system: usize,
+        set_results: &mut [Option<bool>],
+        world: UnsafeWorldCell,
+    ) -> bool {
+        for &set in &self.system_sets[system] {
+            let conditions = &mut self.set_conditions[set];
+            let result = *set_results[set].get_or_insert_with(|| {
+                conditions
+                    .iter_mut()
+                    .all(|condition| condition.evaluate_unsafe(world))
+            });
+            if !result {
+                return false;
+            }
+        }
+
+        self.system_conditions[system]
+            .iter_mut()
+            .all(|condition| condition.evaluate_unsafe(world))
+    }
 }
 
 /// Identifies which phase of the per-frame update loop a system belongs to.
@@ -246,6 +465,9 @@ pub struct CompiledScheduleData {
 /// 6. **Render** — submits draw calls to the GPU.
 /// 7. **LateRender** — post-render work (e.g. UI overlay).
 ///
+/// Sub-apps additionally run **Extract** before their own `Update`, reading the main world
+/// through [`Extract`](crate::system::extract::Extract).  The main app never runs it.
+///
 /// TODO: This will live here until we abstract update groups away from schedules
 #[derive(Hash, PartialEq, Eq)]
 pub enum UpdateGroup {
@@ -263,6 +485,8 @@ pub enum UpdateGroup {
     Render,
     /// Post-render overlay (e.g. UI).
     LateRender,
+    /// Copies data out of the main world; only run by sub-apps.
+    Extract,
 }
 
 #[derive(Resource, Default, Debug)]
@@ -274,6 +498,7 @@ pub struct Schedules {
     late_fixed_update_schedule: Schedule,
     render_schedule: Schedule,
     late_render_schedule: Schedule,
+    extract_schedule: Schedule,
 }
 
 impl Schedules {
@@ -291,6 +516,21 @@ impl Schedules {
             UpdateGroup::LateFixedUpdate => self.late_fixed_update_schedule.add_system(system),
             UpdateGroup::Render => self.render_schedule.add_system(system),
             UpdateGroup::LateRender => self.late_render_schedule.add_system(system),
+            UpdateGroup::Extract => self.extract_schedule.add_system(system),
+        };
+    }
+
+    /// Configures a [`SystemSet`](crate::system::set::SystemSet) in the given [`UpdateGroup`].
+    pub fn configure_set(&mut self, update_group: UpdateGroup, set: impl IntoSystemSetConfig) {
+        match update_group {
+            UpdateGroup::Startup => self.startup_schedule.configure_set(set),
+            UpdateGroup::Update => self.update_schedule.configure_set(set),
+            UpdateGroup::FixedUpdate => self.fixed_update_schedule.configure_set(set),
+            UpdateGroup::LateUpdate => self.late_update_schedule.configure_set(set),
+            UpdateGroup::LateFixedUpdate => self.late_fixed_update_schedule.configure_set(set),
+            UpdateGroup::Render => self.render_schedule.configure_set(set),
+            UpdateGroup::LateRender => self.late_render_schedule.configure_set(set),
+            UpdateGroup::Extract => self.extract_schedule.configure_set(set),
         };
     }
 
@@ -303,6 +543,7 @@ impl Schedules {
             late_fixed_update_schedule: self.late_fixed_update_schedule.compile::<T>(),
             render_schedule: self.render_schedule.compile::<T>(),
             late_render_schedule: self.late_render_schedule.compile::<T>(),
+            extract_schedule: self.extract_schedule.compile::<T>(),
         }
     }
 }
@@ -315,6 +556,7 @@ pub struct CompiledSchedules {
     late_fixed_update_schedule: CompiledSchedule,
     render_schedule: CompiledSchedule,
     late_render_schedule: CompiledSchedule,
+    extract_schedule: CompiledSchedule,
 }
 
 impl CompiledSchedules {
@@ -355,6 +597,11 @@ impl CompiledSchedules {
             self.late_render_schedule.run(world);
         }
     }
+
+    pub fn extract(&mut self, world: &mut World) {
+        profiling::scope!("schedule::extract");
+        self.extract_schedule.run(world);
+    }
 }
 
 pub(crate) fn is_sync_point(system: &dyn System) -> bool {
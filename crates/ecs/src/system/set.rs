@@ -0,0 +1,139 @@
+use std::{
+    any::TypeId,
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+pub use ecs_macros::SystemSet;
+
+use crate::system::condition::{BoxedCondition, IntoCondition};
+
+/// A named group of systems that can be ordered and gated as a whole.
+///
+/// Derive with `#[derive(SystemSet)]` on a unit struct or a field-less enum.  Systems join a
+/// set with [`in_set`](crate::system::IntoSystemConfig::in_set), and the set itself is
+/// configured through [`Schedule::configure_set`](crate::system::schedule::Schedule::configure_set).
+///
+/// # Example
+/// ```
+/// use ecs::{Schedule, IntoSystemConfig, system::set::{IntoSystemSetConfig, SystemSet}};
+///
+/// #[derive(SystemSet, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum GameSet {
+///     Input,
+///     Movement,
+/// }
+///
+/// fn read_input() {}
+/// fn move_players() {}
+///
+/// let mut schedule = Schedule::new();
+/// schedule
+///     .configure_set(GameSet::Movement.after(GameSet::Input))
+///     .add_system(read_input.in_set(GameSet::Input))
+///     .add_system(move_players.in_set(GameSet::Movement));
+/// ```
+pub trait SystemSet: Debug + Hash + Eq + Send + Sync + 'static {
+    /// Returns the identifier used by schedules to refer to this set.
+    fn id(&self) -> SystemSetId {
+        SystemSetId::of(self)
+    }
+}
+
+/// Type-erased identifier of a [`SystemSet`] value.
+///
+/// Two set values compare equal when they have the same type and the same value.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SystemSetId {
+    type_id: TypeId,
+    value_hash: u64,
+}
+
+impl SystemSetId {
+    /// Builds the identifier of `set`.
+    pub fn of<S: SystemSet + ?Sized>(set: &S) -> Self {
+        let mut hasher = DefaultHasher::new();
+        set.hash(&mut hasher);
+        Self {
+            type_id: TypeId::of::<S>(),
+            value_hash: hasher.finish(),
+        }
+    }
+}
+
+/// A system set bundled with ordering constraints and run conditions.
+///
+/// Created by calling [`.after()`](IntoSystemSetConfig::after),
+/// [`.before()`](IntoSystemSetConfig::before) or [`.run_if()`](IntoSystemSetConfig::run_if)
+/// on any [`SystemSet`], and passed to
+/// [`Schedule::configure_set`](crate::system::schedule::Schedule::configure_set).
+pub struct SystemSetConfig {
+    pub(crate) set: SystemSetId,
+    /// Sets whose systems must all run before the systems of this set.
+    pub(crate) after: Vec<SystemSetId>,
+    /// Sets whose systems must all run after the systems of this set.
+    pub(crate) before: Vec<SystemSetId>,
+    /// Conditions that must all hold for any system of this set to run.
+    pub(crate) conditions: Vec<BoxedCondition>,
+}
+
+impl SystemSetConfig {
+    /// Declares that every system in `set` must run before the systems of this set.
+    pub fn after(mut self, set: impl SystemSet) -> Self {
+        self.after.push(set.id());
+        self
+    }
+
+    /// Declares that every system in `set` must run after the systems of this set.
+    pub fn before(mut self, set: impl SystemSet) -> Self {
+        self.before.push(set.id());
+        self
+    }
+
+    /// Gates every system of this set behind `condition`.
+    ///
+    /// The condition is evaluated at most once per schedule run, right before the first
+    /// system of the set would run.
+    pub fn run_if<M>(mut self, condition: impl IntoCondition<M>) -> Self {
+        self.conditions.push(condition.into_condition());
+        self
+    }
+}
+
+/// Converts a [`SystemSet`] or [`SystemSetConfig`] into a [`SystemSetConfig`].
+pub trait IntoSystemSetConfig: Sized {
+    /// Wraps `self` into a [`SystemSetConfig`].
+    fn into_set_config(self) -> SystemSetConfig;
+
+    /// Declares that every system in `set` must run before the systems of this set.
+    fn after(self, set: impl SystemSet) -> SystemSetConfig {
+        self.into_set_config().after(set)
+    }
+
+    /// Declares that every system in `set` must run after the systems of this set.
+    fn before(self, set: impl SystemSet) -> SystemSetConfig {
+        self.into_set_config().before(set)
+    }
+
+    /// Gates every system of this set behind `condition`.
+    fn run_if<M>(self, condition: impl IntoCondition<M>) -> SystemSetConfig {
+        self.into_set_config().run_if(condition)
+    }
+}
+
+impl<S: SystemSet> IntoSystemSetConfig for S {
+    fn into_set_config(self) -> SystemSetConfig {
+        SystemSetConfig {
+            set: self.id(),
+            after: Vec::new(),
+            before: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+}
+
+impl IntoSystemSetConfig for SystemSetConfig {
+    fn into_set_config(self) -> SystemSetConfig {
+        self
+    }
+}
@@ -137,12 +137,10 @@ impl Table {
         self.entities.insert(*row, entity);
     }
 
+    /// Counts entities rather than column entries, so a table without columns (the archetype
+    /// of entities with no components) still reports its rows.
     pub fn get_row_count(&self) -> usize {
-        self.columns
-            .values()
-            .next()
-            .map(|column| column.len())
-            .unwrap_or(0)
+        self.entities.len()
     }
 
     pub fn has_column(&self, type_id: ComponentId) -> bool {
@@ -3,14 +3,25 @@ use anymap3::AnyMap;
 use facet::Facet;
 use log::warn;
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::{any::TypeId, cell::UnsafeCell, collections::HashMap, marker::PhantomData, ptr};
+use std::{
+    any::{Any, TypeId},
+    cell::UnsafeCell,
+    collections::HashMap,
+    marker::PhantomData,
+    ptr,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use crate::component::bundle::ComponentBundle;
 use crate::component::reflection::ComponentReflection;
 use crate::component::registry::ComponentRegistry;
 use crate::component::Tick;
 use crate::entity::entity_store::EntityStore;
-use crate::entity::hierarchy::{ChildOf, Children};
+use crate::entity::hierarchy::{AncestorIter, ChildOf, Children, DepthFirstIter, DescendantIter};
+use crate::events::Event;
+use crate::observer::{
+    BoxedObserver, IntoObserver, ObserverEvent, ObserverId, ObserverKey, Observers,
+};
 use crate::resource::ResourceStorage;
 use crate::table::MutableCellAccessor;
 use crate::{
@@ -20,10 +31,21 @@ use crate::{
     entity::{Entity, EntityLocation, EntityType},
     resource::Resource,
     system::input::SystemInput,
-    table::{Table, TableRowIndex},
+    table::{Table, TableRow, TableRowIndex},
     utilities::TypeIdMap,
 };
 
+/// Identifies a [`World`] for as long as the process runs; no two worlds share one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WorldId(u64);
+
+impl WorldId {
+    fn new() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        WorldId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 /// The central container of the ECS.
 ///
 /// A `World` stores all entities together with their components and all
@@ -41,12 +63,14 @@ use crate::{
 /// let entity = world.spawn(Health(100.0));
 /// ```
 pub struct World {
+    id: WorldId,
     archetypes: Vec<Archetype>,
     resources: AnyMap,
     component_registry: ComponentRegistry,
     entity_store: EntityStore,
     archetype_index: HashMap<EntityType, usize>,
     component_lifetimes: TypeIdMap<ComponentLifecycleCallbacks>,
+    observers: Observers,
     current_tick: u32,
 }
 
@@ -57,10 +81,12 @@ impl World {
     /// Creates a new, empty `World` with no entities or resources.
     pub fn new() -> World {
         Self {
+            id: WorldId::new(),
             archetypes: Vec::new(),
             archetype_index: HashMap::new(),
             resources: AnyMap::new(),
             component_lifetimes: Default::default(),
+            observers: Observers::default(),
             entity_store: EntityStore::new(),
             current_tick: 0,
             component_registry: ComponentRegistry::default(),
@@ -105,7 +131,42 @@ impl World {
     }
 
     /// Removes an entity and all of its components from the world.
+    ///
+    /// The entity is removed from its parent's [`Children`], and its own children become
+    /// roots.  Use [`despawn_recursive`](World::despawn_recursive) to despawn them as well.
     pub fn despawn(&mut self, entity: Entity) {
+        if self.entity_store.find_location(entity).is_some() {
+            self.detach_from_parent(entity);
+            let children = self
+                .get_component_for_entity::<Children>(entity)
+                .map(|children| children.to_vec())
+                .unwrap_or_default();
+            for child in children {
+                self.remove_component::<ChildOf>(child);
+            }
+        }
+
+        self.despawn_internal(entity);
+    }
+
+    /// Removes an entity together with all of its descendants.
+    ///
+    /// The entity is removed from its parent's [`Children`].
+    pub fn despawn_recursive(&mut self, entity: Entity) {
+        let descendants: Vec<Entity> = self.descendants(entity).collect();
+        self.detach_from_parent(entity);
+
+        // Deepest entities first, so lifecycle callbacks still see intact ancestors.
+        for descendant in descendants.into_iter().rev() {
+            // A lifecycle callback may already have despawned it.
+            if self.entity_store.find_location(descendant).is_some() {
+                self.despawn_internal(descendant);
+            }
+        }
+        self.despawn_internal(entity);
+    }
+
+    fn despawn_internal(&mut self, entity: Entity) {
         match self.entity_store.find_location(entity) {
             Some(location) => {
                 {
@@ -133,16 +194,24 @@ impl World {
 
                 archetype.remove_swap(location.row);
                 self.entity_store.free(entity);
+                self.observers.remove_for_entity(entity);
             }
             None => panic!("Entity {:?} should exist in the world", entity),
         }
     }
 
+    /// Returns this world's unique id.
+    pub fn id(&self) -> WorldId {
+        self.id
+    }
+
     pub fn archetypes(&self) -> &Vec<Archetype> {
         &self.archetypes
     }
 
-    pub fn get_archetypes_mut(&mut self) -> &mut Vec<Archetype> {
+    /// Archetypes are only ever appended, which lets queries cache archetype indices; hence
+    /// only a slice is handed out here.
+    pub fn get_archetypes_mut(&mut self) -> &mut [Archetype] {
         &mut self.archetypes
     }
 
@@ -169,13 +238,12 @@ impl World {
     ) {
         match self.entity_store.find_location(entity) {
             Some(location) => {
-                let previous_archetype = &mut self.archetypes[location.archetype_index as usize];
+                let previous_index = location.archetype_index as usize;
+                let previous_archetype = &mut self.archetypes[previous_index];
 
                 let inserted_id = TypeId::of::<T>();
-                let mut component_ids = previous_archetype.component_ids().to_vec();
-                component_ids.push(inserted_id);
-
-                let entity_type = generate_type_id(&component_ids);
+                let added = !previous_archetype.contains(inserted_id);
+                let cached_index = previous_archetype.insert_edge(inserted_id);
 
                 // Update the location of the entity being swapped
                 // It will take the location of the entity being removed
@@ -192,33 +260,36 @@ impl World {
                     Tick::new(self.current_tick),
                 );
 
-                // Add row to new archetype
-                let archetype_index = match self.archetype_index.entry(entity_type.clone()) {
-                    Occupied(occupied_entry) => {
-                        let new_archetype_index = *occupied_entry.get();
-                        let new_archetype = &mut self.archetypes[*occupied_entry.get()];
-                        new_archetype.add_row(removed_row);
-                        new_archetype_index
+                // Add row to new archetype, following the cached edge when there is one
+                let archetype_index = match cached_index {
+                    Some(archetype_index) => {
+                        self.archetypes[archetype_index].add_row(removed_row);
+                        archetype_index
                     }
-                    Vacant(vacant_entry) => {
-                        let new_archetype_index = self.archetypes.len();
-                        let archetype = Archetype::new(Table::from_row(removed_row), component_ids);
-                        self.archetypes.push(archetype);
-                        vacant_entry.insert(new_archetype_index);
-                        new_archetype_index
+                    None => {
+                        let mut component_ids =
+                            self.archetypes[previous_index].component_ids().to_vec();
+                        component_ids.push(inserted_id);
+
+                        let archetype_index = self.add_row_to_archetype(component_ids, removed_row);
+                        self.link_archetypes(previous_index, inserted_id, archetype_index);
+                        archetype_index
                     }
                 };
 
                 let location = EntityLocation {
                     archetype_index: archetype_index as u32,
-                    row: TableRowIndex::new(self.archetypes[archetype_index].len() - 1),
+                    row: TableRowIndex::new(self.archetypes[archetype_index].entities().len() - 1),
                 };
                 // Store in entity store
                 self.entity_store.set_location(entity, location);
 
                 if trigger_events {
-                    self.as_unsafe_world_cell_mut()
-                        .trigger_on_add_component(entity, &inserted_id);
+                    self.as_unsafe_world_cell_mut().trigger_on_insert_component(
+                        entity,
+                        &inserted_id,
+                        added,
+                    );
                 }
             }
             None => panic!("Entity should exist in the world"),
@@ -230,19 +301,29 @@ impl World {
         entity: Entity,
         trigger_events: bool,
     ) {
+        // Observers run while the component is still there, so they can read it; the
+        // `on_remove` hook follows once it is gone. Despawning keeps the same order.
+        if trigger_events && self.get_component_for_entity::<T>(entity).is_some() {
+            self.run_observers(ObserverKey::Remove(TypeId::of::<T>()), &(), Some(entity));
+
+            // An observer may have despawned the entity or removed the component itself.
+            if self.get_component_for_entity::<T>(entity).is_none() {
+                return;
+            }
+        }
+
         match self.entity_store.find_location(entity) {
             Some(location) => {
-                let previous_archetype = &mut self.archetypes[location.archetype_index as usize];
+                let previous_index = location.archetype_index as usize;
+                let previous_archetype = &mut self.archetypes[previous_index];
 
                 let removed_id = TypeId::of::<T>();
 
-                let mut component_ids = previous_archetype.component_ids().to_vec();
-                if let Some(removed_index) = component_ids.iter().position(|id| *id == removed_id) {
-                    component_ids.swap_remove(removed_index);
-                } else {
+                if !previous_archetype.contains(removed_id) {
                     warn!("Entity does not have the component being removed.");
                     return;
                 }
+                let cached_index = previous_archetype.remove_edge(removed_id);
 
                 // Update the location of the entity being swapped: it will
                 // take the location of the entity being removed. This must
@@ -252,36 +333,35 @@ impl World {
                     self.entity_store.set_location(*swapped_entity, location);
                 }
 
-                let entity_type = generate_type_id(&component_ids);
-
                 // Remove row from previous archetype
                 let mut removed_row = previous_archetype.remove_swap(location.row);
 
                 // Remove component from the removed row
                 removed_row.remove::<T>();
 
-                let location = match self.archetype_index.entry(entity_type.clone()) {
-                    Occupied(occupied_entry) => {
-                        let new_archetype_index = *occupied_entry.get();
-                        let new_archetype = &mut self.archetypes[*occupied_entry.get()];
-                        new_archetype.add_row(removed_row);
-                        EntityLocation {
-                            archetype_index: new_archetype_index as u32,
-                            row: TableRowIndex::new(self.archetypes[new_archetype_index].len() - 1),
-                        }
+                // Add row to new archetype, following the cached edge when there is one
+                let archetype_index = match cached_index {
+                    Some(archetype_index) => {
+                        self.archetypes[archetype_index].add_row(removed_row);
+                        archetype_index
                     }
-                    Vacant(vacant_entry) => {
-                        let new_archetype_index = self.archetypes.len();
-                        let archetype = Archetype::new(Table::from_row(removed_row), component_ids);
-                        self.archetypes.push(archetype);
-                        vacant_entry.insert(new_archetype_index);
-                        EntityLocation {
-                            archetype_index: new_archetype_index as u32,
-                            row: TableRowIndex::new(0),
-                        }
+                    None => {
+                        let mut component_ids =
+                            self.archetypes[previous_index].component_ids().to_vec();
+                        component_ids.retain(|id| *id != removed_id);
+
+                        let archetype_index = self.add_row_to_archetype(component_ids, removed_row);
+                        self.link_archetypes(archetype_index, removed_id, previous_index);
+                        archetype_index
                     }
                 };
 
+                // The archetype without any component has no columns to count rows from, so
+                // the entity list gives the row.
+                let location = EntityLocation {
+                    archetype_index: archetype_index as u32,
+                    row: TableRowIndex::new(self.archetypes[archetype_index].entities().len() - 1),
+                };
                 // Store in entity store
                 self.entity_store.set_location(entity, location);
 
@@ -294,6 +374,37 @@ impl World {
         }
     }
 
+    /// Adds `row` to the archetype made of `component_ids`, creating the archetype if needed.
+    /// Returns the index of the archetype.
+    fn add_row_to_archetype(&mut self, component_ids: Vec<ComponentId>, row: TableRow) -> usize {
+        let entity_type = generate_type_id(&component_ids);
+
+        match self.archetype_index.entry(entity_type) {
+            Occupied(occupied_entry) => {
+                let archetype_index = *occupied_entry.get();
+                self.archetypes[archetype_index].add_row(row);
+                archetype_index
+            }
+            Vacant(vacant_entry) => {
+                let archetype_index = self.archetypes.len();
+                let archetype = Archetype::new(Table::from_row(row), component_ids);
+                self.archetypes.push(archetype);
+                vacant_entry.insert(archetype_index);
+                archetype_index
+            }
+        }
+    }
+
+    /// Records in the archetype graph that inserting `component_id` into the archetype at
+    /// `without` leads to the archetype at `with`, and removing it leads back.
+    fn link_archetypes(&mut self, without: usize, component_id: ComponentId, with: usize) {
+        self.archetypes[without].set_insert_edge(component_id, with);
+        // Inserting a component the entity already has replaces it in place.
+        if without != with {
+            self.archetypes[with].set_remove_edge(component_id, without);
+        }
+    }
+
     pub(crate) fn entity_store(&self) -> &EntityStore {
         &self.entity_store
     }
@@ -456,8 +567,22 @@ impl World {
     ///
     /// Inserts a [`ChildOf`](crate::entity::hierarchy::ChildOf) component on `child` and
     /// updates (or creates) the [`Children`](crate::entity::hierarchy::Children) component on
-    /// `parent`.
+    /// `parent`.  If `child` already had another parent, it is removed from that parent's
+    /// children first.  Requests that would make an entity its own ancestor are ignored with a
+    /// warning.
     pub fn add_child(&mut self, parent: Entity, child: Entity) {
+        if parent == child || self.ancestors(parent).any(|ancestor| ancestor == child) {
+            warn!("Cannot add {child} as a child of {parent}: the hierarchy would form a cycle.");
+            return;
+        }
+        if self
+            .get_component_for_entity::<ChildOf>(child)
+            .is_some_and(|child_of| child_of.parent() == parent)
+        {
+            return;
+        }
+
+        self.detach_from_parent(child);
         self.insert_component(ChildOf::new(parent), child);
 
         match self.get_component_accessor_for_entity_mut::<Children>(parent) {
@@ -470,6 +595,135 @@ impl World {
         }
     }
 
+    /// Makes `child` a child of `parent`, removing it from its previous parent if it had one.
+    ///
+    /// Same as [`add_child`](World::add_child) with the arguments swapped.
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) {
+        self.add_child(parent, child);
+    }
+
+    /// Breaks the parent-child relationship between `parent` and `child`; `child` becomes a
+    /// root entity.
+    ///
+    /// If `child` is not a child of `parent`, a warning is logged and the call is a no-op.
+    pub fn remove_child(&mut self, parent: Entity, child: Entity) {
+        if !self
+            .get_component_for_entity::<ChildOf>(child)
+            .is_some_and(|child_of| child_of.parent() == parent)
+        {
+            warn!("{child} is not a child of {parent}.");
+            return;
+        }
+
+        self.detach_from_parent(child);
+        self.remove_component::<ChildOf>(child);
+    }
+
+    /// Removes `child` from its parent's [`Children`], removing the component once it is
+    /// empty.  The [`ChildOf`] component of `child` is left untouched.
+    fn detach_from_parent(&mut self, child: Entity) {
+        let Some(parent) = self
+            .get_component_for_entity::<ChildOf>(child)
+            .map(ChildOf::parent)
+        else {
+            return;
+        };
+
+        let is_empty = match self.get_component_accessor_for_entity_mut::<Children>(parent) {
+            Some(table_cell) => {
+                table_cell.data.remove_child(child);
+                table_cell.data.is_empty()
+            }
+            None => false,
+        };
+        if is_empty {
+            self.remove_component::<Children>(parent);
+        }
+    }
+
+    /// Returns an iterator over the ancestors of `entity`, from its parent up to the root.
+    pub fn ancestors(&self, entity: Entity) -> AncestorIter<'_> {
+        AncestorIter::new(self, entity)
+    }
+
+    /// Returns a breadth-first iterator over the descendants of `entity`.
+    pub fn descendants(&self, entity: Entity) -> DescendantIter<'_> {
+        DescendantIter::new(self, entity)
+    }
+
+    /// Returns a depth-first, pre-order iterator over the descendants of `entity`.
+    pub fn descendants_depth_first(&self, entity: Entity) -> DepthFirstIter<'_> {
+        DepthFirstIter::new(self, entity)
+    }
+
+    /// Registers a global observer, run every time its event is triggered.
+    ///
+    /// The first parameter of the observer is a [`Trigger`](crate::observer::Trigger) naming
+    /// the event; the others are regular system parameters.  Commands queued by the
+    /// observer are applied as soon as it returns.
+    pub fn add_observer<E: ObserverEvent, M>(
+        &mut self,
+        observer: impl IntoObserver<E, M>,
+    ) -> ObserverId {
+        self.add_observer_boxed(E::observer_key(), None, observer.into_observer())
+    }
+
+    /// Registers an observer that only runs for events targeting `entity`.
+    ///
+    /// The observer is removed when `entity` is despawned.
+    pub fn observe<E: ObserverEvent, M>(
+        &mut self,
+        entity: Entity,
+        observer: impl IntoObserver<E, M>,
+    ) -> ObserverId {
+        self.add_observer_boxed(E::observer_key(), Some(entity), observer.into_observer())
+    }
+
+    pub(crate) fn add_observer_boxed(
+        &mut self,
+        key: ObserverKey,
+        target: Option<Entity>,
+        observer: BoxedObserver,
+    ) -> ObserverId {
+        self.observers.add(key, target, observer)
+    }
+
+    /// Unregisters an observer.  Does nothing if it was already removed.
+    pub fn remove_observer(&mut self, id: ObserverId) {
+        self.observers.remove(id);
+    }
+
+    /// Runs the global observers of `event` immediately.
+    pub fn trigger<E: Event + 'static>(&mut self, event: E) {
+        self.run_observers(E::observer_key(), &event, None);
+    }
+
+    /// Runs the observers of `event` scoped to `entity`, and the global ones, immediately.
+    pub fn trigger_targets<E: Event + 'static>(&mut self, event: E, entity: Entity) {
+        self.run_observers(E::observer_key(), &event, Some(entity));
+    }
+
+    pub(crate) fn run_observers(
+        &mut self,
+        key: ObserverKey,
+        payload: &dyn Any,
+        target: Option<Entity>,
+    ) {
+        if !self.observers.has_observers(key) {
+            return;
+        }
+
+        for id in self.observers.matching(key, target) {
+            // An observer that is already running (the event was re-triggered from inside
+            // it) is skipped rather than re-entered.
+            let Some(mut observer) = self.observers.take(key, id) else {
+                continue;
+            };
+            observer.run(payload, target, self);
+            self.observers.put_back(key, id, observer);
+        }
+    }
+
     pub fn register_component<T: Component>(&mut self) {
         self.component_registry.register_component::<T>();
     }
@@ -481,6 +735,10 @@ impl World {
     pub(crate) fn get_reflection(&self, name: &str) -> Option<&ComponentReflection> {
         self.component_registry.get_reflection(name)
     }
+
+    pub(crate) fn get_reflection_by_id(&self, id: ComponentId) -> Option<&ComponentReflection> {
+        self.component_registry.get_reflection_by_id(id)
+    }
 }
 
 impl Default for World {
@@ -591,8 +849,26 @@ impl<'w> UnsafeWorldCell<'w> {
 
     pub(crate) fn trigger_on_add(&self, entity: Entity, ids: &[ComponentId]) {
         for id in ids {
-            self.trigger_on_add_component(entity, id);
+            self.trigger_on_insert_component(entity, id, true);
+        }
+    }
+
+    /// Runs the `on_add` callback of component `id`, then its [`OnAdd`](crate::observer::OnAdd)
+    /// observers if it is `added` rather than replaced, then its
+    /// [`OnInsert`](crate::observer::OnInsert) observers.
+    pub(crate) fn trigger_on_insert_component(
+        &self,
+        entity: Entity,
+        id: &ComponentId,
+        added: bool,
+    ) {
+        self.trigger_on_add_component(entity, id);
+
+        let world = self.world_mut();
+        if added {
+            world.run_observers(ObserverKey::Add(*id), &(), Some(entity));
         }
+        world.run_observers(ObserverKey::Insert(*id), &(), Some(entity));
     }
 
     pub(crate) fn trigger_on_add_component(&self, entity: Entity, id: &ComponentId) {
@@ -604,9 +880,23 @@ impl<'w> UnsafeWorldCell<'w> {
         }
     }
 
+    /// Runs the `OnRemove` observers, then the `on_remove` hook, of each component in `ids` —
+    /// the same order as [`World::remove_component`].
+    ///
+    /// Stops as soon as a callback despawns the entity: the nested despawn has already run
+    /// every remaining callback.
     pub(crate) fn trigger_on_remove(&self, entity: Entity, ids: &[ComponentId]) {
+        let is_alive = || self.world().entity_store.find_location(entity).is_some();
         for id in ids {
+            self.world_mut()
+                .run_observers(ObserverKey::Remove(*id), &(), Some(entity));
+            if !is_alive() {
+                return;
+            }
             self.trigger_on_remove_component(entity, id);
+            if !is_alive() {
+                return;
+            }
         }
     }
 
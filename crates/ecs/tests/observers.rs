@@ -0,0 +1,234 @@
+//! Observers: which lifecycle changes and triggered events reach them, how
+//! entity-scoped observers are filtered, and when their commands are applied.
+
+use ecs::command::CommandQueue;
+use ecs::component::{Component, ComponentLifecycleCallback};
+use ecs::entity::Entity;
+use ecs::events::Event;
+use ecs::observer::{OnAdd, OnInsert, OnRemove, Trigger};
+use ecs::query::Query;
+use ecs::resource::{ResMut, Resource};
+use ecs::system::executor::single_thread::SingleThreadedExecutor;
+use ecs::system::schedule::Schedule;
+use ecs::world::World;
+
+#[derive(Component)]
+struct Value(i32);
+
+#[derive(Component)]
+struct Companion;
+
+#[derive(Event)]
+struct Explode(u32);
+
+/// Every observer firing, in order.
+#[derive(Resource, Default)]
+struct Log(Vec<String>);
+
+fn log_added(trigger: Trigger<OnAdd<Value>>, values: Query<&Value>, mut log: ResMut<Log>) {
+    let value = values.get_entity(trigger.entity()).unwrap();
+    log.0.push(format!("add {}", value.0));
+}
+
+fn log_inserted(trigger: Trigger<OnInsert<Value>>, values: Query<&Value>, mut log: ResMut<Log>) {
+    let value = values.get_entity(trigger.entity()).unwrap();
+    log.0.push(format!("insert {}", value.0));
+}
+
+fn log_removed(trigger: Trigger<OnRemove<Value>>, values: Query<&Value>, mut log: ResMut<Log>) {
+    // Still readable: observers run before the component is removed.
+    let value = values.get_entity(trigger.entity()).unwrap();
+    log.0.push(format!("remove {}", value.0));
+}
+
+fn log_explosion(trigger: Trigger<Explode>, mut log: ResMut<Log>) {
+    let target = match trigger.target() {
+        Some(_) => "entity",
+        None => "world",
+    };
+    log.0.push(format!("global {} {target}", trigger.event().0));
+}
+
+fn world_with_log() -> World {
+    let mut world = World::new();
+    world.init_resource::<Log>();
+    world
+}
+
+fn log(world: &World) -> Vec<String> {
+    world.get_resource::<Log>().unwrap().0.clone()
+}
+
+#[test]
+fn lifecycle_observers_fire_for_add_insert_and_remove() {
+    let mut world = world_with_log();
+    world.add_observer(log_added);
+    world.add_observer(log_inserted);
+    world.add_observer(log_removed);
+
+    let entity = world.spawn(Value(1));
+    world.insert_component(Value(2), entity);
+    world.remove_component::<Value>(entity);
+    world.insert_component(Value(3), entity);
+    world.despawn(entity);
+
+    assert_eq!(
+        log(&world),
+        vec![
+            "add 1", "insert 1", // spawn
+            "insert 2", // replacing a value is not an add
+            "remove 2", "add 3", "insert 3", "remove 3", // despawn removes every component
+        ]
+    );
+}
+
+#[test]
+fn entity_observers_only_see_their_entity() {
+    let mut world = world_with_log();
+    let watched = world.spawn(Companion);
+    let other = world.spawn(Companion);
+
+    world.add_observer(log_explosion);
+    world.observe(
+        watched,
+        |trigger: Trigger<Explode>, mut log: ResMut<Log>| {
+            log.0.push(format!("watched {}", trigger.event().0));
+        },
+    );
+
+    world.trigger(Explode(1));
+    world.trigger_targets(Explode(2), other);
+    world.trigger_targets(Explode(3), watched);
+
+    // Entity observers go away with their entity.
+    world.despawn(watched);
+    world.trigger_targets(Explode(4), watched);
+
+    assert_eq!(
+        log(&world),
+        vec![
+            "global 1 world",
+            "global 2 entity",
+            "global 3 entity",
+            "watched 3",
+            "global 4 entity",
+        ]
+    );
+}
+
+#[test]
+fn observer_commands_are_applied_immediately() {
+    let mut world = World::new();
+    world.add_observer(|trigger: Trigger<OnAdd<Value>>, mut cmd: CommandQueue| {
+        cmd.insert(Companion, trigger.entity());
+    });
+
+    let entity = world.spawn(Value(0));
+
+    assert!(world
+        .get_component_for_entity::<Companion>(entity)
+        .is_some());
+}
+
+fn detonate(query: Query<Entity, ecs::With<Companion>>, mut cmd: CommandQueue) {
+    for entity in query.iter() {
+        cmd.trigger_targets(Explode(7), entity);
+    }
+}
+
+#[test]
+fn events_triggered_from_systems_reach_observers() {
+    let mut world = world_with_log();
+    world.spawn(Companion);
+    let id = world.add_observer(log_explosion);
+
+    let mut schedule = Schedule::new();
+    schedule.add_system(detonate);
+    let mut schedule = schedule.compile::<SingleThreadedExecutor>();
+    schedule.run(&mut world);
+
+    world.remove_observer(id);
+    schedule.run(&mut world);
+
+    assert_eq!(log(&world), vec!["global 7 entity"]);
+}
+
+/// Logs its `on_remove` hook next to the observers, to compare their order.
+struct Hooked;
+
+impl Component for Hooked {
+    fn name() -> &'static str {
+        "Hooked"
+    }
+
+    fn on_remove() -> Option<ComponentLifecycleCallback> {
+        Some(|mut world, _context| {
+            if let Some(log) = world.get_resource_mut::<Log>() {
+                log.0.push("hook".to_string());
+            }
+        })
+    }
+}
+
+#[test]
+fn removal_and_despawn_run_observers_before_the_hook() {
+    let mut world = world_with_log();
+    world.register_component_lifetimes::<Hooked>();
+    world.add_observer(|_: Trigger<OnRemove<Hooked>>, mut log: ResMut<Log>| {
+        log.0.push("observer".to_string());
+    });
+
+    let entity = world.spawn(Hooked);
+    world.remove_component::<Hooked>(entity);
+    world.insert_component(Hooked, entity);
+    world.despawn(entity);
+
+    assert_eq!(log(&world), vec!["observer", "hook", "observer", "hook"]);
+}
+
+#[test]
+fn an_observer_may_despawn_the_entity_losing_a_component() {
+    let mut world = World::new();
+    world.add_observer(|trigger: Trigger<OnRemove<Value>>, mut cmd: CommandQueue| {
+        cmd.despawn(trigger.entity());
+    });
+
+    let entity = world.spawn((Value(0), Companion));
+    let other = world.spawn((Value(1), Companion));
+    world.remove_component::<Value>(entity);
+
+    assert!(world
+        .get_component_for_entity::<Companion>(entity)
+        .is_none());
+    assert_eq!(
+        world
+            .get_component_for_entity::<Value>(other)
+            .map(|value| value.0),
+        Some(1)
+    );
+}
+
+#[test]
+fn an_observer_despawning_its_entity_mid_despawn_runs_each_hook_once() {
+    for companion_first in [false, true] {
+        let mut world = world_with_log();
+        world.register_component_lifetimes::<Hooked>();
+        world.add_observer(|trigger: Trigger<OnRemove<Value>>, mut cmd: CommandQueue| {
+            cmd.despawn(trigger.entity());
+        });
+
+        let entity = if companion_first {
+            world.spawn((Hooked, Value(0)))
+        } else {
+            world.spawn((Value(0), Hooked))
+        };
+        world.despawn(entity);
+
+        assert_eq!(
+            log(&world),
+            vec!["hook"],
+            "companion_first: {companion_first}"
+        );
+        assert!(world.get_component_for_entity::<Value>(entity).is_none());
+    }
+}
@@ -0,0 +1,148 @@
+//! Scenes: saving reflected components to JSON and spawning them back, with the hierarchy
+//! and entity references pointing to the newly spawned entities.
+
+use ecs::component::Component;
+use ecs::entity::hierarchy::{ChildOf, Children};
+use ecs::entity::Entity;
+use ecs::scene::Scene;
+use ecs::world::World;
+use facet::Facet;
+
+#[derive(Component, Facet, Debug, PartialEq)]
+struct Name(String);
+
+#[derive(Component, Facet, Debug, PartialEq)]
+struct Health {
+    current: f32,
+    max: f32,
+}
+
+/// Not registered for reflection, so never saved.
+#[derive(Component)]
+struct Runtime;
+
+/// Holds a reference to another entity of the scene.
+#[derive(Facet)]
+struct Target(Entity);
+
+impl Component for Target {
+    fn name() -> &'static str {
+        "Target"
+    }
+
+    fn map_entities(&mut self, mapper: &mut dyn FnMut(Entity) -> Entity) {
+        self.0 = mapper(self.0);
+    }
+}
+
+fn registered_world() -> World {
+    let mut world = World::new();
+    world.register_reflection::<Name>();
+    world.register_reflection::<Health>();
+    world.register_reflection::<Target>();
+    world
+}
+
+fn find_by_name(
+    world: &World,
+    map: &std::collections::HashMap<Entity, Entity>,
+    name: &str,
+) -> Entity {
+    map.values()
+        .copied()
+        .find(|entity| {
+            world
+                .get_component_for_entity::<Name>(*entity)
+                .is_some_and(|n| n.0 == name)
+        })
+        .unwrap()
+}
+
+#[test]
+fn scene_round_trips_components_hierarchy_and_references() {
+    let mut world = registered_world();
+    // Shift the entity handles, so the loaded ones can't match by accident.
+    world.spawn(Runtime);
+    world.spawn(Runtime);
+
+    let root = world.spawn((
+        Name("root".into()),
+        Health {
+            current: 3.0,
+            max: 10.0,
+        },
+    ));
+    let first = world.spawn((Name("first".into()), Runtime));
+    let second = world.spawn(Name("second".into()));
+    world.add_child(root, first);
+    world.add_child(root, second);
+    world.insert_component(Target(second), first);
+
+    let json = Scene::from_world(&world).to_json().unwrap();
+
+    let mut loaded = registered_world();
+    let map = Scene::from_json(&json).unwrap().spawn_into(&mut loaded);
+
+    // Entities holding only unreflected components are left out.
+    assert_eq!(map.len(), 3);
+
+    let new_root = find_by_name(&loaded, &map, "root");
+    let new_first = find_by_name(&loaded, &map, "first");
+    let new_second = find_by_name(&loaded, &map, "second");
+    assert_eq!(map[&root], new_root);
+
+    assert_eq!(
+        loaded.get_component_for_entity::<Health>(new_root),
+        Some(&Health {
+            current: 3.0,
+            max: 10.0
+        })
+    );
+    assert!(loaded
+        .get_component_for_entity::<Runtime>(new_first)
+        .is_none());
+
+    let children = loaded
+        .get_component_for_entity::<Children>(new_root)
+        .unwrap();
+    assert_eq!(&children[..], &[new_first, new_second]);
+    assert_eq!(
+        loaded
+            .get_component_for_entity::<ChildOf>(new_second)
+            .unwrap()
+            .parent(),
+        new_root
+    );
+
+    assert_eq!(
+        loaded
+            .get_component_for_entity::<Target>(new_first)
+            .unwrap()
+            .0,
+        new_second
+    );
+}
+
+#[test]
+fn unknown_components_are_skipped() {
+    let mut world = registered_world();
+    world.spawn((
+        Name("kept".into()),
+        Health {
+            current: 1.0,
+            max: 1.0,
+        },
+    ));
+    let json = Scene::from_world(&world).to_json().unwrap();
+
+    let mut loaded = World::new();
+    loaded.register_reflection::<Name>();
+    let map = Scene::from_json(&json).unwrap().spawn_into(&mut loaded);
+
+    let entity = *map.values().next().unwrap();
+    assert_eq!(
+        loaded.get_component_for_entity::<Name>(entity),
+        Some(&Name("kept".into()))
+    );
+    assert!(loaded.get_component_for_entity::<Health>(entity).is_none());
+}
@@ -1,57 +1,197 @@
 use std::{
     any::TypeId,
     collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+    path::PathBuf,
     sync::{Arc, RwLock, Weak},
 };
 
+use anyhow::Context;
 use crossbeam_channel::{Receiver, Sender};
-use ecs::{resource::Resource, world};
+use ecs::{
+    resource::Resource,
+    world::{self, World},
+};
 use tasks::load_pool::LoadTaskPool;
 
 use crate::{
-    assets::{handle::StrongAssetHandle, AssetPath, LoadableAsset},
+    assets::{handle::StrongAssetHandle, AssetPath, Fingerprint, LoadableAsset},
     tasks::{task_pool::TaskPool, Task},
 };
 
 use super::{
     asset_container::AssetContainer,
     asset_store::AssetStore,
-    handle::{AssetHandle, AssetLifetimeEvent},
+    bake::{Bake, BakeCache, BakeKey, ContentHasher},
+    handle::{AssetHandle, AssetLifetimeEvent, SerializedHandle},
+    source::{AssetSource, AssetSources},
+    status::{send_failed, send_loaded, AssetStatus},
     Asset, AssetId,
 };
 
+/// Sends the typed load events of one asset, whose type is erased by the time they are due.
+#[derive(Clone, Copy)]
+struct AssetNotifier {
+    send_loaded: fn(&mut World, AssetId),
+    send_failed: fn(&mut World, AssetId, String),
+}
+
+impl AssetNotifier {
+    fn of<A: Asset>() -> Self {
+        Self {
+            send_loaded: send_loaded::<A>,
+            send_failed: send_failed::<A>,
+        }
+    }
+}
+
 struct LoadedAsset {
     pub(crate) id: AssetId,
     pub(crate) value: Box<dyn AssetContainer>,
+    pub(crate) dependencies: Vec<AssetId>,
+    notifier: AssetNotifier,
 }
 
 impl LoadedAsset {
-    pub fn new<A: Asset + 'static>(id: AssetId, value: A) -> Self {
+    pub fn new<A: Asset + 'static>(id: AssetId, value: A, dependencies: Vec<AssetId>) -> Self {
         LoadedAsset {
             id,
             value: Box::new(value),
+            dependencies,
+            notifier: AssetNotifier::of::<A>(),
         }
     }
 }
 
+struct FailedAsset {
+    id: AssetId,
+    error: String,
+    notifier: AssetNotifier,
+}
+
 enum AssetLoadEvent {
     Loaded(LoadedAsset),
-    LoadFailed(AssetId),
+    LoadFailed(FailedAsset),
 }
 
+/// Passed to an [`AssetLoader`](super::asset_loader::AssetLoader) while it loads one asset.
+///
+/// Loading or adding sub-assets through the context, rather than through
+/// [`asset_server`](AssetLoadContext::asset_server), records them as dependencies of the
+/// asset being loaded: it only counts as loaded once they are, and
+/// [`AssetServer::dependencies_of`] lists them.
 pub struct AssetLoadContext {
     asset_server: AssetServer,
+    /// The path of the asset being loaded, followed by `?` and the hash of its usage settings
+    /// unless they are the default ones.  Labeled sub-asset ids are derived from it.
+    scope: String,
+    dependencies: Vec<AssetId>,
 }
 
 impl AssetLoadContext {
     pub fn asset_server(&self) -> &AssetServer {
         &self.asset_server
     }
+
+    /// Loads a sub-asset from `path` and records it as a dependency.
+    pub fn load<'a, A>(&mut self, path: impl Into<AssetPath<'a>>) -> AssetHandle<A>
+    where
+        A: LoadableAsset + 'static,
+    {
+        let handle = self.asset_server.load(path);
+        self.dependencies.push(handle.id());
+        handle
+    }
+
+    /// Loads a sub-asset from `path` with the given settings and records it as a dependency.
+    pub fn load_with_usage_settings<'a, A>(
+        &mut self,
+        path: impl Into<AssetPath<'a>>,
+        usage_settings: A::UsageSettings,
+    ) -> AssetHandle<A>
+    where
+        A: LoadableAsset + 'static,
+    {
+        let handle = self
+            .asset_server
+            .load_with_usage_settings(path, usage_settings);
+        self.dependencies.push(handle.id());
+        handle
+    }
+
+    /// Reads the file at `path` from its [`AssetSource`].
+    pub async fn read(&self, path: &AssetPath<'_>) -> anyhow::Result<Vec<u8>> {
+        self.asset_server.read(path).await
+    }
+
+    /// Reads the file at `path` from its [`AssetSource`] as UTF-8 text.
+    pub async fn read_to_string(&self, path: &AssetPath<'_>) -> anyhow::Result<String> {
+        self.asset_server.read_to_string(path).await
+    }
+
+    /// Where `path` lives on the local filesystem, if its [`AssetSource`] reads from one.
+    pub fn local_path(&self, path: &AssetPath<'_>) -> Option<PathBuf> {
+        self.asset_server.local_path(path)
+    }
+
+    /// Returns the asset baked under `key`, if the server has a [`BakeCache`] holding a fresh
+    /// one.
+    pub fn baked<A: Bake>(&self, key: &BakeKey) -> Option<A> {
+        let cache = self.asset_server.bake_cache()?;
+        cache.get(key).unwrap_or_else(|error| {
+            log::warn!("Ignoring baked {}: {error:#}", A::name());
+            None
+        })
+    }
+
+    /// Stores `asset` under `key`, if the server has a writable [`BakeCache`].
+    pub fn bake<A: Bake>(&self, key: &BakeKey, asset: &A) {
+        let Some(cache) = self.asset_server.bake_cache() else {
+            return;
+        };
+        if let Err(error) = cache.insert(key, asset) {
+            log::warn!("Failed to bake {}: {error:#}", A::name());
+        }
+    }
+
+    /// Adds a sub-asset built by the loader and records it as a dependency.
+    pub fn add<A: Asset>(&mut self, asset: A) -> AssetHandle<A> {
+        let handle = self.asset_server.add(asset);
+        self.dependencies.push(handle.id());
+        handle
+    }
+
+    /// Like [`add`](Self::add), but gives the sub-asset an id derived from the path being
+    /// loaded and `label`, which stays the same across runs and reloads.  Saved scenes refer to
+    /// the sub-asset as `path#label` (see [`SerializedHandle`]).
+    ///
+    /// Labels must be unique within one loader, e.g. `mesh/0` and `mesh/1`.
+    pub fn add_labeled<A: Asset>(&mut self, label: &str, asset: A) -> AssetHandle<A> {
+        let labeled_path = format!("{}#{label}", self.scope);
+        let id = AssetId::derived(&labeled_path);
+        let handle = self
+            .asset_server
+            .add_with_path(id, asset, Some(labeled_path));
+        self.dependencies.push(handle.id());
+        handle
+    }
+
+    /// Like [`add`](Self::add), but reuses an identical asset if there is one (see
+    /// [`AssetServer::add_unique`]).
+    pub fn add_unique<A: Fingerprint>(&mut self, asset: A) -> AssetHandle<A> {
+        let handle = self.asset_server.add_unique(asset);
+        self.dependencies.push(handle.id());
+        handle
+    }
 }
 
 impl AssetLoadContext {
-    pub(crate) fn new(asset_server: AssetServer) -> Self {
-        Self { asset_server }
+    pub(crate) fn new(asset_server: AssetServer, scope: String) -> Self {
+        Self {
+            asset_server,
+            scope,
+            dependencies: Vec::new(),
+        }
     }
 }
 
@@ -59,10 +199,144 @@ pub(crate) struct AssetInfo {
     handle: Weak<StrongAssetHandle>,
 }
 
+/// Re-runs the loader of one path-loaded asset with the settings it was first loaded with.
+type Reloader = Box<dyn Fn(&AssetServer) + Send + Sync>;
+
+/// Loads one [`LoadableAsset`] type from a path, with its type erased.
+type PathLoader = fn(&AssetServer, AssetPath<'static>) -> Arc<StrongAssetHandle>;
+
+/// What two requests must share to resolve to the same asset.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum AssetKey {
+    /// Loaded from a path, with usage settings hashing to the second field.
+    Path(AssetPath<'static>, u64),
+    /// Added through [`AssetServer::add_unique`], with the bytes of its
+    /// [`Fingerprint`] as the second field, so equal hashes of different content stay apart.
+    Content(TypeId, Box<[u8]>),
+}
+
+/// The [`AssetKey`] of each path-loaded or deduplicated asset, in both directions.
+#[derive(Default)]
+struct AssetKeys {
+    ids: HashMap<AssetKey, AssetId>,
+    keys: HashMap<AssetId, AssetKey>,
+}
+
+impl AssetKeys {
+    fn insert(&mut self, key: AssetKey, id: AssetId) {
+        self.ids.insert(key.clone(), id);
+        self.keys.insert(id, key);
+    }
+
+    fn remove(&mut self, id: AssetId) {
+        if let Some(key) = self.keys.remove(&id) {
+            self.ids.remove(&key);
+        }
+    }
+
+    /// Returns the assets loaded from `path`, with any usage settings.
+    fn loaded_from(&self, path: &AssetPath<'_>) -> Vec<AssetId> {
+        self.ids
+            .iter()
+            .filter(|(key, _)| matches!(key, AssetKey::Path(key_path, _) if key_path == path))
+            .map(|(_, id)| *id)
+            .collect()
+    }
+}
+
+/// Keeps every byte fed into it, so [`Fingerprint`]s can be compared in full.
+#[derive(Default)]
+struct FingerprintBytes(Vec<u8>);
+
+impl Hasher for FingerprintBytes {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let mut hasher = ContentHasher::default();
+        hasher.write(&self.0);
+        hasher.finish()
+    }
+}
+
+/// Names a load of `path` with `usage_settings`: the path itself for the default settings,
+/// `path?settings` with the settings' hash otherwise.  Labeled sub-assets derive their ids from
+/// it, and handles to the load are saved as it.
+fn load_scope<A: LoadableAsset>(path: &AssetPath<'_>, usage_settings: &A::UsageSettings) -> String {
+    let settings_hash = hash_of(usage_settings);
+    if settings_hash == hash_of(&A::default_usage_settings()) {
+        path.to_string()
+    } else {
+        format!("{path}?{settings_hash:016x}")
+    }
+}
+
+/// Hashes with [`ContentHasher`], so hashes that end up in asset ids are stable across runs.
+fn hash_of(value: &impl Hash) -> u64 {
+    let mut hasher = ContentHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Which assets were loaded or added by the loader of which other asset.
+#[derive(Default)]
+struct DependencyGraph {
+    dependencies: HashMap<AssetId, Vec<AssetId>>,
+    dependents: HashMap<AssetId, HashSet<AssetId>>,
+}
+
+impl DependencyGraph {
+    /// Replaces the dependencies of `parent`, e.g. after a reload.
+    fn set(&mut self, parent: AssetId, dependencies: Vec<AssetId>) {
+        self.remove_dependencies(parent);
+        for &child in &dependencies {
+            self.dependents.entry(child).or_default().insert(parent);
+        }
+        if !dependencies.is_empty() {
+            self.dependencies.insert(parent, dependencies);
+        }
+    }
+
+    /// Forgets every edge to and from `id`.
+    fn remove(&mut self, id: AssetId) {
+        self.remove_dependencies(id);
+        for parent in self.dependents.remove(&id).unwrap_or_default() {
+            if let Some(children) = self.dependencies.get_mut(&parent) {
+                children.retain(|child| *child != id);
+            }
+        }
+    }
+
+    fn remove_dependencies(&mut self, parent: AssetId) {
+        for child in self.dependencies.remove(&parent).unwrap_or_default() {
+            if let Some(parents) = self.dependents.get_mut(&child) {
+                parents.remove(&parent);
+                if parents.is_empty() {
+                    self.dependents.remove(&child);
+                }
+            }
+        }
+    }
+}
+
 pub(crate) struct AssetServerData {
     pending_tasks: RwLock<HashMap<AssetId, Task<()>>>,
-    loaded_assets: RwLock<HashSet<AssetId>>,
-    path_to_id: RwLock<HashMap<AssetPath<'static>, AssetId>>,
+    /// Status of each asset on its own, regardless of its dependencies.
+    statuses: RwLock<HashMap<AssetId, AssetStatus>>,
+    dependencies: RwLock<DependencyGraph>,
+    /// Assets whose own load finished but whose load events are not sent yet.
+    unnotified: RwLock<HashMap<AssetId, AssetNotifier>>,
+    keys: RwLock<AssetKeys>,
+    reloaders: RwLock<HashMap<AssetId, Reloader>>,
+    path_loaders: RwLock<HashMap<TypeId, PathLoader>>,
+    /// The same loaders, by the file extensions they read.
+    extension_loaders: RwLock<HashMap<&'static str, PathLoader>>,
+    /// Files loaded for a saved `path#label` handle, held until their own load finishes so
+    /// their labeled sub-assets get added.
+    label_parents: RwLock<Vec<Arc<StrongAssetHandle>>>,
+    sources: RwLock<AssetSources>,
+    bake_cache: RwLock<Option<Arc<BakeCache>>>,
     handle_provider: AssetHandleProvider,
     asset_load_event_sender: Sender<AssetLoadEvent>,
     asset_load_event_receiver: Receiver<AssetLoadEvent>,
@@ -78,8 +352,16 @@ impl AssetServer {
         let (asset_load_event_sender, asset_load_event_receiver) = crossbeam_channel::unbounded();
         let server_data = AssetServerData {
             pending_tasks: RwLock::new(HashMap::new()),
-            loaded_assets: RwLock::new(HashSet::new()),
-            path_to_id: RwLock::new(HashMap::new()),
+            statuses: RwLock::new(HashMap::new()),
+            dependencies: RwLock::new(DependencyGraph::default()),
+            unnotified: RwLock::new(HashMap::new()),
+            keys: RwLock::new(AssetKeys::default()),
+            reloaders: RwLock::new(HashMap::new()),
+            path_loaders: RwLock::new(HashMap::new()),
+            extension_loaders: RwLock::new(HashMap::new()),
+            label_parents: RwLock::new(Vec::new()),
+            sources: RwLock::new(AssetSources::default()),
+            bake_cache: RwLock::new(None),
             handle_provider: AssetHandleProvider::new(),
             asset_load_event_sender,
             asset_load_event_receiver,
@@ -96,6 +378,71 @@ impl AssetServer {
             .register_asset::<A>(asset.clone_drop_sender());
     }
 
+    /// Lets [`handle_from_serialized`](Self::handle_from_serialized) load assets of type `A`
+    /// from a saved path, and the files with [`A::extensions`](LoadableAsset::extensions) for
+    /// their labeled sub-assets.
+    pub fn register_loader<A: LoadableAsset>(&self) {
+        let loader: PathLoader = |server, path| server.load::<A>(path).into_strong();
+        self.data
+            .path_loaders
+            .write()
+            .unwrap()
+            .insert(TypeId::of::<A>(), loader);
+        let mut extension_loaders = self.data.extension_loaders.write().unwrap();
+        for extension in A::extensions() {
+            extension_loaders.insert(extension, loader);
+        }
+    }
+
+    /// Registers `source` under `name`, so paths starting with `name://` are read from it.
+    /// Replaces any source already registered under that name.
+    pub fn register_source(&self, name: impl Into<String>, source: impl AssetSource) {
+        self.data
+            .sources
+            .write()
+            .unwrap()
+            .insert(name.into(), Arc::new(source));
+    }
+
+    /// Replaces the source that paths without a `name://` prefix are read from.
+    pub fn set_default_source(&self, source: impl AssetSource) {
+        self.data
+            .sources
+            .write()
+            .unwrap()
+            .set_default(Arc::new(source));
+    }
+
+    /// Sets the [`BakeCache`] that loaders look their assets up in before importing them.
+    pub fn set_bake_cache(&self, cache: BakeCache) {
+        *self.data.bake_cache.write().unwrap() = Some(Arc::new(cache));
+    }
+
+    pub fn bake_cache(&self) -> Option<Arc<BakeCache>> {
+        self.data.bake_cache.read().unwrap().clone()
+    }
+
+    /// Reads the file at `path` from its [`AssetSource`].
+    pub async fn read(&self, path: &AssetPath<'_>) -> anyhow::Result<Vec<u8>> {
+        let source = self.data.sources.read().unwrap().get(path.source())?;
+        source
+            .read(path.to_path())
+            .await
+            .with_context(|| format!("failed to read asset '{path}'"))
+    }
+
+    /// Reads the file at `path` from its [`AssetSource`] as UTF-8 text.
+    pub async fn read_to_string(&self, path: &AssetPath<'_>) -> anyhow::Result<String> {
+        let bytes = self.read(path).await?;
+        String::from_utf8(bytes).with_context(|| format!("asset '{path}' is not valid UTF-8"))
+    }
+
+    /// Where `path` lives on the local filesystem, if its [`AssetSource`] reads from one.
+    pub fn local_path(&self, path: &AssetPath<'_>) -> Option<PathBuf> {
+        let source = self.data.sources.read().unwrap().get(path.source()).ok()?;
+        source.local_path(path.to_path())
+    }
+
     pub fn load<'a, A>(&self, path: impl Into<AssetPath<'a>>) -> AssetHandle<A>
     where
         A: LoadableAsset + 'static,
@@ -104,11 +451,172 @@ impl AssetServer {
     }
 
     pub fn add<A: Asset>(&self, asset: A) -> AssetHandle<A> {
-        let id = AssetId::new();
+        self.add_with_id(AssetId::new(), asset)
+    }
+
+    /// Adds `asset` under `id`, e.g. one from [`AssetId::derived`], replacing any asset
+    /// already stored under it.
+    pub fn add_with_id<A: Asset>(&self, id: AssetId, asset: A) -> AssetHandle<A> {
+        self.add_with_path(id, asset, None)
+    }
 
+    /// Adds `asset` under `id`, with a handle saved as `path` if there is one.
+    fn add_with_path<A: Asset>(
+        &self,
+        id: AssetId,
+        asset: A,
+        path: Option<String>,
+    ) -> AssetHandle<A> {
+        self.data
+            .statuses
+            .write()
+            .unwrap()
+            .insert(id, AssetStatus::Loading);
         let sender = self.data.asset_load_event_sender.clone();
-        let _ = sender.send(AssetLoadEvent::Loaded(LoadedAsset::new(id, asset)));
-        self.data.handle_provider.request_handle(id, None)
+        let _ = sender.send(AssetLoadEvent::Loaded(LoadedAsset::new(
+            id,
+            asset,
+            Vec::new(),
+        )));
+        self.data.handle_provider.request_handle(id, path)
+    }
+
+    /// Like [`add`](Self::add), but returns the handle of an asset of the same type with the
+    /// same [`Fingerprint`] if one was added this way and is still alive, e.g. for the
+    /// identical default materials of several glTF files.
+    ///
+    /// The id is derived from the fingerprint, so it is the same in every run.
+    pub fn add_unique<A: Fingerprint>(&self, asset: A) -> AssetHandle<A> {
+        let mut fingerprint = FingerprintBytes::default();
+        asset.fingerprint(&mut fingerprint);
+        let hash = fingerprint.finish();
+        let key = AssetKey::Content(TypeId::of::<A>(), fingerprint.0.into_boxed_slice());
+
+        // Looked up and claimed under one lock, so two threads adding the same content agree
+        // on the id.
+        let (id, existing) = {
+            let mut keys = self.data.keys.write().unwrap();
+            match keys.ids.get(&key) {
+                Some(id) => (*id, true),
+                None => {
+                    let derived = AssetId::derived(&format!("{}#{hash:016x}", A::name()));
+                    // Different content with the same hash can't share the derived id.
+                    let id = if keys.keys.contains_key(&derived) {
+                        AssetId::new()
+                    } else {
+                        derived
+                    };
+                    keys.insert(key, id);
+                    (id, false)
+                }
+            }
+        };
+
+        if existing {
+            self.data.handle_provider.request_handle(id, None)
+        } else {
+            self.add_with_id(id, asset)
+        }
+    }
+
+    /// Turns a saved handle back into a live one.
+    ///
+    /// A plain path is loaded with default usage settings, which needs a loader registered
+    /// with [`register_loader`](Self::register_loader).  A `path#label` path resolves to the
+    /// sub-asset with that label, loading the file with the loader registered for its
+    /// extension unless the sub-asset is alive already.  A `path?settings` path or an id only
+    /// resolves to an asset that is alive, and is an error otherwise.
+    pub fn handle_from_serialized<A: Asset>(
+        &self,
+        serialized: &SerializedHandle,
+    ) -> anyhow::Result<AssetHandle<A>> {
+        let id = match serialized {
+            SerializedHandle::Path(path) => match path.split_once('#') {
+                Some((path, label)) => {
+                    let id = AssetId::derived(&format!("{}#{label}", AssetPath::new(path)));
+                    if !self.data.statuses.read().unwrap().contains_key(&id) {
+                        self.load_label_parent(path, label, id)?;
+                    }
+                    id
+                }
+                None => match path.rsplit_once('?') {
+                    Some((path, settings)) => {
+                        let settings = u64::from_str_radix(settings, 16)
+                            .with_context(|| format!("invalid usage settings hash '{settings}'"))?;
+                        let key = AssetKey::Path(AssetPath::new(path).into_owned(), settings);
+                        let id = self.data.keys.read().unwrap().ids.get(&key).copied();
+                        id.with_context(|| {
+                            format!(
+                                "'{path}' is not loaded with usage settings {settings:016x}, \
+                                 which are not saved with the handle"
+                            )
+                        })?
+                    }
+                    None => {
+                        let loader = self
+                            .data
+                            .path_loaders
+                            .read()
+                            .unwrap()
+                            .get(&TypeId::of::<A>())
+                            .copied()
+                            .with_context(|| {
+                                format!("no loader registered for {} assets", A::name())
+                            })?;
+                        return Ok(AssetHandle::new(loader(
+                            self,
+                            AssetPath::new(path).into_owned(),
+                        )));
+                    }
+                },
+            },
+            SerializedHandle::Id(id) => {
+                let id = id
+                    .parse()
+                    .with_context(|| format!("invalid asset id '{id}'"))?;
+                anyhow::ensure!(
+                    self.data.statuses.read().unwrap().contains_key(&id),
+                    "no asset with id {id} is alive"
+                );
+                id
+            }
+        };
+        Ok(self.data.handle_provider.request_handle(id, None))
+    }
+
+    /// Starts loading the file a saved `path#label` handle points into, holding it until it
+    /// has loaded so the sub-asset `id` gets added.
+    fn load_label_parent(&self, path: &str, label: &str, id: AssetId) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            !path.contains('?'),
+            "'{path}' is not loaded with the usage settings '{label}' was added with, \
+             which are not saved with the handle"
+        );
+        let path = AssetPath::new(path).into_owned();
+        let extension = path
+            .to_path()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let loader = self
+            .data
+            .extension_loaders
+            .read()
+            .unwrap()
+            .get(extension)
+            .copied()
+            .with_context(|| format!("no loader registered for '{path}'"))?;
+
+        let parent = loader(self, path.clone());
+        if self.data.statuses.read().unwrap().get(&parent.id) == Some(&AssetStatus::Loading) {
+            self.data.label_parents.write().unwrap().push(parent);
+        } else {
+            anyhow::ensure!(
+                self.data.statuses.read().unwrap().contains_key(&id),
+                "'{path}' has no sub-asset labeled '{label}'"
+            );
+        }
+        Ok(())
     }
 
     pub fn load_with_usage_settings<'a, A>(
@@ -129,28 +637,135 @@ impl AssetServer {
     ) -> AssetHandle<A> {
         let path = path.into().into_owned();
 
-        let id = match self.data.path_to_id.write().unwrap().entry(path.clone()) {
-            std::collections::hash_map::Entry::Occupied(occupied_entry) => *occupied_entry.get(),
-            std::collections::hash_map::Entry::Vacant(vacant_entry) => {
-                *vacant_entry.insert(AssetId::new())
+        let key = AssetKey::Path(path.clone(), hash_of(&usage_settings));
+        let serialized_path = load_scope::<A>(&path, &usage_settings);
+        let id = {
+            let mut keys = self.data.keys.write().unwrap();
+            match keys.ids.get(&key) {
+                Some(id) => *id,
+                None => {
+                    let id = AssetId::new();
+                    keys.insert(key, id);
+                    id
+                }
             }
         };
 
-        if !self.data.pending_tasks.read().unwrap().contains_key(&id)
-            && !self.data.loaded_assets.read().unwrap().contains(&id)
-        {
+        let own_status = self.data.statuses.read().unwrap().get(&id).cloned();
+        if !matches!(own_status, Some(AssetStatus::Loading | AssetStatus::Loaded)) {
+            let reload_path = path.clone();
+            let reload_settings = usage_settings.clone();
+            self.data.reloaders.write().unwrap().insert(
+                id,
+                Box::new(move |server| {
+                    server.request_load::<A>(reload_path.clone(), id, reload_settings.clone())
+                }),
+            );
             self.request_load::<A>(path.clone(), id, usage_settings);
         }
 
-        self.data.handle_provider.request_handle(id, Some(path))
+        self.data
+            .handle_provider
+            .request_handle(id, Some(serialized_path))
     }
 
-    pub fn process_handle_drop(&mut self, id: &AssetId, path: Option<AssetPath<'static>>) {
-        self.data.loaded_assets.write().unwrap().remove(id);
+    /// Loads the asset at `path` again, replacing it in its [`AssetStore`] under the same
+    /// [`AssetId`] once done, so existing handles see the new data.  An asset loaded from
+    /// `path` with several usage settings is reloaded with each of them.
+    ///
+    /// Returns `false` if nothing is loaded from `path`.
+    pub fn reload<'a>(&self, path: impl Into<AssetPath<'a>>) -> bool {
+        let path = path.into();
+        let ids = self.data.keys.read().unwrap().loaded_from(&path);
+        let reloaders = self.data.reloaders.read().unwrap();
 
-        if let Some(path) = path {
-            self.data.path_to_id.write().unwrap().remove(&path);
+        let mut reloaded = false;
+        for reloader in ids.iter().filter_map(|id| reloaders.get(id)) {
+            if !reloaded {
+                log::info!("Reloading asset '{path}'");
+            }
+            reloader(self);
+            reloaded = true;
         }
+        reloaded
+    }
+
+    /// Returns where the asset `id` is in its load, or `None` if the server does not know it
+    /// (it was never requested, or every handle to it was dropped).
+    ///
+    /// An asset only counts as [`Loaded`](AssetStatus::Loaded) once its dependencies,
+    /// recursively, are too, and as [`Failed`](AssetStatus::Failed) if any of them failed.
+    pub fn status(&self, id: AssetId) -> Option<AssetStatus> {
+        let statuses = self.data.statuses.read().unwrap();
+        let graph = self.data.dependencies.read().unwrap();
+
+        let own_status = statuses.get(&id)?;
+        if *own_status != AssetStatus::Loaded {
+            return Some(own_status.clone());
+        }
+
+        let mut loading = false;
+        let mut visited = HashSet::from([id]);
+        let mut stack: Vec<AssetId> = graph.dependencies.get(&id).cloned().unwrap_or_default();
+        while let Some(dependency) = stack.pop() {
+            if !visited.insert(dependency) {
+                continue;
+            }
+            match statuses.get(&dependency) {
+                Some(AssetStatus::Loaded) => {
+                    stack.extend(graph.dependencies.get(&dependency).into_iter().flatten());
+                }
+                Some(AssetStatus::Failed(error)) => {
+                    return Some(AssetStatus::Failed(format!("a dependency failed: {error}")));
+                }
+                Some(AssetStatus::Loading) => loading = true,
+                // Dropped, and so no longer needed.
+                None => {}
+            }
+        }
+
+        Some(if loading {
+            AssetStatus::Loading
+        } else {
+            AssetStatus::Loaded
+        })
+    }
+
+    /// Returns `true` once the asset `id` and, recursively, its dependencies are loaded.
+    pub fn is_loaded_with_dependencies(&self, id: AssetId) -> bool {
+        self.status(id) == Some(AssetStatus::Loaded)
+    }
+
+    /// Returns the assets the loader of `id` loaded or added through its [`AssetLoadContext`].
+    pub fn dependencies_of(&self, id: AssetId) -> Vec<AssetId> {
+        let graph = self.data.dependencies.read().unwrap();
+        graph.dependencies.get(&id).cloned().unwrap_or_default()
+    }
+
+    /// Returns the assets whose loader loaded or added `id`.
+    pub fn dependents_of(&self, id: AssetId) -> Vec<AssetId> {
+        let graph = self.data.dependencies.read().unwrap();
+        graph
+            .dependents
+            .get(&id)
+            .map(|parents| parents.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Forgets the asset `id` after its last handle was dropped.
+    ///
+    /// Returns `false`, and forgets nothing, if a new handle to it was requested since, e.g. by
+    /// loading the same path again; the asset is still in use then.
+    pub fn process_handle_drop(&mut self, id: &AssetId) -> bool {
+        if !self.data.handle_provider.forget_if_unused(*id) {
+            return false;
+        }
+        self.data.statuses.write().unwrap().remove(id);
+        self.data.dependencies.write().unwrap().remove(*id);
+        self.data.unnotified.write().unwrap().remove(id);
+        self.data.reloaders.write().unwrap().remove(id);
+        self.data.keys.write().unwrap().remove(*id);
+        true
     }
 
     fn request_load<A: LoadableAsset>(
@@ -164,29 +779,46 @@ impl AssetServer {
         let sender = self.data.asset_load_event_sender.clone();
 
         let server = self.clone();
+        let scope = load_scope::<A>(&path, &usage_settings);
+        self.data
+            .statuses
+            .write()
+            .unwrap()
+            .insert(id, AssetStatus::Loading);
         // No profiling scope around the async body: a scope guard must not be
         // held across .await (tasks can migrate between worker threads).
         // Load costs show up on the named "asset-load-N" threads instead.
         let task =
             LoadTaskPool::get_or_init(|| TaskPool::with_name("asset-load")).spawn(async move {
                 let log_path = path.clone();
+                let mut load_context = AssetLoadContext::new(server, scope);
                 let asset = asset_loader
-                    .load(path, &mut AssetLoadContext::new(server), usage_settings)
+                    .load(path, &mut load_context, usage_settings)
                     .await;
                 match asset {
                     Ok(asset) => {
                         sender
-                            .send(AssetLoadEvent::Loaded(LoadedAsset::new(id, asset)))
+                            .send(AssetLoadEvent::Loaded(LoadedAsset::new(
+                                id,
+                                asset,
+                                load_context.dependencies,
+                            )))
                             .unwrap();
                     }
                     Err(error) => {
                         log::error!(
                             "Failed to load asset '{}' (type {}): {:#}",
-                            log_path.to_path().display(),
+                            log_path,
                             std::any::type_name::<A>(),
                             error
                         );
-                        sender.send(AssetLoadEvent::LoadFailed(id)).unwrap();
+                        sender
+                            .send(AssetLoadEvent::LoadFailed(FailedAsset {
+                                id,
+                                error: format!("{error:#}"),
+                                notifier: AssetNotifier::of::<A>(),
+                            }))
+                            .unwrap();
                     }
                 }
             });
@@ -201,6 +833,9 @@ impl Default for AssetServer {
     }
 }
 
+/// Moves finished loads into their [`AssetStore`]s and updates their [`AssetStatus`].  Sends
+/// [`AssetLoaded`](super::status::AssetLoaded) or [`AssetFailed`](super::status::AssetFailed)
+/// for every asset that, along with its dependencies, finished loading.
 // TODO: This shouldn't need to be public
 pub fn handle_asset_load_events(world: &mut world::World) {
     let server = world.remove_resource::<AssetServer>().unwrap();
@@ -219,17 +854,76 @@ pub fn handle_asset_load_events(world: &mut world::World) {
                     .remove(&loaded_asset.id);
                 server
                     .data
-                    .loaded_assets
+                    .statuses
+                    .write()
+                    .unwrap()
+                    .insert(loaded_asset.id, AssetStatus::Loaded);
+                server
+                    .data
+                    .dependencies
+                    .write()
+                    .unwrap()
+                    .set(loaded_asset.id, loaded_asset.dependencies);
+                server
+                    .data
+                    .unnotified
                     .write()
                     .unwrap()
-                    .insert(loaded_asset.id);
+                    .insert(loaded_asset.id, loaded_asset.notifier);
                 loaded_asset.value.insert(loaded_asset.id, world);
             }
-            AssetLoadEvent::LoadFailed(id) => {
-                server.data.pending_tasks.write().unwrap().remove(&id);
-                server.data.loaded_assets.write().unwrap().remove(&id);
+            AssetLoadEvent::LoadFailed(failed_asset) => {
+                server
+                    .data
+                    .pending_tasks
+                    .write()
+                    .unwrap()
+                    .remove(&failed_asset.id);
+                server
+                    .data
+                    .statuses
+                    .write()
+                    .unwrap()
+                    .insert(failed_asset.id, AssetStatus::Failed(failed_asset.error));
+                server
+                    .data
+                    .unnotified
+                    .write()
+                    .unwrap()
+                    .insert(failed_asset.id, failed_asset.notifier);
+            }
+        });
+
+    // Files loaded for saved `path#label` handles have added their sub-assets once their own
+    // load is over; only the sub-asset handles need to keep them alive from here on.
+    let statuses = server.data.statuses.read().unwrap();
+    server
+        .data
+        .label_parents
+        .write()
+        .unwrap()
+        .retain(|parent| statuses.get(&parent.id) == Some(&AssetStatus::Loading));
+    drop(statuses);
+
+    // Parents wait here until their last dependency is in.
+    server
+        .data
+        .unnotified
+        .write()
+        .unwrap()
+        .retain(|&id, notifier| match server.status(id) {
+            Some(AssetStatus::Loading) => true,
+            Some(AssetStatus::Loaded) => {
+                (notifier.send_loaded)(world, id);
+                false
+            }
+            Some(AssetStatus::Failed(error)) => {
+                (notifier.send_failed)(world, id, error);
+                false
             }
+            None => false,
         });
+
     world.insert_resource(server);
 }
 
@@ -254,11 +948,7 @@ impl AssetHandleProvider {
             .insert(type_id, lifetime_sender);
     }
 
-    pub fn request_handle<A: Asset>(
-        &self,
-        id: AssetId,
-        path: Option<AssetPath<'static>>,
-    ) -> AssetHandle<A> {
+    pub fn request_handle<A: Asset>(&self, id: AssetId, path: Option<String>) -> AssetHandle<A> {
         let lifetime_sender = self
             .asset_lifetime_send_map
             .read()
@@ -287,4 +977,538 @@ impl AssetHandleProvider {
             AssetHandle::new(handle)
         }
     }
+
+    /// Removes the entry of `id` unless a live handle to it exists, in which case it returns
+    /// `false`.  Checked under the same lock [`request_handle`](Self::request_handle) takes, so
+    /// no handle can be handed out in between.
+    fn forget_if_unused(&self, id: AssetId) -> bool {
+        let mut handles = self.asset_handles.write().unwrap();
+        if handles
+            .get(&id)
+            .is_some_and(|info| info.handle.strong_count() > 0)
+        {
+            return false;
+        }
+        handles.remove(&id);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use ecs::{
+        events::{
+            event_channel::EventChannel, event_reader::EventReader, event_writer::EventWriter,
+        },
+        scene::Scene,
+        system::executor::single_thread::SingleThreadedExecutor,
+        Component, IntoSystemConfig, ResMut, Schedule,
+    };
+    use facet::Facet;
+
+    use super::*;
+    use crate::assets::{
+        asset_loader::AssetLoader,
+        asset_store::AssetStore,
+        handle::AssetLifetimeEvent,
+        status::{AssetFailed, AssetLoaded},
+    };
+
+    struct Part;
+
+    impl Asset for Part {
+        fn name() -> &'static str {
+            "Part"
+        }
+    }
+
+    /// Content told apart by [`AssetServer::add_unique`].
+    struct Tag(&'static str);
+
+    impl Asset for Tag {
+        fn name() -> &'static str {
+            "Tag"
+        }
+    }
+
+    impl Fingerprint for Tag {
+        fn fingerprint<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state);
+        }
+    }
+
+    /// Loads from any path containing "good", adding a [`Part`] as a dependency.  A path
+    /// starting with "parent." instead loads the rest of the path as a child bundle.
+    // The handles are only held to keep the sub-assets alive.
+    #[allow(dead_code)]
+    enum Bundle {
+        Leaf(AssetHandle<Part>),
+        Parent(AssetHandle<Bundle>),
+    }
+
+    impl Asset for Bundle {
+        fn name() -> &'static str {
+            "Bundle"
+        }
+    }
+
+    impl LoadableAsset for Bundle {
+        /// Unused by the loader; only tells loads of the same path apart.
+        type UsageSettings = u32;
+
+        fn loader() -> Box<dyn AssetLoader<Asset = Self>> {
+            Box::new(BundleLoader)
+        }
+
+        fn default_usage_settings() -> Self::UsageSettings {
+            0
+        }
+
+        fn extensions() -> &'static [&'static str] {
+            &["bundle"]
+        }
+    }
+
+    struct BundleLoader;
+
+    #[async_trait]
+    impl AssetLoader for BundleLoader {
+        type Asset = Bundle;
+
+        async fn load(
+            &self,
+            path: AssetPath<'static>,
+            load_context: &mut AssetLoadContext,
+            _usage_setting: u32,
+        ) -> anyhow::Result<Bundle> {
+            let file_name = path.to_path().file_name().unwrap().to_string_lossy();
+            if let Some(child) = file_name.strip_prefix("parent.") {
+                return Ok(Bundle::Parent(load_context.load(child.to_string())));
+            }
+
+            anyhow::ensure!(file_name.contains("good"), "not a good bundle");
+            Ok(Bundle::Leaf(load_context.add_labeled("part", Part)))
+        }
+    }
+
+    fn setup() -> (World, AssetServer) {
+        let mut world = World::new();
+        let mut server = AssetServer::new();
+
+        let parts = AssetStore::<Part>::new();
+        server.register_asset(&parts);
+        world.insert_resource(parts);
+        world.insert_resource(EventChannel::<AssetLoaded<Part>>::new());
+
+        let bundles = AssetStore::<Bundle>::new();
+        server.register_asset(&bundles);
+        world.insert_resource(bundles);
+        world.insert_resource(EventChannel::<AssetLoaded<Bundle>>::new());
+        world.insert_resource(EventChannel::<AssetFailed<Bundle>>::new());
+
+        let tags = AssetStore::<Tag>::new();
+        server.register_asset(&tags);
+        world.insert_resource(tags);
+
+        world.insert_resource(server.clone());
+        (world, server)
+    }
+
+    #[derive(Resource, Default)]
+    struct Seen(Vec<AssetId>);
+
+    /// Runs `system` once and returns the ids it pushed to [`Seen`].
+    fn seen<M>(world: &mut World, system: impl IntoSystemConfig<M> + 'static) -> Vec<AssetId> {
+        world.insert_resource(Seen::default());
+        let mut schedule = Schedule::new();
+        schedule.add_system(system);
+        schedule.compile::<SingleThreadedExecutor>().run(world);
+        world.remove_resource::<Seen>().unwrap().0
+    }
+
+    /// Handles load events until the asset `id` is no longer loading.
+    fn wait_for(world: &mut World, server: &AssetServer, id: AssetId) -> AssetStatus {
+        for _ in 0..500 {
+            handle_asset_load_events(world);
+            match server.status(id) {
+                Some(AssetStatus::Loading) => std::thread::sleep(Duration::from_millis(10)),
+                Some(status) => return status,
+                None => panic!("asset {id:?} unknown to the server"),
+            }
+        }
+        panic!("asset {id:?} still loading");
+    }
+
+    #[test]
+    fn added_asset_is_loading_until_its_event_is_handled() {
+        let (mut world, server) = setup();
+
+        let handle = server.add(Part);
+        assert_eq!(server.status(handle.id()), Some(AssetStatus::Loading));
+
+        handle_asset_load_events(&mut world);
+        assert_eq!(server.status(handle.id()), Some(AssetStatus::Loaded));
+
+        let loaded = seen(
+            &mut world,
+            |mut events: EventReader<AssetLoaded<Part>>, mut seen: ResMut<Seen>| {
+                seen.0.extend(events.read().map(|event| event.id))
+            },
+        );
+        assert_eq!(loaded, [handle.id()]);
+    }
+
+    #[test]
+    fn failed_load_reports_its_error() {
+        let (mut world, server) = setup();
+
+        let handle = server.load::<Bundle>("bad.bundle");
+        let status = wait_for(&mut world, &server, handle.id());
+        assert!(
+            matches!(status, AssetStatus::Failed(error) if error.contains("not a good bundle"))
+        );
+
+        let failed = seen(
+            &mut world,
+            |mut events: EventReader<AssetFailed<Bundle>>, mut seen: ResMut<Seen>| {
+                seen.0.extend(events.read().map(|event| event.id))
+            },
+        );
+        assert_eq!(failed, [handle.id()]);
+    }
+
+    #[test]
+    fn dependencies_are_loaded_with_their_parent() {
+        let (mut world, server) = setup();
+
+        let handle = server.load::<Bundle>("good.bundle");
+        assert!(!server.is_loaded_with_dependencies(handle.id()));
+
+        assert_eq!(
+            wait_for(&mut world, &server, handle.id()),
+            AssetStatus::Loaded
+        );
+        assert!(server.is_loaded_with_dependencies(handle.id()));
+    }
+
+    #[test]
+    fn reload_replaces_the_asset_under_the_same_id() {
+        let (mut world, server) = setup();
+
+        let handle = server.load::<Bundle>("good.bundle");
+        wait_for(&mut world, &server, handle.id());
+        assert!(!server.reload("other.bundle"));
+
+        assert!(server.reload("good.bundle"));
+        assert_eq!(server.status(handle.id()), Some(AssetStatus::Loading));
+        assert_eq!(
+            wait_for(&mut world, &server, handle.id()),
+            AssetStatus::Loaded
+        );
+
+        let loaded = seen(
+            &mut world,
+            |mut events: EventReader<AssetLoaded<Bundle>>, mut seen: ResMut<Seen>| {
+                seen.0.extend(events.read().map(|event| event.id))
+            },
+        );
+        assert_eq!(loaded, [handle.id(), handle.id()]);
+        assert!(world
+            .get_resource::<AssetStore<Bundle>>()
+            .unwrap()
+            .get(&handle)
+            .is_some());
+    }
+
+    #[test]
+    fn path_loads_are_shared_only_with_the_same_usage_settings() {
+        let (mut world, server) = setup();
+
+        let default = server.load::<Bundle>("good.bundle");
+        let variant = server.load_with_usage_settings::<Bundle>("good.bundle", 1);
+        assert_eq!(server.load::<Bundle>("good.bundle").id(), default.id());
+        assert_eq!(
+            server
+                .load_with_usage_settings::<Bundle>("good.bundle", 1)
+                .id(),
+            variant.id()
+        );
+        assert_ne!(default.id(), variant.id());
+
+        wait_for(&mut world, &server, default.id());
+        wait_for(&mut world, &server, variant.id());
+        assert!(server.reload("good.bundle"));
+        assert_eq!(server.status(default.id()), Some(AssetStatus::Loading));
+        assert_eq!(server.status(variant.id()), Some(AssetStatus::Loading));
+        assert_eq!(
+            wait_for(&mut world, &server, variant.id()),
+            AssetStatus::Loaded
+        );
+    }
+
+    #[test]
+    fn add_unique_shares_assets_with_the_same_fingerprint() {
+        let (mut world, server) = setup();
+
+        let first = server.add_unique(Tag("red"));
+        let same = server.add_unique(Tag("red"));
+        let other = server.add_unique(Tag("blue"));
+        assert_eq!(first.id(), same.id());
+        assert_ne!(first.id(), other.id());
+        // Plain `add` never deduplicates.
+        assert_ne!(server.add(Tag("red")).id(), first.id());
+
+        handle_asset_load_events(&mut world);
+        assert_eq!(server.status(same.id()), Some(AssetStatus::Loaded));
+    }
+
+    #[test]
+    fn add_unique_from_several_threads_agrees_on_one_asset() {
+        let (_world, server) = setup();
+
+        let handles: Vec<_> = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| server.add_unique(Tag("red"))))
+                .collect();
+            threads.into_iter().map(|t| t.join().unwrap()).collect()
+        });
+        assert!(handles.iter().all(|handle| handle.id() == handles[0].id()));
+    }
+
+    /// Runs [`AssetStore::track_assets`] for [`Tag`]s once.
+    fn track_tags(world: &mut World) {
+        let mut schedule = Schedule::new();
+        schedule.add_system(
+            |mut tags: ResMut<AssetStore<Tag>>,
+             server: ResMut<AssetServer>,
+             events: EventWriter<AssetLifetimeEvent>| {
+                tags.track_assets(server, events)
+            },
+        );
+        schedule.compile::<SingleThreadedExecutor>().run(world);
+    }
+
+    #[test]
+    fn an_asset_handed_out_again_before_its_drop_is_tracked_survives() {
+        let (mut world, server) = setup();
+        world.insert_resource(EventChannel::<AssetLifetimeEvent>::new());
+
+        let handle = server.add_unique(Tag("red"));
+        let id = handle.id();
+        handle_asset_load_events(&mut world);
+
+        drop(handle);
+        let again = server.add_unique(Tag("red"));
+        assert_eq!(again.id(), id);
+        track_tags(&mut world);
+        assert!(world
+            .get_resource::<AssetStore<Tag>>()
+            .unwrap()
+            .get(&again)
+            .is_some());
+        assert_eq!(server.status(id), Some(AssetStatus::Loaded));
+
+        drop(again);
+        track_tags(&mut world);
+        assert!(world
+            .get_resource::<AssetStore<Tag>>()
+            .unwrap()
+            .into_iter()
+            .next()
+            .is_none());
+        assert_eq!(server.status(id), None);
+    }
+
+    #[test]
+    fn labeled_sub_assets_get_ids_derived_from_their_parent() {
+        let (mut world, server) = setup();
+
+        let default = server.load::<Bundle>("good.bundle");
+        let variant = server.load_with_usage_settings::<Bundle>("good.bundle", 1);
+        wait_for(&mut world, &server, default.id());
+        wait_for(&mut world, &server, variant.id());
+
+        let part = server.dependencies_of(default.id())[0];
+        assert_eq!(part, AssetId::derived("res/good.bundle#part"));
+        assert_ne!(server.dependencies_of(variant.id())[0], part);
+
+        let serialized = SerializedHandle::Path("good.bundle#part".to_string());
+        let handle = server.handle_from_serialized::<Part>(&serialized).unwrap();
+        assert_eq!(handle.id(), part);
+
+        // A reload adds the part again under the same id.
+        server.reload("good.bundle");
+        wait_for(&mut world, &server, default.id());
+        assert_eq!(server.dependencies_of(default.id()), [part]);
+    }
+
+    #[test]
+    fn serialized_handles_keep_their_usage_settings_and_labels() {
+        let (mut world, server) = setup();
+
+        let variant = server.load_with_usage_settings::<Bundle>("good.bundle", 1);
+        wait_for(&mut world, &server, variant.id());
+        let path = format!("res/good.bundle?{:016x}", hash_of(&1u32));
+        assert_eq!(
+            variant.to_serialized(),
+            SerializedHandle::Path(path.clone())
+        );
+        let resolved = server
+            .handle_from_serialized::<Bundle>(&SerializedHandle::Path(path.clone()))
+            .unwrap();
+        assert_eq!(resolved.id(), variant.id());
+
+        // Sub-assets are saved by label, so they resolve before the file is loaded again.
+        let part = server.dependencies_of(variant.id())[0];
+        let part = server
+            .handle_from_serialized::<Part>(&SerializedHandle::Id(part.to_string()))
+            .unwrap();
+        assert_eq!(
+            part.to_serialized(),
+            SerializedHandle::Path(format!("{path}#part"))
+        );
+    }
+
+    #[test]
+    fn labeled_serialized_handles_load_their_file() {
+        let (mut world, server) = setup();
+        let saved = SerializedHandle::Path("good.bundle#part".to_string());
+        assert!(server.handle_from_serialized::<Part>(&saved).is_err());
+
+        server.register_loader::<Bundle>();
+        let part = server.handle_from_serialized::<Part>(&saved).unwrap();
+        assert_eq!(part.id(), AssetId::derived("res/good.bundle#part"));
+        assert_eq!(
+            wait_for(&mut world, &server, part.id()),
+            AssetStatus::Loaded
+        );
+        assert!(server.data.label_parents.read().unwrap().is_empty());
+
+        let missing = SerializedHandle::Path("good.bundle#missing".to_string());
+        assert!(server.handle_from_serialized::<Part>(&missing).is_err());
+    }
+
+    #[test]
+    fn serialized_handles_to_dead_assets_are_errors() {
+        let (_world, server) = setup();
+
+        let unknown = SerializedHandle::Id(AssetId::new().to_string());
+        assert!(server.handle_from_serialized::<Part>(&unknown).is_err());
+
+        let not_loaded = SerializedHandle::Path("good.bundle?0000000000000001".to_string());
+        assert!(server
+            .handle_from_serialized::<Bundle>(&not_loaded)
+            .is_err());
+    }
+
+    #[test]
+    fn handles_survive_a_scene_round_trip() {
+        #[derive(Component, Facet)]
+        struct Uses {
+            bundle: AssetHandle<Bundle>,
+            tag: AssetHandle<Tag>,
+        }
+
+        let (mut world, server) = setup();
+        server.register_loader::<Bundle>();
+        world.register_reflection::<Uses>();
+
+        let bundle = server.load::<Bundle>("good.bundle");
+        let tag = server.add(Tag("red"));
+        world.spawn(Uses {
+            bundle: bundle.clone(),
+            tag: tag.clone(),
+        });
+
+        let json = Scene::from_world(&world).to_json().unwrap();
+        assert!(json.contains(r#"{"Path":"res/good.bundle"}"#), "{json}");
+        assert!(json.contains(&tag.id().to_string()), "{json}");
+
+        let mut loaded = world;
+        let spawned: Vec<_> = Scene::from_json(&json)
+            .unwrap()
+            .spawn_into(&mut loaded)
+            .into_values()
+            .collect();
+        let uses = loaded.get_component_for_entity::<Uses>(spawned[0]).unwrap();
+        assert_eq!(uses.bundle.id(), bundle.id());
+        assert_eq!(uses.tag.id(), tag.id());
+    }
+
+    #[test]
+    fn parent_only_counts_as_loaded_with_its_dependencies() {
+        let (_world, server) = setup();
+        let (parent, child) = (AssetId::new(), AssetId::new());
+
+        let mut statuses = server.data.statuses.write().unwrap();
+        statuses.insert(parent, AssetStatus::Loaded);
+        statuses.insert(child, AssetStatus::Loading);
+        drop(statuses);
+        server
+            .data
+            .dependencies
+            .write()
+            .unwrap()
+            .set(parent, vec![child]);
+
+        assert_eq!(server.status(parent), Some(AssetStatus::Loading));
+        assert_eq!(server.dependencies_of(parent), [child]);
+        assert_eq!(server.dependents_of(child), [parent]);
+
+        server
+            .data
+            .statuses
+            .write()
+            .unwrap()
+            .insert(child, AssetStatus::Loaded);
+        assert_eq!(server.status(parent), Some(AssetStatus::Loaded));
+    }
+
+    #[test]
+    fn loader_records_loaded_sub_assets_as_dependencies() {
+        let (mut world, server) = setup();
+
+        let parent = server.load::<Bundle>("parent.good.bundle");
+        assert_eq!(
+            wait_for(&mut world, &server, parent.id()),
+            AssetStatus::Loaded
+        );
+
+        let [child] = server.dependencies_of(parent.id())[..] else {
+            panic!("expected exactly one dependency");
+        };
+        assert_eq!(server.dependents_of(child), [parent.id()]);
+        assert_eq!(server.dependencies_of(child).len(), 1);
+
+        let loaded = seen(
+            &mut world,
+            |mut events: EventReader<AssetLoaded<Bundle>>, mut seen: ResMut<Seen>| {
+                seen.0.extend(events.read().map(|event| event.id))
+            },
+        );
+        assert!(loaded.contains(&parent.id()) && loaded.contains(&child));
+    }
+
+    #[test]
+    fn failed_dependency_fails_its_parent() {
+        let (mut world, server) = setup();
+
+        let parent = server.load::<Bundle>("parent.bad.bundle");
+        let status = wait_for(&mut world, &server, parent.id());
+        assert!(
+            matches!(status, AssetStatus::Failed(error) if error.contains("not a good bundle"))
+        );
+
+        let failed = seen(
+            &mut world,
+            |mut events: EventReader<AssetFailed<Bundle>>, mut seen: ResMut<Seen>| {
+                seen.0.extend(events.read().map(|event| event.id))
+            },
+        );
+        assert_eq!(failed.len(), 2);
+        assert!(failed.contains(&parent.id()));
+    }
 }
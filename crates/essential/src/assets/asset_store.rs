@@ -64,15 +64,20 @@ impl<A: Asset + 'static> AssetStore<A> {
         mut events: EventWriter<AssetLifetimeEvent>,
     ) {
         for event in self.drop_receiver.try_iter() {
-            if self.assets.contains_key(&event.id()) {
-                events.write(event.clone());
-                match event {
-                    AssetLifetimeEvent::Dropped(id, asset_path) => {
-                        self.assets.remove(&id);
-                        asset_server.process_handle_drop(&id, asset_path);
+            if !self.assets.contains_key(&event.id()) {
+                continue;
+            }
+            match event {
+                AssetLifetimeEvent::Dropped(id) => {
+                    // The last handle may be gone while a new one to the same id was already
+                    // handed out, e.g. by loading the same path again.
+                    if !asset_server.process_handle_drop(&id) {
+                        continue;
                     }
+                    self.assets.remove(&id);
                 }
             }
+            events.write(event);
         }
     }
 
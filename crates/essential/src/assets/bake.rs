@@ -0,0 +1,380 @@
+//! Cache of assets already run through their importer, stored in a compact binary form.
+//!
+//! A loader that supports baking looks its asset up with
+//! [`AssetLoadContext::baked`](super::asset_server::AssetLoadContext::baked) before importing
+//! it, and stores the result with
+//! [`AssetLoadContext::bake`](super::asset_server::AssetLoadContext::bake) afterwards.  A baked
+//! file is only used while its [`BakeKey`] still matches: same source bytes, same usage
+//! settings, same engine version and bake format.  Anything else falls back to the importer.
+
+use std::{
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, ensure, Context};
+
+use super::{Asset, AssetPath};
+
+/// Bumped whenever the layout written by a [`Bake`] implementation changes.
+pub const BAKE_FORMAT_VERSION: u32 = 1;
+
+const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+const MAGIC: &[u8; 4] = b"BAKE";
+
+/// An asset that can be written to and restored from the [`BakeCache`].
+pub trait Bake: Asset + Sized {
+    fn bake(&self, writer: &mut BakeWriter) -> anyhow::Result<()>;
+
+    fn unbake(reader: &mut BakeReader<'_>) -> anyhow::Result<Self>;
+}
+
+/// Writes the little-endian payload of a baked asset.
+#[derive(Default)]
+pub struct BakeWriter {
+    bytes: Vec<u8>,
+}
+
+impl BakeWriter {
+    pub fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_f32(&mut self, value: f32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Writes a length-prefixed byte string.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_u64(bytes.len() as u64);
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    pub fn write_str(&mut self, value: &str) {
+        self.write_bytes(value.as_bytes());
+    }
+
+    /// Writes a length-prefixed slice of plain data, e.g. GPU-ready vertices.
+    pub fn write_slice<T: bytemuck::Pod>(&mut self, values: &[T]) {
+        self.write_u64(values.len() as u64);
+        self.bytes.extend_from_slice(bytemuck::cast_slice(values));
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Reads back what a [`BakeWriter`] wrote, failing instead of panicking on truncated data.
+pub struct BakeReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> BakeReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        ensure!(self.bytes.len() >= len, "baked data is truncated");
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    pub fn read_u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    pub fn read_u64(&mut self) -> anyhow::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    pub fn read_f32(&mut self) -> anyhow::Result<f32> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_len(&mut self) -> anyhow::Result<usize> {
+        usize::try_from(self.read_u64()?).context("baked length does not fit in memory")
+    }
+
+    pub fn read_bytes(&mut self) -> anyhow::Result<&'a [u8]> {
+        let len = self.read_len()?;
+        self.take(len)
+    }
+
+    pub fn read_str(&mut self) -> anyhow::Result<&'a str> {
+        std::str::from_utf8(self.read_bytes()?).context("baked string is not valid UTF-8")
+    }
+
+    pub fn read_vec<T: bytemuck::Pod>(&mut self) -> anyhow::Result<Vec<T>> {
+        let len = self.read_len()?;
+        let size = len
+            .checked_mul(size_of::<T>())
+            .context("baked length does not fit in memory")?;
+        // Copied rather than cast, as the bytes need not be aligned for `T`.
+        let bytes = self.take(size)?;
+        let mut values = vec![T::zeroed(); len];
+        bytemuck::cast_slice_mut(&mut values).copy_from_slice(bytes);
+        Ok(values)
+    }
+}
+
+/// FNV-1a, used for the hashes in a [`BakeKey`] because it is stable across builds and
+/// platforms, unlike [`std::collections::hash_map::DefaultHasher`].
+#[derive(Clone, Copy)]
+pub struct ContentHasher(u64);
+
+impl Default for ContentHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for ContentHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Returns the [`ContentHasher`] hash of `bytes`.
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = ContentHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Identifies one baked asset and the inputs it was baked from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BakeKey {
+    path: String,
+    item: String,
+    source_hash: u64,
+    settings_hash: u64,
+}
+
+impl BakeKey {
+    /// Key for the asset imported from `path`, whose source bytes hash to `source_hash` (see
+    /// [`content_hash`]).
+    pub fn new(path: &AssetPath<'_>, source_hash: u64) -> Self {
+        Self {
+            path: path.to_string(),
+            item: String::new(),
+            source_hash,
+            settings_hash: 0,
+        }
+    }
+
+    /// Names one of several assets imported from the same file, e.g. `mesh/0/1`.
+    pub fn item(&self, item: impl Into<String>) -> Self {
+        Self {
+            item: item.into(),
+            ..self.clone()
+        }
+    }
+
+    /// Includes the usage settings that change the baked result.
+    pub fn with_settings(mut self, settings: impl Hash) -> Self {
+        let mut hasher = ContentHasher::default();
+        settings.hash(&mut hasher);
+        self.settings_hash = hasher.finish();
+        self
+    }
+
+    fn file_name<A: Asset>(&self) -> String {
+        let mut hasher = ContentHasher::default();
+        hasher.write(self.path.as_bytes());
+        hasher.write(b"#");
+        hasher.write(self.item.as_bytes());
+        format!("{:016x}.{}.baked", hasher.finish(), A::name())
+    }
+
+    fn write_header<A: Asset>(&self, writer: &mut BakeWriter) {
+        writer.bytes.extend_from_slice(MAGIC);
+        writer.write_u32(BAKE_FORMAT_VERSION);
+        writer.write_str(ENGINE_VERSION);
+        writer.write_str(A::name());
+        writer.write_str(&self.path);
+        writer.write_str(&self.item);
+        writer.write_u64(self.source_hash);
+        writer.write_u64(self.settings_hash);
+    }
+
+    /// Returns `false` if the header was written for another key, engine or format version.
+    fn matches_header<A: Asset>(&self, reader: &mut BakeReader<'_>) -> anyhow::Result<bool> {
+        if reader.take(MAGIC.len())? != MAGIC {
+            bail!("not a baked asset");
+        }
+        Ok(reader.read_u32()? == BAKE_FORMAT_VERSION
+            && reader.read_str()? == ENGINE_VERSION
+            && reader.read_str()? == A::name()
+            && reader.read_str()? == self.path
+            && reader.read_str()? == self.item
+            && reader.read_u64()? == self.source_hash
+            && reader.read_u64()? == self.settings_hash)
+    }
+}
+
+/// A directory of baked assets, usually `bake-cache` next to `res`.
+pub struct BakeCache {
+    root: PathBuf,
+    writable: bool,
+}
+
+impl BakeCache {
+    /// A cache in `root` that loaders also write missing and stale bakes to.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            writable: true,
+        }
+    }
+
+    /// A cache in `root` that is only read from, e.g. one shipped with a build.
+    pub fn read_only(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            writable: false,
+        }
+    }
+
+    /// Returns the `bake-cache` directory next to the executable, if there is one.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn find() -> Option<Self> {
+        let root = std::env::current_exe().ok()?.parent()?.join("bake-cache");
+        root.is_dir().then(|| Self::read_only(root))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+
+    /// Returns the asset baked under `key`, or `None` if there is none or it is stale.
+    pub fn get<A: Bake>(&self, key: &BakeKey) -> anyhow::Result<Option<A>> {
+        let path = self.root.join(key.file_name::<A>());
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read '{}'", path.display()))
+            }
+        };
+
+        let mut reader = BakeReader::new(&bytes);
+        if !key.matches_header::<A>(&mut reader)? {
+            return Ok(None);
+        }
+        A::unbake(&mut reader)
+            .map(Some)
+            .with_context(|| format!("failed to restore baked asset '{}'", path.display()))
+    }
+
+    /// Stores `asset` under `key`.  Does nothing for a read-only cache.
+    pub fn insert<A: Bake>(&self, key: &BakeKey, asset: &A) -> anyhow::Result<()> {
+        if !self.writable {
+            return Ok(());
+        }
+
+        let mut writer = BakeWriter::default();
+        key.write_header::<A>(&mut writer);
+        asset.bake(&mut writer)?;
+
+        std::fs::create_dir_all(&self.root)
+            .with_context(|| format!("failed to create '{}'", self.root.display()))?;
+        let path = self.root.join(key.file_name::<A>());
+        std::fs::write(&path, writer.into_bytes())
+            .with_context(|| format!("failed to write '{}'", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Samples(Vec<[f32; 2]>);
+
+    impl Asset for Samples {
+        fn name() -> &'static str {
+            "Samples"
+        }
+    }
+
+    impl Bake for Samples {
+        fn bake(&self, writer: &mut BakeWriter) -> anyhow::Result<()> {
+            writer.write_slice(&self.0);
+            Ok(())
+        }
+
+        fn unbake(reader: &mut BakeReader<'_>) -> anyhow::Result<Self> {
+            Ok(Self(reader.read_vec()?))
+        }
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bake-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn baked_asset_is_restored_while_its_key_matches() {
+        let dir = cache_dir("roundtrip");
+        let cache = BakeCache::new(&dir);
+        let path = AssetPath::new("samples.bin");
+        let key = BakeKey::new(&path, content_hash(b"source")).with_settings(2u32);
+        let samples = Samples(vec![[1.0, 2.0], [3.0, 4.0]]);
+
+        assert_eq!(cache.get::<Samples>(&key).unwrap(), None);
+        cache.insert(&key, &samples).unwrap();
+        assert_eq!(cache.get::<Samples>(&key).unwrap(), Some(samples));
+
+        let edited = BakeKey::new(&path, content_hash(b"edited")).with_settings(2u32);
+        assert_eq!(cache.get::<Samples>(&edited).unwrap(), None);
+        assert_eq!(
+            cache
+                .get::<Samples>(&key.clone().with_settings(3u32))
+                .unwrap(),
+            None
+        );
+        assert_eq!(cache.get::<Samples>(&key.item("other")).unwrap(), None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn read_only_cache_does_not_write() {
+        let dir = cache_dir("read-only");
+        let key = BakeKey::new(&AssetPath::new("samples.bin"), 0);
+
+        BakeCache::read_only(&dir)
+            .insert(&key, &Samples(vec![[0.0; 2]]))
+            .unwrap();
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn truncated_bake_is_an_error() {
+        let mut writer = BakeWriter::default();
+        writer.write_slice(&[[1.0f32, 2.0]]);
+        let bytes = writer.into_bytes();
+
+        let mut reader = BakeReader::new(&bytes[..bytes.len() - 1]);
+        assert!(Samples::unbake(&mut reader).is_err());
+    }
+}
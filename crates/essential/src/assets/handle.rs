@@ -1,36 +1,36 @@
 use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 
-use crate::assets::AssetPath;
-
-use super::{Asset, AssetId};
+use super::{asset_server::AssetServer, Asset, AssetId};
 use crossbeam_channel::Sender;
-use ecs::Event;
+use ecs::{component::reflection::with_deserializing_world, Event};
+use facet::Facet;
 
 #[derive(Clone, Event)]
 pub enum AssetLifetimeEvent {
-    Dropped(AssetId, Option<AssetPath<'static>>),
+    Dropped(AssetId),
 }
 
 impl AssetLifetimeEvent {
     pub fn id(&self) -> AssetId {
         match self {
-            AssetLifetimeEvent::Dropped(id, _) => *id,
+            AssetLifetimeEvent::Dropped(id) => *id,
         }
     }
 }
 
 pub struct StrongAssetHandle {
     pub(crate) id: AssetId,
-    pub(crate) path: Option<AssetPath<'static>>,
+    /// What [`SerializedHandle::Path`] to save the handle as, if the asset can be found again
+    /// from its path.
+    pub(crate) path: Option<String>,
     pub(crate) lifetime_sender: Sender<AssetLifetimeEvent>,
 }
 
 impl Drop for StrongAssetHandle {
     fn drop(&mut self) {
-        let path = self.path.clone();
         let _ = self
             .lifetime_sender
-            .send(AssetLifetimeEvent::Dropped(self.id, path));
+            .send(AssetLifetimeEvent::Dropped(self.id));
     }
 }
 
@@ -43,6 +43,14 @@ impl Debug for StrongAssetHandle {
     }
 }
 
+/// A reference-counted reference to an asset in its
+/// [`AssetStore`](super::asset_store::AssetStore).
+///
+/// Reflected through [`SerializedHandle`], so components holding handles can be saved in
+/// scenes and written in glTF extras.  Deserializing needs an [`AssetServer`] resource in the
+/// world the component is inserted into.
+#[derive(Facet)]
+#[facet(opaque, proxy = SerializedHandle)]
 pub struct AssetHandle<A: Asset> {
     handle: Arc<StrongAssetHandle>,
     _marker: PhantomData<A>,
@@ -59,6 +67,60 @@ impl<A: Asset> AssetHandle<A> {
     pub fn id(&self) -> AssetId {
         self.handle.id
     }
+
+    pub(crate) fn into_strong(self) -> Arc<StrongAssetHandle> {
+        self.handle
+    }
+
+    /// Returns the form of this handle that is written to disk.
+    pub fn to_serialized(&self) -> SerializedHandle {
+        match &self.handle.path {
+            Some(path) => SerializedHandle::Path(path.clone()),
+            None => SerializedHandle::Id(self.id().to_string()),
+        }
+    }
+}
+
+/// How an [`AssetHandle`] is saved, and turned back into a live handle with
+/// [`AssetServer::handle_from_serialized`].
+#[derive(Facet, Debug, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SerializedHandle {
+    /// The asset is loaded from this path, with default usage settings.
+    ///
+    /// `path?settings` is loaded with other usage settings, `settings` being their hash in
+    /// hex.  The settings themselves aren't saved, so it only resolves while the asset is
+    /// loaded with them.
+    ///
+    /// `path#label` names a sub-asset that the loader of `path` adds with
+    /// [`add_labeled`](super::asset_server::AssetLoadContext::add_labeled), e.g.
+    /// `models/rock.gltf#mesh/0/0`.  Unless the sub-asset is alive already, its file is
+    /// loaded with the loader registered for the file's extension (see
+    /// [`LoadableAsset::extensions`](super::LoadableAsset::extensions)).
+    Path(String),
+    /// The asset has this [`AssetId`], and only resolves while the asset is alive.
+    Id(String),
+}
+
+impl<A: Asset> From<&AssetHandle<A>> for SerializedHandle {
+    fn from(handle: &AssetHandle<A>) -> Self {
+        handle.to_serialized()
+    }
+}
+
+impl<A: Asset> TryFrom<SerializedHandle> for AssetHandle<A> {
+    type Error = String;
+
+    fn try_from(serialized: SerializedHandle) -> Result<Self, Self::Error> {
+        with_deserializing_world(|world| {
+            let server = world
+                .and_then(|world| world.get_resource::<AssetServer>())
+                .ok_or("asset handles can only be deserialized into a world with an AssetServer")?;
+            server
+                .handle_from_serialized(&serialized)
+                .map_err(|err| format!("{err:#}"))
+        })
+    }
 }
 
 impl<A: Asset> Clone for AssetHandle<A> {
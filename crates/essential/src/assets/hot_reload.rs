@@ -0,0 +1,107 @@
+//! Reloading assets whose source file changed on disk.
+//!
+//! Only built with the `hot-reload` feature on native targets, so release builds can leave the
+//! file watcher out entirely.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crossbeam_channel::Receiver;
+use ecs::resource::{Res, ResMut, Resource};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use super::{asset_server::AssetServer, AssetPath};
+
+/// Watches the asset root and reports source files that changed.
+///
+/// Editors often write a file in several steps, so a change is only reported once the file
+/// has been left alone for [`AssetWatcher::SETTLE_TIME`].
+#[derive(Resource)]
+pub struct AssetWatcher {
+    // Kept alive for as long as the watcher should run.
+    _watcher: Mutex<RecommendedWatcher>,
+    receiver: Receiver<PathBuf>,
+    root: PathBuf,
+    /// Changed files, with the time of their latest change.
+    pending: HashMap<PathBuf, Instant>,
+}
+
+impl AssetWatcher {
+    pub const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+    /// Starts watching the `res` directory next to the executable, which is where the default
+    /// source reads paths without a source name from.
+    pub fn for_default_source() -> notify::Result<Self> {
+        let exe_path = std::env::current_exe()?;
+        let exe_dir = exe_path
+            .parent()
+            .ok_or_else(|| notify::Error::generic("could not determine executable directory"))?;
+        Self::new(exe_dir.join("res"))
+    }
+
+    /// Starts watching `root` recursively.  Changed files are reported relative to `root`, so
+    /// it should be the directory the default source resolves `res/` paths in.
+    pub fn new(root: impl AsRef<Path>) -> notify::Result<Self> {
+        let root = root.as_ref().canonicalize()?;
+        let (sender, receiver) = crossbeam_channel::unbounded();
+
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+                Ok(event) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    for path in event.paths {
+                        let _ = sender.send(path);
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => log::warn!("Asset watcher error: {err}"),
+            })?;
+        watcher.watch(&root, RecursiveMode::Recursive)?;
+
+        Ok(Self {
+            _watcher: Mutex::new(watcher),
+            receiver,
+            root,
+            pending: HashMap::new(),
+        })
+    }
+
+    /// Returns the asset paths of the files that changed and have since settled.
+    pub fn changed_paths(&mut self) -> Vec<AssetPath<'static>> {
+        let now = Instant::now();
+        for path in self.receiver.try_iter() {
+            self.pending.insert(path, now);
+        }
+
+        let mut settled = Vec::new();
+        self.pending.retain(|path, changed_at| {
+            if now.duration_since(*changed_at) < Self::SETTLE_TIME {
+                return true;
+            }
+            settled.push(path.clone());
+            false
+        });
+
+        settled
+            .iter()
+            .filter_map(|path| self.to_asset_path(path))
+            .collect()
+    }
+
+    fn to_asset_path(&self, path: &Path) -> Option<AssetPath<'static>> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        Some(AssetPath::new(relative.to_str()?).into_owned())
+    }
+}
+
+/// Reloads every loaded asset whose source file changed.
+///
+/// Registered by `AssetManagerPlugin` when the `hot-reload` feature is enabled.
+pub fn reload_changed_assets(asset_server: Res<AssetServer>, mut watcher: ResMut<AssetWatcher>) {
+    for path in watcher.changed_paths() {
+        asset_server.reload(path);
+    }
+}
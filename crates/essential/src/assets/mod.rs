@@ -1,63 +1,109 @@
 use std::{
     borrow::Cow,
-    hash::Hash,
+    hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
 
 use uuid::Uuid;
 
+use bake::ContentHasher;
+
 pub use essential_macros::Asset;
 
 pub mod asset_container;
 pub mod asset_loader;
 pub mod asset_server;
 pub mod asset_store;
+pub mod bake;
 pub mod handle;
+#[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+pub mod hot_reload;
+pub mod source;
+pub mod status;
 pub mod utils;
 
 // Path to an asset in a virtual file system.
+//
+// `name://some/file` names a file in the source registered as `name` on the asset server;
+// any other path is in the default source, below `res/`.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct AssetPath<'a> {
+    source: Option<Cow<'a, str>>,
     normalized_path: Cow<'a, Path>,
 }
 
 impl<'a> AssetPath<'a> {
     pub fn new(path: impl AsRef<str>) -> Self {
-        let mut normalized = path.as_ref().replace('\\', "/");
+        let path = path.as_ref();
 
-        // Remove any leading ./ or .\
-        if normalized.starts_with("./") {
-            normalized.drain(..2);
+        if let Some((source, path)) = path.split_once("://") {
+            return AssetPath {
+                source: Some(Cow::Owned(source.to_string())),
+                normalized_path: Cow::Owned(PathBuf::from(normalize_path(path))),
+            };
         }
 
+        let mut normalized = normalize_path(path);
         if !normalized.starts_with("res/") {
             normalized = format!("res/{}", normalized);
         }
 
         AssetPath {
+            source: None,
             normalized_path: Cow::Owned(Path::new(&normalized).to_owned()),
         }
     }
 
+    /// The name of the source this path is in, or `None` for the default source.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// The path within its source.
     pub fn to_path(&self) -> &Path {
         &self.normalized_path
     }
 
+    /// Returns the path of `relative`, taken relative to the directory of this path, in the
+    /// same source.
+    pub fn resolve(&self, relative: impl AsRef<str>) -> AssetPath<'static> {
+        let directory = self.normalized_path.parent().unwrap_or(Path::new(""));
+        AssetPath {
+            source: self.source.as_ref().map(|s| Cow::Owned(s.to_string())),
+            normalized_path: Cow::Owned(directory.join(normalize_path(relative.as_ref()))),
+        }
+    }
+
     pub fn into_owned(self) -> AssetPath<'static> {
         AssetPath {
+            source: self.source.map(|s| Cow::Owned(s.into_owned())),
             normalized_path: Cow::Owned(self.normalized_path.into_owned()),
         }
     }
+}
+
+impl std::fmt::Display for AssetPath<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(source) = &self.source {
+            write!(f, "{source}://")?;
+        }
+        write!(f, "{}", self.normalized_path.display())
+    }
+}
 
-    #[cfg(target_arch = "wasm32")]
-    pub fn to_string(&self) -> &str {
-        self.normalized_path.to_str().unwrap()
+/// Uses forward slashes and drops a leading `./`.
+pub(crate) fn normalize_path(path: &str) -> String {
+    let mut normalized = path.replace('\\', "/");
+    if normalized.starts_with("./") {
+        normalized.drain(..2);
     }
+    normalized
 }
 
 impl<'a> From<PathBuf> for AssetPath<'a> {
     fn from(path: PathBuf) -> Self {
         AssetPath {
+            source: None,
             normalized_path: Cow::Owned(path),
         }
     }
@@ -82,6 +128,31 @@ impl AssetId {
     pub fn new() -> Self {
         AssetId(Uuid::new_v4())
     }
+
+    /// Returns the same id for the same `name` in every run, so an asset that is generated
+    /// rather than loaded from a path keeps its identity in saved scenes.
+    pub fn derived(name: &str) -> Self {
+        let mut low = ContentHasher::default();
+        low.write(name.as_bytes());
+        let mut high = ContentHasher::default();
+        high.write(b"asset-id:");
+        high.write(name.as_bytes());
+        AssetId(Uuid::from_u64_pair(high.finish(), low.finish()))
+    }
+}
+
+impl std::fmt::Display for AssetId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for AssetId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s).map(AssetId)
+    }
 }
 
 impl Default for AssetId {
@@ -95,8 +166,26 @@ pub trait Asset: Send + Sync + 'static {
 }
 
 pub trait LoadableAsset: Asset {
-    type UsageSettings: Send + Sync;
+    /// Cloned to load the asset again on [`reload`](asset_server::AssetServer::reload), and
+    /// hashed so loading a path with different settings loads a separate asset.
+    type UsageSettings: Clone + Hash + Send + Sync;
     fn loader() -> Box<dyn asset_loader::AssetLoader<Asset = Self>>;
 
     fn default_usage_settings() -> Self::UsageSettings;
+
+    /// File extensions the loader reads, without the dot.  Lets a saved `path#label` handle
+    /// load its file when nothing else has (see [`handle::SerializedHandle::Path`]), so loaders
+    /// adding labeled sub-assets should list theirs.
+    fn extensions() -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// An asset whose content can be hashed, so
+/// [`add_unique`](asset_server::AssetServer::add_unique) can hand out an identical asset that
+/// was already added instead of a duplicate.
+pub trait Fingerprint: Asset {
+    /// Feeds everything that tells two assets of this type apart into `state`.  Assets are only
+    /// shared when they feed in exactly the same bytes.
+    fn fingerprint<H: Hasher>(&self, state: &mut H);
 }
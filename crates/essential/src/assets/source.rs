@@ -0,0 +1,323 @@
+//! Where the [`AssetServer`](super::asset_server::AssetServer) reads asset bytes from.
+//!
+//! An [`AssetPath`](super::AssetPath) such as `memory://meshes/cube.obj` is read from the
+//! source registered under `memory`; a path without a `name://` prefix is read from the default
+//! source, which is the executable's directory on native and the page's origin on wasm.
+
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Seek},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+
+use super::{normalize_path, utils::load_binary, AssetPath};
+
+/// Provides the bytes of the assets under one source name.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait AssetSource: Send + Sync + 'static {
+    /// Reads the whole file at `path`, relative to the root of the source.
+    async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>>;
+
+    /// Where `path` lives on the local filesystem, for sources that read from one.  Loaders
+    /// whose formats refer to other files by relative path need it to find them.
+    fn local_path(&self, _path: &Path) -> Option<PathBuf> {
+        None
+    }
+}
+
+/// The source used for paths without a source name: the directory of the executable on
+/// native, HTTP requests to the page's origin on wasm.
+pub struct DefaultSource;
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AssetSource for DefaultSource {
+    async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        load_binary(AssetPath::from(path.to_path_buf())).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn local_path(&self, path: &Path) -> Option<PathBuf> {
+        Some(std::env::current_exe().ok()?.parent()?.join(path))
+    }
+}
+
+/// Reads files below a directory on disk.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct DirectorySource {
+    root: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DirectorySource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl AssetSource for DirectorySource {
+    async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        let path = self.root.join(path);
+        std::fs::read(&path).with_context(|| format!("failed to read file '{}'", path.display()))
+    }
+
+    fn local_path(&self, path: &Path) -> Option<PathBuf> {
+        Some(self.root.join(path))
+    }
+}
+
+/// Files kept in memory, which can be added and replaced at any time.
+///
+/// Cloning shares the files, so a clone kept after registering the source can still insert
+/// into it.
+#[derive(Clone, Default)]
+pub struct MemorySource {
+    files: Arc<RwLock<HashMap<PathBuf, Arc<[u8]>>>>,
+}
+
+impl MemorySource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the file at `path`, replacing any previous contents.
+    pub fn insert(&self, path: impl AsRef<str>, bytes: impl Into<Vec<u8>>) {
+        self.files
+            .write()
+            .unwrap()
+            .insert(normalize_path(path.as_ref()).into(), bytes.into().into());
+    }
+
+    /// Removes the file at `path`, returning `true` if there was one.
+    pub fn remove(&self, path: impl AsRef<str>) -> bool {
+        self.files
+            .write()
+            .unwrap()
+            .remove(Path::new(&normalize_path(path.as_ref())))
+            .is_some()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AssetSource for MemorySource {
+    async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        let files = self.files.read().unwrap();
+        let bytes = files
+            .get(path)
+            .with_context(|| format!("no file '{}' in memory source", path.display()))?;
+        Ok(bytes.to_vec())
+    }
+}
+
+/// Files compiled into the executable, usually with [`include_bytes!`].
+///
+/// # Example
+/// ```
+/// use essential::assets::source::EmbeddedSource;
+///
+/// let source = EmbeddedSource::new().with("shaders/blit.wgsl", b"@vertex fn main() {}");
+/// ```
+#[derive(Default)]
+pub struct EmbeddedSource {
+    files: HashMap<PathBuf, &'static [u8]>,
+}
+
+impl EmbeddedSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the file at `path`.
+    pub fn with(mut self, path: impl AsRef<str>, bytes: &'static [u8]) -> Self {
+        self.files
+            .insert(normalize_path(path.as_ref()).into(), bytes);
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AssetSource for EmbeddedSource {
+    async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        let bytes = self
+            .files
+            .get(path)
+            .with_context(|| format!("no file '{}' embedded", path.display()))?;
+        Ok(bytes.to_vec())
+    }
+}
+
+trait ArchiveReader: Read + Seek + Send {}
+
+impl<R: Read + Seek + Send> ArchiveReader for R {}
+
+/// Files packed into a zip archive, e.g. a `.pak` shipped next to the executable.
+pub struct ArchiveSource {
+    archive: Mutex<zip::ZipArchive<Box<dyn ArchiveReader>>>,
+}
+
+impl ArchiveSource {
+    /// Reads the archive from `reader`, which is kept open to read files from on demand.
+    pub fn new(reader: impl Read + Seek + Send + 'static) -> anyhow::Result<Self> {
+        let reader: Box<dyn ArchiveReader> = Box::new(reader);
+        let archive = zip::ZipArchive::new(reader).context("failed to read archive")?;
+        Ok(Self {
+            archive: Mutex::new(archive),
+        })
+    }
+
+    /// Reads the archive from bytes already in memory.
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> anyhow::Result<Self> {
+        Self::new(Cursor::new(bytes.into()))
+    }
+
+    /// Opens the archive file at `path`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open archive '{}'", path.display()))?;
+        Self::new(std::io::BufReader::new(file))
+            .with_context(|| format!("failed to read archive '{}'", path.display()))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AssetSource for ArchiveSource {
+    async fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        let name = path.to_str().context("archive paths must be valid UTF-8")?;
+        let mut archive = self.archive.lock().unwrap();
+        let mut file = archive
+            .by_name(name)
+            .with_context(|| format!("no file '{name}' in archive"))?;
+        let mut bytes = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut bytes)
+            .with_context(|| format!("failed to read '{name}' from archive"))?;
+        Ok(bytes)
+    }
+}
+
+/// The sources registered on an [`AssetServer`](super::asset_server::AssetServer).
+pub(crate) struct AssetSources {
+    default: Arc<dyn AssetSource>,
+    named: HashMap<String, Arc<dyn AssetSource>>,
+}
+
+impl AssetSources {
+    pub(crate) fn get(&self, name: Option<&str>) -> anyhow::Result<Arc<dyn AssetSource>> {
+        match name {
+            None => Ok(self.default.clone()),
+            Some(name) => self
+                .named
+                .get(name)
+                .cloned()
+                .with_context(|| format!("no asset source named '{name}'")),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, name: String, source: Arc<dyn AssetSource>) {
+        self.named.insert(name, source);
+    }
+
+    pub(crate) fn set_default(&mut self, source: Arc<dyn AssetSource>) {
+        self.default = source;
+    }
+}
+
+impl Default for AssetSources {
+    fn default() -> Self {
+        Self {
+            default: Arc::new(DefaultSource),
+            named: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::assets::asset_server::AssetServer;
+
+    #[test]
+    fn named_paths_keep_their_source_and_skip_the_res_prefix() {
+        let path = AssetPath::new("memory://.\\meshes/cube.obj");
+        assert_eq!(path.source(), Some("memory"));
+        assert_eq!(path.to_path(), Path::new("meshes/cube.obj"));
+        assert_eq!(path.to_string(), "memory://meshes/cube.obj");
+
+        let sibling = path.resolve("cube.mtl");
+        assert_eq!(sibling.source(), Some("memory"));
+        assert_eq!(sibling.to_path(), Path::new("meshes/cube.mtl"));
+
+        let default = AssetPath::new("meshes/cube.obj");
+        assert_eq!(default.source(), None);
+        assert_eq!(default.to_path(), Path::new("res/meshes/cube.obj"));
+    }
+
+    #[test]
+    fn server_reads_from_the_source_named_in_the_path() {
+        let server = AssetServer::new();
+        let memory = MemorySource::new();
+        server.register_source("memory", memory.clone());
+        server.register_source(
+            "embedded",
+            EmbeddedSource::new().with("hello.txt", b"embedded hello"),
+        );
+
+        memory.insert("hello.txt", "memory hello");
+
+        let read = |path| pollster::block_on(server.read_to_string(&AssetPath::new(path)));
+        assert_eq!(read("memory://hello.txt").unwrap(), "memory hello");
+        assert_eq!(read("embedded://hello.txt").unwrap(), "embedded hello");
+        assert!(read("memory://missing.txt").is_err());
+        assert!(read("unknown://hello.txt").is_err());
+
+        assert!(memory.remove("hello.txt"));
+        assert!(read("memory://hello.txt").is_err());
+    }
+
+    #[test]
+    fn archive_source_reads_files_from_a_zip() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        writer.start_file("meshes/cube.obj", options).unwrap();
+        writer.write_all(b"v 0 0 0").unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let server = AssetServer::new();
+        server.register_source("pak", ArchiveSource::from_bytes(bytes).unwrap());
+
+        let read = |path| pollster::block_on(server.read(&AssetPath::new(path)));
+        assert_eq!(read("pak://meshes/cube.obj").unwrap(), b"v 0 0 0");
+        assert!(read("pak://meshes/sphere.obj").is_err());
+    }
+
+    #[test]
+    fn only_directory_sources_have_local_paths() {
+        let server = AssetServer::new();
+        server.register_source("memory", MemorySource::new());
+        server.register_source("disk", DirectorySource::new("/assets"));
+
+        let local_path = |path| server.local_path(&AssetPath::new(path));
+        assert_eq!(local_path("memory://scene.gltf"), None);
+        assert_eq!(
+            local_path("disk://scenes/scene.gltf"),
+            Some(PathBuf::from("/assets/scenes/scene.gltf"))
+        );
+
+        server.set_default_source(MemorySource::new());
+        assert_eq!(local_path("scenes/scene.gltf"), None);
+    }
+}
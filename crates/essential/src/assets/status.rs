@@ -0,0 +1,72 @@
+use std::marker::PhantomData;
+
+use ecs::{events::event_channel::EventChannel, world::World, Event};
+
+use super::{handle::AssetHandle, Asset, AssetId};
+
+/// Where an asset known to the [`AssetServer`](super::asset_server::AssetServer) is in its
+/// load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetStatus {
+    /// Requested, and it or one of its dependencies is not in its
+    /// [`AssetStore`](super::asset_store::AssetStore) yet.
+    Loading,
+    /// In its [`AssetStore`](super::asset_store::AssetStore), along with its dependencies.
+    Loaded,
+    /// Its loader, or the loader of one of its dependencies, returned an error; loading the
+    /// path again retries.
+    Failed(String),
+}
+
+/// Sent once an asset of type `A` and its dependencies have been inserted into their
+/// [`AssetStore`](super::asset_store::AssetStore)s, and again each time a
+/// [`reload`](super::asset_server::AssetServer::reload) replaces it.
+#[derive(Event)]
+pub struct AssetLoaded<A: Asset> {
+    pub id: AssetId,
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A: Asset> AssetLoaded<A> {
+    /// Returns `true` if this event is about the asset behind `handle`.
+    pub fn is(&self, handle: &AssetHandle<A>) -> bool {
+        self.id == handle.id()
+    }
+}
+
+/// Sent when the loader of an asset of type `A`, or of one of its dependencies, returns an
+/// error.
+#[derive(Event)]
+pub struct AssetFailed<A: Asset> {
+    pub id: AssetId,
+    pub error: String,
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A: Asset> AssetFailed<A> {
+    /// Returns `true` if this event is about the asset behind `handle`.
+    pub fn is(&self, handle: &AssetHandle<A>) -> bool {
+        self.id == handle.id()
+    }
+}
+
+/// Sends an [`AssetLoaded<A>`] event, if the app registered one for `A`.
+pub(crate) fn send_loaded<A: Asset>(world: &mut World, id: AssetId) {
+    if let Some(channel) = world.get_resource_mut::<EventChannel<AssetLoaded<A>>>() {
+        channel.push_event(AssetLoaded {
+            id,
+            _marker: PhantomData,
+        });
+    }
+}
+
+/// Sends an [`AssetFailed<A>`] event, if the app registered one for `A`.
+pub(crate) fn send_failed<A: Asset>(world: &mut World, id: AssetId, error: String) {
+    if let Some(channel) = world.get_resource_mut::<EventChannel<AssetFailed<A>>>() {
+        channel.push_event(AssetFailed {
+            id,
+            error,
+            _marker: PhantomData,
+        });
+    }
+}
@@ -10,7 +10,7 @@ fn format_url<'a>(path: AssetPath<'a>) -> reqwest::Url {
     let origin = location.origin().unwrap();
 
     let base = reqwest::Url::parse(&format!("{}/", origin,)).unwrap();
-    base.join(path.to_string()).unwrap()
+    base.join(path.to_path().to_str().unwrap()).unwrap()
 }
 
 pub async fn load_to_string<'a>(path: AssetPath<'a>) -> anyhow::Result<String> {
@@ -6,38 +6,187 @@ mod frame_stats;
 mod instant;
 
 pub use frame_stats::FrameStats;
+pub use instant::Instant;
 
-use crate::time::instant::Instant;
-
+/// Frame timing, as two clocks.
+///
+/// The *virtual* clock ([`delta`](Time::delta), [`elapsed`](Time::elapsed)) is what gameplay
+/// should use: it runs at [`time_scale`](Time::time_scale) times real time and stands still
+/// while [paused](Time::pause).  It also drives the fixed timestep, so slow motion and pausing
+/// apply to `FixedUpdate` as well.
+///
+/// The *real* clock ([`real_delta`](Time::real_delta), [`real_elapsed`](Time::real_elapsed))
+/// is unaffected by either, for UI, menus and frame statistics.
+///
+/// Both clocks normally follow the wall clock; with [`TimeUpdateStrategy::Manual`] they only
+/// move when [`advance`](Time::advance) is called, e.g. by `App::step` in tests.
 #[derive(Resource)]
 pub struct Time {
     last_update: Instant,
+    real_delta: Duration,
+    real_elapsed: Duration,
     delta: Duration,
+    elapsed: Duration,
+    time_scale: f32,
+    paused: bool,
+    update_strategy: TimeUpdateStrategy,
+    fixed_delta: Duration,
+    max_fixed_steps: u32,
     fixed_overstep: f32,
 }
 
 impl Time {
-    const FIXED_DELTA_TIME: f32 = 1.0 / 30.0; // 30 FPS
+    /// Default length of a fixed step, in seconds.
+    pub const DEFAULT_FIXED_DELTA_TIME: f32 = 1.0 / 30.0; // 30 FPS
+
+    /// Default cap on fixed steps per frame.  Past it, the remaining time is dropped, so a
+    /// long hitch slows the simulation down for a frame instead of snowballing into ever
+    /// longer frames.
+    pub const DEFAULT_MAX_FIXED_STEPS: u32 = 8;
 
     pub fn new() -> Self {
         Self {
             last_update: Instant::now(),
-            delta: Duration::default(),
+            real_delta: Duration::ZERO,
+            real_elapsed: Duration::ZERO,
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            time_scale: 1.0,
+            paused: false,
+            update_strategy: TimeUpdateStrategy::Automatic,
+            fixed_delta: Duration::from_secs_f32(Self::DEFAULT_FIXED_DELTA_TIME),
+            max_fixed_steps: Self::DEFAULT_MAX_FIXED_STEPS,
             fixed_overstep: 0.0,
         }
     }
 
+    /// Virtual time since the previous frame: scaled by the time scale, and zero while paused.
     pub fn delta(&self) -> Duration {
         self.delta
     }
 
+    /// Virtual time since the app started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Real time since the previous frame, ignoring time scale and pause.
+    pub fn real_delta(&self) -> Duration {
+        self.real_delta
+    }
+
+    /// Real time since the app started, ignoring time scale and pause.
+    pub fn real_elapsed(&self) -> Duration {
+        self.real_elapsed
+    }
+
+    /// Measures the real time since the previous call and advances both clocks by it.
+    /// Does nothing with [`TimeUpdateStrategy::Manual`].
     pub fn update(&mut self) {
-        self.delta = Instant::now() - self.last_update;
+        if self.update_strategy == TimeUpdateStrategy::Manual {
+            return;
+        }
+
+        let now = Instant::now();
+        self.advance(now - self.last_update);
+        self.last_update = now;
+    }
+
+    /// Advances the clocks as if `real_delta` had passed since the previous frame.
+    pub fn advance(&mut self, real_delta: Duration) {
+        self.real_delta = real_delta;
+        self.real_elapsed += real_delta;
+
+        self.delta = if self.paused {
+            Duration::ZERO
+        } else {
+            real_delta.mul_f64(f64::from(self.time_scale))
+        };
+        self.elapsed += self.delta;
+    }
+
+    /// How the clocks advance each frame.
+    pub fn update_strategy(&self) -> TimeUpdateStrategy {
+        self.update_strategy
+    }
+
+    /// Chooses whether [`update`](Time::update) follows the wall clock.
+    pub fn set_update_strategy(&mut self, update_strategy: TimeUpdateStrategy) {
+        self.update_strategy = update_strategy;
         self.last_update = Instant::now();
     }
 
-    pub fn fixed_delta_time() -> f32 {
-        Self::FIXED_DELTA_TIME
+    /// How fast virtual time runs relative to real time; `1.0` by default.
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Sets how fast virtual time runs relative to real time, e.g. `0.25` for slow motion.
+    /// Takes effect from the next frame.
+    ///
+    /// # Panics
+    /// Panics if `time_scale` is negative or not finite.
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        assert!(
+            time_scale.is_finite() && time_scale >= 0.0,
+            "time scale must be finite and non-negative, got {time_scale}"
+        );
+        self.time_scale = time_scale;
+    }
+
+    /// Stops virtual time from the next frame on, which also stops the fixed timestep.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes virtual time after [`pause`](Time::pause).
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Length of a fixed step, in seconds of virtual time.
+    pub fn fixed_delta_time(&self) -> f32 {
+        self.fixed_delta.as_secs_f32()
+    }
+
+    /// Length of a fixed step, in virtual time.
+    pub fn fixed_delta(&self) -> Duration {
+        self.fixed_delta
+    }
+
+    /// Sets the length of a fixed step, in seconds of virtual time.
+    ///
+    /// # Panics
+    /// Panics unless `fixed_delta_time` is a finite, positive duration of at least a
+    /// nanosecond.
+    pub fn set_fixed_delta_time(&mut self, fixed_delta_time: f32) {
+        let fixed_delta = Duration::try_from_secs_f32(fixed_delta_time)
+            .ok()
+            .filter(|fixed_delta| !fixed_delta.is_zero());
+        let Some(fixed_delta) = fixed_delta else {
+            panic!("fixed delta time must be positive, got {fixed_delta_time}");
+        };
+        self.fixed_delta = fixed_delta;
+    }
+
+    /// Most fixed steps run in a single frame.
+    pub fn max_fixed_steps(&self) -> u32 {
+        self.max_fixed_steps
+    }
+
+    /// Sets the most fixed steps run in a single frame; see
+    /// [`DEFAULT_MAX_FIXED_STEPS`](Time::DEFAULT_MAX_FIXED_STEPS).
+    ///
+    /// # Panics
+    /// Panics if `max_fixed_steps` is zero, which would stop `FixedUpdate` altogether; use
+    /// [`pause`](Time::pause) for that.
+    pub fn set_max_fixed_steps(&mut self, max_fixed_steps: u32) {
+        assert!(max_fixed_steps > 0, "max fixed steps must be at least 1");
+        self.max_fixed_steps = max_fixed_steps;
     }
 
     /// Seconds elapsed since the last fixed step. The physics world is frozen between
@@ -53,12 +202,72 @@ impl Time {
     /// Fraction of the way from the last fixed step to the next, for interpolating
     /// fixed-step state into frame-rate rendering.
     pub fn fixed_alpha(&self) -> f32 {
-        (self.fixed_overstep / Self::FIXED_DELTA_TIME).clamp(0.0, 1.0)
+        (self.fixed_overstep / self.fixed_delta_time()).clamp(0.0, 1.0)
     }
 }
 
+/// How [`Time`] advances from frame to frame.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TimeUpdateStrategy {
+    /// Both clocks follow the wall clock, measured once per frame.
+    #[default]
+    Automatic,
+    /// The clocks only move when [`Time::advance`] is called, so a run can be replayed
+    /// exactly.
+    Manual,
+}
+
 impl Default for Time {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtual_time_follows_scale_and_pause() {
+        let mut time = Time::new();
+
+        time.advance(Duration::from_millis(100));
+        assert_eq!(time.delta(), Duration::from_millis(100));
+
+        time.set_time_scale(0.5);
+        time.advance(Duration::from_millis(100));
+        assert_eq!(time.delta(), Duration::from_millis(50));
+
+        time.pause();
+        time.advance(Duration::from_millis(100));
+        assert_eq!(time.delta(), Duration::ZERO);
+        assert_eq!(time.real_delta(), Duration::from_millis(100));
+
+        time.unpause();
+        time.advance(Duration::from_millis(100));
+        assert_eq!(time.delta(), Duration::from_millis(50));
+
+        assert_eq!(time.elapsed(), Duration::from_millis(200));
+        assert_eq!(time.real_elapsed(), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn fixed_delta_time_rejects_zero_sub_nanosecond_and_infinite_steps() {
+        for fixed_delta_time in [0.0, -1.0, 1e-10, f32::INFINITY, f32::NAN, f32::MAX] {
+            let result = std::panic::catch_unwind(|| {
+                Time::new().set_fixed_delta_time(fixed_delta_time);
+            });
+            assert!(result.is_err(), "{fixed_delta_time} was accepted");
+        }
+
+        let mut time = Time::new();
+        time.set_fixed_delta_time(0.25);
+        assert_eq!(time.fixed_delta(), Duration::from_millis(250));
+    }
+
+    #[test]
+    #[should_panic(expected = "max fixed steps must be at least 1")]
+    fn max_fixed_steps_rejects_zero() {
+        Time::new().set_max_fixed_steps(0);
+    }
+}
@@ -26,10 +26,13 @@ pub fn propagate_global_transforms(
     roots: Query<(&Children, &mut GlobalTransform, &Transform), Without<ChildOf>>,
     transform_query: Query<(Entity, &mut GlobalTransform, &Transform, Option<&Children>)>,
 ) {
-    for (root_children, mut root_global_transform, root_local_transform) in roots.iter() {
-        root_global_transform.set_matrix(root_local_transform.compute_matrix());
-        propagate_to_children(&root_global_transform, root_children, &transform_query);
-    }
+    // Every root owns a disjoint subtree, so hierarchies are propagated in parallel.
+    roots.par_iter().for_each(
+        |(root_children, mut root_global_transform, root_local_transform)| {
+            root_global_transform.set_matrix(root_local_transform.compute_matrix());
+            propagate_to_children(&root_global_transform, root_children, &transform_query);
+        },
+    );
 }
 
 fn propagate_to_children(
@@ -2,6 +2,7 @@ use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
+    path::Path,
 };
 
 use animation::{
@@ -17,8 +18,12 @@ use ecs::{
 };
 use essential::{
     assets::{
-        Asset, LoadableAsset, asset_loader::AssetLoader, asset_server::AssetServer,
-        asset_store::AssetStore, handle::AssetHandle,
+        Asset, LoadableAsset,
+        asset_loader::AssetLoader,
+        asset_server::AssetLoadContext,
+        asset_store::AssetStore,
+        bake::{BakeKey, ContentHasher},
+        handle::AssetHandle,
     },
     transform::Transform,
 };
@@ -38,6 +43,8 @@ use render::{
     components::{
         camera::Camera,
         light::{Light, LightType},
+        post_process::LUMINOUS_EFFICACY,
+        projection::{Projection, ScalingMode},
     },
 };
 use serde_json::Value;
@@ -46,10 +53,10 @@ use uuid::Uuid;
 // glTF (KHR_lights_punctual) stores light intensity in photometric units: lux
 // (lm/m^2) for directional lights and candela (lm/sr) for point/spot lights.
 // Blender produces these by scaling the artist's watt-based energy by the
-// luminous efficacy of an ideal source, 683 lm/W. This engine has no camera
-// exposure stage and lights at the radiometric (watt) scale, so we divide the
-// imported intensity by this factor to undo that conversion.
-const LUMINOUS_EFFICACY: f32 = 683.0;
+// luminous efficacy of an ideal source. This engine lights at the radiometric
+// (watt) scale, so imported intensities are divided by `LUMINOUS_EFFICACY`;
+// a camera's `Exposure` multiplies it back in when exposing the image, so
+// photographic EV values apply to glTF scenes as they are.
 
 const EXTRAS_COMPONENTS_KEY: &str = "components";
 
@@ -118,21 +125,11 @@ impl GLTFAnimation {
 }
 
 pub(crate) struct GLTFCamera {
-    pub(crate) fovy: f32,
+    pub(crate) projection: Projection,
     pub(crate) znear: f32,
     pub(crate) zfar: f32,
 }
 
-impl Default for GLTFCamera {
-    fn default() -> Self {
-        Self {
-            fovy: std::f32::consts::FRAC_PI_4,
-            znear: 0.1,
-            zfar: 100.0,
-        }
-    }
-}
-
 pub(crate) enum GLTFLightType {
     Point,
     Spot { cone_angle: f32 },
@@ -158,7 +155,7 @@ pub(crate) struct GLTFExtraComponentData {
     pub(crate) data: String,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Hash)]
 pub struct GLTFUsageSettings {
     pub root_bone: Option<&'static str>,
 }
@@ -173,6 +170,10 @@ impl LoadableAsset for GLTFScene {
     fn default_usage_settings() -> Self::UsageSettings {
         GLTFUsageSettings::default()
     }
+
+    fn extensions() -> &'static [&'static str] {
+        &["gltf", "glb"]
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -183,56 +184,104 @@ impl AssetLoader for GLTFLoader {
     async fn load(
         &self,
         path: essential::assets::AssetPath<'static>,
-        load_context: &mut essential::assets::asset_server::AssetLoadContext,
+        load_context: &mut AssetLoadContext,
         usage_setting: <Self::Asset as essential::assets::LoadableAsset>::UsageSettings,
     ) -> anyhow::Result<Self::Asset> {
-        // wasm has no filesystem, so `gltf::import` (path-based) fails with
-        // "operation not supported"; fetch the bytes over HTTP and parse the
-        // self-contained GLB from memory instead.
-        #[cfg(target_arch = "wasm32")]
-        let (document, buffers, images) = {
-            let bytes = essential::assets::utils::load_binary(path.clone())
-                .await
-                .with_context(|| {
-                    format!("failed to fetch GLTF file '{}'", path.to_path().display())
-                })?;
-            gltf::import_slice(&bytes).with_context(|| {
-                format!("failed to import GLTF file '{}'", path.to_path().display())
-            })?
-        };
-        #[cfg(not(target_arch = "wasm32"))]
-        let (document, buffers, images) = gltf::import(path.to_path()).with_context(|| {
-            format!("failed to import GLTF file '{}'", path.to_path().display())
-        })?;
+        let bytes = load_context
+            .read(&path)
+            .await
+            .with_context(|| format!("failed to read GLTF file '{path}'"))?;
+        // External buffers and images are loaded from next to the file, which needs the
+        // source to be a directory on disk.  Elsewhere (wasm, `memory://` and the like) only a
+        // self-contained GLB, or a glTF with embedded data, can be imported.
+        let local_path = load_context.local_path(&path);
+        let base = local_path.as_deref().and_then(Path::parent);
+        let gltf::Gltf { document, blob } = gltf::Gltf::from_slice(&bytes)
+            .with_context(|| format!("failed to import GLTF file '{path}'"))?;
+        let buffers = gltf::import_buffers(&document, base, blob)
+            .with_context(|| format!("failed to import GLTF buffers of '{path}'"))?;
+
+        let mut source_hasher = ContentHasher::default();
+        source_hasher.write(&bytes);
+        for buffer in &buffers {
+            source_hasher.write(buffer);
+        }
+        let bake_key = BakeKey::new(&path, source_hasher.finish());
 
         let nodes = document.nodes().map(GLTFLoader::extract_node).collect();
 
-        let mut decoded_images = Vec::new();
-        for (image_index, data) in images.into_iter().enumerate() {
-            let image = GLTFLoader::dynamic_image_from_gltf(data)
-                .with_context(|| format!("failed to load GLTF image at index {}", image_index))?;
-            decoded_images.push(image);
-        }
-
         // Color textures (base color, emissive) are sRGB-encoded while data
         // textures (normal, metallic-roughness, occlusion) are linear.  An
-        // image can be referenced in both roles, so texture assets are
-        // created lazily per (image, color space) pair.
-        let mut texture_cache: HashMap<(usize, bool), AssetHandle<Texture>> = HashMap::new();
-        let mut texture_handle = |texture: gltf::Texture<'_>, srgb: bool| {
-            let image_index = texture.source().index();
-            texture_cache
-                .entry((image_index, srgb))
-                .or_insert_with(|| {
+        // image can be referenced in both roles, so a texture asset is
+        // created per (image, color space) pair the materials use.
+        let mut texture_uses = HashSet::new();
+        for gltf_material in document.materials() {
+            let pbr = gltf_material.pbr_metallic_roughness();
+            let srgb_textures = [
+                pbr.base_color_texture().map(|info| info.texture()),
+                gltf_material.emissive_texture().map(|info| info.texture()),
+            ];
+            let linear_textures = [
+                gltf_material.normal_texture().map(|info| info.texture()),
+                pbr.metallic_roughness_texture().map(|info| info.texture()),
+                gltf_material.occlusion_texture().map(|info| info.texture()),
+            ];
+            texture_uses.extend(
+                srgb_textures
+                    .into_iter()
+                    .flatten()
+                    .map(|texture| (texture.source().index(), true)),
+            );
+            texture_uses.extend(
+                linear_textures
+                    .into_iter()
+                    .flatten()
+                    .map(|texture| (texture.source().index(), false)),
+            );
+        }
+
+        // Images are only decoded if one of the textures is not baked.
+        let mut decoded_images = Vec::new();
+        let mut textures: HashMap<(usize, bool), AssetHandle<Texture>> = HashMap::new();
+        for (image_index, srgb) in texture_uses {
+            let color_space = if srgb { "srgb" } else { "linear" };
+            let texture_label = format!("image/{image_index}/{color_space}");
+            let texture_key = bake_key.item(&texture_label);
+            // An image in a separate file is not part of the source hash, so a bake of it
+            // could go stale unnoticed.
+            let bakeable = document
+                .images()
+                .nth(image_index)
+                .is_some_and(|image| !GLTFLoader::is_external_image(&image));
+
+            let baked = bakeable
+                .then(|| load_context.baked::<Texture>(&texture_key))
+                .flatten();
+            let texture = match baked {
+                Some(texture) => texture,
+                None => {
+                    if decoded_images.is_empty() {
+                        decoded_images = GLTFLoader::decode_images(&document, base, &buffers)?;
+                    }
                     let image = decoded_images[image_index].clone();
                     let texture = if srgb {
                         Texture::from_dynamic_image(image)
                     } else {
                         Texture::from_dynamic_image_linear(image)
                     };
-                    load_context.asset_server().add(texture)
-                })
-                .clone()
+                    if bakeable {
+                        load_context.bake(&texture_key, &texture);
+                    }
+                    texture
+                }
+            };
+            textures.insert(
+                (image_index, srgb),
+                load_context.add_labeled(&texture_label, texture),
+            );
+        }
+        let texture_handle = |texture: gltf::Texture<'_>, srgb: bool| {
+            textures[&(texture.source().index(), srgb)].clone()
         };
 
         let mut materials = Vec::new();
@@ -267,7 +316,7 @@ impl AssetLoader for GLTFLoader {
                 material.set_alpha_cutoff(gltf_material.alpha_cutoff().unwrap_or(0.5));
             }
 
-            materials.push(load_context.asset_server().add(material));
+            materials.push(load_context.add_unique(material));
         }
 
         let mut meshes = Vec::new();
@@ -281,7 +330,9 @@ impl AssetLoader for GLTFLoader {
                     GLTFLoader::load_primitive(
                         &buffers,
                         &gltf_primitive,
-                        load_context.asset_server(),
+                        &format!("mesh/{}/{}", mesh.index(), gltf_primitive.index()),
+                        &bake_key,
+                        load_context,
                     )
                     .with_context(|| {
                         format!(
@@ -294,8 +345,7 @@ impl AssetLoader for GLTFLoader {
                 primitive_materials.push(match gltf_primitive.material().index() {
                     Some(material_index) => material_index,
                     None => *default_material.get_or_insert_with(|| {
-                        materials
-                            .push(load_context.asset_server().add(StandardMaterial::default()));
+                        materials.push(load_context.add_unique(StandardMaterial::default()));
                         materials.len() - 1
                     }),
                 });
@@ -325,7 +375,8 @@ impl AssetLoader for GLTFLoader {
                         .into()
                 })
             {
-                let skeleton = load_context.asset_server().add(inverse_bind_matrices);
+                let skeleton = load_context
+                    .add_labeled(&format!("skin/{}", skin.index()), inverse_bind_matrices);
                 let bones: Vec<usize> = skin.joints().map(|j| j.index()).collect();
                 let bone_ids = skin
                     .joints()
@@ -356,12 +407,32 @@ impl AssetLoader for GLTFLoader {
         let mut target_id_to_node_idx = HashMap::new();
         let mut animations = Vec::new();
         for (index, animation) in document.animations().enumerate() {
+            let clip_label = format!("animation/{index}");
+            let clip_key = bake_key.item(&clip_label);
+            let baked_clip = load_context.baked::<AnimationClip>(&clip_key);
             let mut animation_clip = AnimationClip::default();
 
             for channel in animation.channels() {
                 let target = channel.target();
 
                 let target_node_idx = target.node().index();
+
+                if let Some(baked_clip) = &baked_clip {
+                    // The samples come from the bake; only the target mapping is needed.
+                    if let Some(node_path_info) = node_paths.get(&target_node_idx) {
+                        let target_id = paths_to_uuid(&node_path_info.node_path);
+                        if baked_clip.get_channels(&target_id).is_some() {
+                            target_id_to_node_idx.insert(
+                                target_id,
+                                GLTFAnimationTargetInfo {
+                                    node_index: target_node_idx,
+                                },
+                            );
+                        }
+                    }
+                    continue;
+                }
+
                 let channel_reader = channel.reader(|buffer| Some(&buffers[buffer.index()]));
 
                 let time_samples = channel_reader
@@ -417,12 +488,19 @@ impl AssetLoader for GLTFLoader {
                     warn!("Missing an node name for node {}.", target_node_idx);
                 }
             }
+            let animation_clip = match baked_clip {
+                Some(baked_clip) => baked_clip,
+                None => {
+                    load_context.bake(&clip_key, &animation_clip);
+                    animation_clip
+                }
+            };
             animations.push(GLTFAnimation {
                 name: animation
                     .name()
                     .map(|str| str.into())
                     .unwrap_or(format!("Animation{}", index)),
-                handle: load_context.asset_server().add(animation_clip),
+                handle: load_context.add_labeled(&clip_label, animation_clip),
             });
         }
 
@@ -430,17 +508,20 @@ impl AssetLoader for GLTFLoader {
             .cameras()
             .map(|cam| match cam.projection() {
                 gltf::camera::Projection::Perspective(p) => GLTFCamera {
-                    fovy: p.yfov(),
+                    projection: Projection::perspective(p.yfov()),
                     znear: p.znear(),
                     zfar: p.zfar().unwrap_or(100.0),
                 },
-                gltf::camera::Projection::Orthographic(_) => {
-                    warn!(
-                        "Orthographic camera '{}' is not supported, using default perspective",
-                        cam.name().unwrap_or("<unnamed>")
-                    );
-                    GLTFCamera::default()
-                }
+                // `ymag` is half the view's height. The width follows the
+                // camera's aspect like it does for perspective cameras, rather
+                // than `xmag`, which was only right for the exporter's viewport.
+                gltf::camera::Projection::Orthographic(o) => GLTFCamera {
+                    projection: Projection::orthographic(ScalingMode::FixedVertical {
+                        height: o.ymag() * 2.0,
+                    }),
+                    znear: o.znear(),
+                    zfar: o.zfar(),
+                },
             })
             .collect();
 
@@ -482,6 +563,31 @@ impl AssetLoader for GLTFLoader {
 }
 
 impl GLTFLoader {
+    fn decode_images(
+        document: &gltf::Document,
+        base: Option<&Path>,
+        buffers: &[Data],
+    ) -> anyhow::Result<Vec<image::DynamicImage>> {
+        gltf::import_images(document, base, buffers)
+            .context("failed to import GLTF images")?
+            .into_iter()
+            .enumerate()
+            .map(|(image_index, data)| {
+                GLTFLoader::dynamic_image_from_gltf(data)
+                    .with_context(|| format!("failed to load GLTF image at index {}", image_index))
+            })
+            .collect()
+    }
+
+    /// Returns `true` if `image` is stored in its own file rather than in a buffer or a data
+    /// URI.
+    fn is_external_image(image: &gltf::Image<'_>) -> bool {
+        match image.source() {
+            gltf::image::Source::Uri { uri, .. } => !uri.starts_with("data:"),
+            gltf::image::Source::View { .. } => false,
+        }
+    }
+
     fn dynamic_image_from_gltf(data: gltf::image::Data) -> anyhow::Result<image::DynamicImage> {
         let (width, height, format) = (data.width, data.height, data.format);
         let buffer_error = || {
@@ -510,11 +616,19 @@ impl GLTFLoader {
         Ok(image)
     }
 
+    /// Loads one primitive as the sub-asset `label`, baked under that item of `bake_key`.
     fn load_primitive(
         buffers: &[Data],
         gltf_primitive: &Primitive,
-        asset_server: &AssetServer,
+        label: &str,
+        bake_key: &BakeKey,
+        load_context: &mut AssetLoadContext,
     ) -> anyhow::Result<AssetHandle<Mesh>> {
+        let bake_key = bake_key.item(label);
+        if let Some(primitive) = load_context.baked::<Mesh>(&bake_key) {
+            return Ok(load_context.add_labeled(label, primitive));
+        }
+
         let mut primitive = Mesh {
             vertices: Vec::new(),
             indices: Vec::new(),
@@ -610,7 +724,8 @@ impl GLTFLoader {
             }
         }
 
-        Ok(asset_server.add(primitive))
+        load_context.bake(&bake_key, &primitive);
+        Ok(load_context.add_labeled(label, primitive))
     }
 
     fn extract_node(gltf_node: Node) -> GLTFNode {
@@ -859,7 +974,7 @@ pub(crate) fn spawn_gltf_components(
                 {
                     cmd.insert(
                         Camera {
-                            fovy: gltf_camera.fovy,
+                            projection: gltf_camera.projection,
                             znear: gltf_camera.znear,
                             zfar: gltf_camera.zfar,
                             ..Camera::default()
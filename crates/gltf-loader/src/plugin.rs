@@ -8,7 +8,7 @@ pub struct GLTFPlugin;
 
 impl Plugin for GLTFPlugin {
     fn build(&self, app: &mut app::App) {
-        app.register_asset::<GLTFScene>();
+        app.register_loadable_asset::<GLTFScene>();
         app.add_system(UpdateGroup::Update, spawn_gltf_components);
     }
 }
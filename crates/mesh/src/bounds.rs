@@ -0,0 +1,299 @@
+use ecs::Component;
+use glam::{Mat4, Vec3, Vec4, Vec4Swizzles};
+
+use crate::{mesh::Mesh, vertex::Vertex};
+
+/// An axis-aligned bounding box.
+///
+/// As a component, it is the world-space bounds of a mesh entity, kept up to
+/// date by the renderer from the entity's `GlobalTransform` (and, for skinned
+/// meshes, its bones).
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// The smallest box containing every point, or `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |aabb, point| {
+            Self::new(aabb.min.min(point), aabb.max.max(point))
+        }))
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(self.min.min(other.min), self.max.max(other.max))
+    }
+
+    /// The smallest axis-aligned box containing this one once transformed by
+    /// `matrix`.
+    pub fn transformed(&self, matrix: Mat4) -> Self {
+        let center = matrix.transform_point3(self.center());
+        let half_extents = self.half_extents();
+        let half_extents = matrix.x_axis.xyz().abs() * half_extents.x
+            + matrix.y_axis.xyz().abs() * half_extents.y
+            + matrix.z_axis.xyz().abs() * half_extents.z;
+        Self::new(center - half_extents, center + half_extents)
+    }
+}
+
+/// A sphere containing a set of points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingSphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+/// The local-space bounds of a [`Mesh`]'s vertices.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshBounds {
+    pub aabb: Aabb,
+    /// Centred on [`aabb`](Self::aabb), which usually fits tighter than the
+    /// smallest enclosing sphere would but is much cheaper to find.
+    pub sphere: BoundingSphere,
+}
+
+impl Mesh {
+    /// Returns the bounds of the vertices in bind pose, or `None` for a mesh
+    /// without any.
+    ///
+    /// Skinned meshes move away from their bind pose; see
+    /// [`compute_joint_bounds`](Self::compute_joint_bounds) for them.
+    pub fn compute_bounds(&self) -> Option<MeshBounds> {
+        let aabb = Aabb::from_points(self.positions())?;
+        let center = aabb.center();
+        let radius = self
+            .positions()
+            .map(|position| position.distance_squared(center))
+            .fold(0.0, f32::max)
+            .sqrt();
+
+        Some(MeshBounds {
+            aabb,
+            sphere: BoundingSphere { center, radius },
+        })
+    }
+
+    /// Returns, for each joint of a skeleton with `inverse_bindposes`, the
+    /// bounds of the vertices it influences in that joint's space, or `None`
+    /// for joints influencing none.
+    ///
+    /// A skinned vertex is a weighted average of its position carried along
+    /// by each of its joints, so it always lies within the union of these
+    /// bounds transformed by their joints' current transforms.
+    pub fn compute_joint_bounds(&self, inverse_bindposes: &[Mat4]) -> Vec<Option<Aabb>> {
+        let mut bounds: Vec<Option<Aabb>> = vec![None; inverse_bindposes.len()];
+
+        for vertex in &self.vertices {
+            let position = Vec3::from(vertex.pos_coords);
+            for influence in 0..Vertex::MAX_AFFECTED_BONES {
+                let joint = vertex.bone_indices[influence] as usize;
+                if vertex.bone_weights[influence] <= 0.0 || joint >= inverse_bindposes.len() {
+                    continue;
+                }
+
+                let local = inverse_bindposes[joint].transform_point3(position);
+                let point = Aabb::new(local, local);
+                bounds[joint] = Some(match bounds[joint] {
+                    Some(aabb) => aabb.union(&point),
+                    None => point,
+                });
+            }
+        }
+
+        bounds
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Vec3> + '_ {
+        self.vertices
+            .iter()
+            .map(|vertex| Vec3::from(vertex.pos_coords))
+    }
+}
+
+/// The six planes of a view volume, each facing inwards.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Frustum {
+    /// `xyz` is the unit normal and `w` the distance, so a point `p` is on the
+    /// inner side of a plane when `normal.dot(p) + w >= 0`.
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extracts the planes of the volume a wgpu view-projection matrix maps
+    /// into clip space, with depth from 0 to 1.
+    pub fn from_view_proj(view_proj: Mat4) -> Self {
+        let x = view_proj.row(0);
+        let y = view_proj.row(1);
+        let z = view_proj.row(2);
+        let w = view_proj.row(3);
+
+        let planes = [w + x, w - x, w + y, w - y, z, w - z].map(|plane| {
+            let length = plane.xyz().length();
+            if length > 0.0 {
+                plane / length
+            } else {
+                plane
+            }
+        });
+
+        Self { planes }
+    }
+
+    /// Returns `false` only if `aabb` is entirely outside the frustum. Boxes
+    /// near a corner of the frustum may be reported as intersecting it
+    /// without doing so.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let center = aabb.center();
+        let half_extents = aabb.half_extents();
+        self.planes.iter().all(|plane| {
+            let normal = plane.xyz();
+            normal.dot(center) + normal.abs().dot(half_extents) + plane.w >= 0.0
+        })
+    }
+
+    /// Returns `false` only if `sphere` is entirely outside the frustum, with
+    /// the same caveat as [`intersects_aabb`](Self::intersects_aabb).
+    pub fn intersects_sphere(&self, sphere: &BoundingSphere) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.xyz().dot(sphere.center) + plane.w >= -sphere.radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_box_at(center: Vec3) -> Aabb {
+        Aabb::new(center - Vec3::ONE, center + Vec3::ONE)
+    }
+
+    #[test]
+    fn transformed_box_contains_the_rotated_box() {
+        let aabb = Aabb::new(Vec3::new(-1.0, -2.0, -3.0), Vec3::new(1.0, 2.0, 3.0));
+        let matrix = Mat4::from_translation(Vec3::new(10.0, 0.0, 0.0))
+            * Mat4::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        let transformed = aabb.transformed(matrix);
+        assert!(transformed
+            .min
+            .abs_diff_eq(Vec3::new(8.0, -1.0, -3.0), 1e-5));
+        assert!(transformed.max.abs_diff_eq(Vec3::new(12.0, 1.0, 3.0), 1e-5));
+    }
+
+    #[test]
+    fn transformed_box_grows_to_fit_a_diagonal_rotation() {
+        let aabb = unit_box_at(Vec3::ZERO);
+        let transformed = aabb.transformed(
+            Mat4::from_rotation_y(std::f32::consts::FRAC_PI_4) * Mat4::from_scale(Vec3::splat(2.0)),
+        );
+        let half_width = 2.0 * std::f32::consts::SQRT_2;
+        assert!(transformed
+            .max
+            .abs_diff_eq(Vec3::new(half_width, 2.0, half_width), 1e-5));
+        assert!(transformed.min.abs_diff_eq(-transformed.max, 1e-5));
+    }
+
+    fn camera_frustum() -> Frustum {
+        // Looking down -Z from the origin, seeing 1..10 units ahead.
+        Frustum::from_view_proj(Mat4::perspective_rh(
+            std::f32::consts::FRAC_PI_2,
+            1.0,
+            1.0,
+            10.0,
+        ))
+    }
+
+    #[test]
+    fn frustum_planes_are_normalized_and_face_inwards() {
+        let frustum = camera_frustum();
+        let inside = Vec3::new(0.0, 0.0, -5.0);
+        for plane in frustum.planes {
+            assert!((plane.xyz().length() - 1.0).abs() < 1e-5, "{plane}");
+            assert!(plane.xyz().dot(inside) + plane.w > 0.0, "{plane}");
+        }
+    }
+
+    #[test]
+    fn boxes_outside_any_plane_are_culled() {
+        let frustum = camera_frustum();
+        assert!(frustum.intersects_aabb(&unit_box_at(Vec3::new(0.0, 0.0, -5.0))));
+        // Straddling the near plane and the left edge.
+        assert!(frustum.intersects_aabb(&unit_box_at(Vec3::new(0.0, 0.0, -0.5))));
+        assert!(frustum.intersects_aabb(&unit_box_at(Vec3::new(-5.5, 0.0, -5.0))));
+
+        assert!(!frustum.intersects_aabb(&unit_box_at(Vec3::new(0.0, 0.0, 5.0))));
+        assert!(!frustum.intersects_aabb(&unit_box_at(Vec3::new(0.0, 0.0, -12.0))));
+        assert!(!frustum.intersects_aabb(&unit_box_at(Vec3::new(-8.0, 0.0, -5.0))));
+        assert!(!frustum.intersects_aabb(&unit_box_at(Vec3::new(0.0, 8.0, -5.0))));
+    }
+
+    #[test]
+    fn spheres_outside_any_plane_are_culled() {
+        let frustum = camera_frustum();
+        let sphere = |center, radius| BoundingSphere { center, radius };
+        assert!(frustum.intersects_sphere(&sphere(Vec3::new(0.0, 0.0, -5.0), 0.5)));
+        assert!(frustum.intersects_sphere(&sphere(Vec3::new(0.0, 0.0, -11.0), 1.5)));
+        assert!(frustum.intersects_sphere(&sphere(Vec3::new(6.0, 0.0, -5.0), 1.0)));
+
+        assert!(!frustum.intersects_sphere(&sphere(Vec3::new(0.0, 0.0, -11.0), 0.5)));
+        assert!(!frustum.intersects_sphere(&sphere(Vec3::new(0.0, 0.0, 2.0), 0.5)));
+        assert!(!frustum.intersects_sphere(&sphere(Vec3::new(0.0, -8.0, -5.0), 1.0)));
+    }
+
+    fn skinned_vertex(position: Vec3, bone_indices: [u32; 4], bone_weights: [f32; 4]) -> Vertex {
+        Vertex {
+            pos_coords: position.into(),
+            bone_indices,
+            bone_weights,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn joint_bounds_cover_influenced_vertices_in_joint_space() {
+        let mesh = Mesh {
+            vertices: vec![
+                skinned_vertex(Vec3::new(1.0, 0.0, 0.0), [0, 1, 0, 0], [0.5, 0.5, 0.0, 0.0]),
+                skinned_vertex(Vec3::new(3.0, 2.0, 0.0), [0, 0, 0, 0], [1.0, 0.0, 0.0, 0.0]),
+                // Zero weights and out-of-range joints influence nothing.
+                skinned_vertex(Vec3::new(9.0, 9.0, 9.0), [1, 5, 0, 0], [0.0, 1.0, 0.0, 0.0]),
+            ],
+            indices: Vec::new(),
+        };
+        let inverse_bindposes = [
+            Mat4::IDENTITY,
+            Mat4::from_translation(Vec3::new(-1.0, 0.0, 0.0)),
+            Mat4::IDENTITY,
+        ];
+
+        let bounds = mesh.compute_joint_bounds(&inverse_bindposes);
+        assert_eq!(
+            bounds,
+            vec![
+                Some(Aabb::new(
+                    Vec3::new(1.0, 0.0, 0.0),
+                    Vec3::new(3.0, 2.0, 0.0)
+                )),
+                Some(Aabb::new(Vec3::ZERO, Vec3::ZERO)),
+                None,
+            ]
+        );
+    }
+}
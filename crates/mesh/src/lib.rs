@@ -1,7 +1,9 @@
+pub mod bounds;
 pub mod mesh;
 pub mod skeleton;
 pub mod vertex;
 
+pub use bounds::{Aabb, BoundingSphere, Frustum, MeshBounds};
 pub use mesh::{Mesh, MeshComponent};
 pub use skeleton::{Skeleton, SkeletonComponent};
 pub use vertex::Vertex;
@@ -1,5 +1,10 @@
 use ecs::Component;
-use essential::assets::{handle::AssetHandle, Asset};
+use essential::assets::{
+    bake::{Bake, BakeReader, BakeWriter},
+    handle::AssetHandle,
+    Asset,
+};
+use facet::Facet;
 use glam::{Vec2, Vec3};
 
 use crate::vertex::Vertex;
@@ -105,7 +110,24 @@ impl Mesh {
     }
 }
 
-#[derive(Component)]
+/// Stores the vertices in their GPU-ready interleaved layout, so restoring one skips the
+/// normal and tangent computation.
+impl Bake for Mesh {
+    fn bake(&self, writer: &mut BakeWriter) -> anyhow::Result<()> {
+        writer.write_slice(&self.vertices);
+        writer.write_slice(&self.indices);
+        Ok(())
+    }
+
+    fn unbake(reader: &mut BakeReader<'_>) -> anyhow::Result<Self> {
+        Ok(Self {
+            vertices: reader.read_vec()?,
+            indices: reader.read_vec()?,
+        })
+    }
+}
+
+#[derive(Component, Facet)]
 pub struct MeshComponent {
     pub handle: AssetHandle<Mesh>,
 }
@@ -3,4 +3,102 @@ pub mod obj_loader;
 pub mod plugin;
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use std::time::Duration;
+
+    use ecs::world::World;
+    use essential::assets::{
+        Asset, AssetPath,
+        asset_server::{AssetServer, handle_asset_load_events},
+        asset_store::AssetStore,
+        bake::{BakeCache, BakeKey, content_hash},
+        source::MemorySource,
+        status::AssetStatus,
+    };
+    use render::assets::{material::StandardMaterial, mesh::Mesh, vertex::Vertex};
+
+    use crate::{mtl_loader::MTLMaterial, obj_loader::OBJAsset};
+
+    const TRIANGLE_OBJ: &str = "mtllib triangle.mtl
+v 0 0 0
+v 1 0 0
+v 0 1 0
+usemtl red
+f 1 2 3
+";
+
+    const TRIANGLE_MTL: &str = "newmtl red
+Kd 1 0 0
+";
+
+    fn register<A: Asset>(world: &mut World, server: &mut AssetServer) {
+        let store = AssetStore::<A>::new();
+        server.register_asset(&store);
+        world.insert_resource(store);
+    }
+
+    /// A server reading `memory://models/triangle.obj` and its MTL from memory.
+    fn setup() -> (World, AssetServer) {
+        let mut world = World::new();
+        let mut server = AssetServer::new();
+        register::<OBJAsset>(&mut world, &mut server);
+        register::<MTLMaterial>(&mut world, &mut server);
+        register::<Mesh>(&mut world, &mut server);
+        register::<StandardMaterial>(&mut world, &mut server);
+        world.insert_resource(server.clone());
+
+        let memory = MemorySource::new();
+        memory.insert("models/triangle.obj", TRIANGLE_OBJ);
+        memory.insert("models/triangle.mtl", TRIANGLE_MTL);
+        server.register_source("memory", memory);
+        (world, server)
+    }
+
+    /// Loads the triangle and returns the index count of its only mesh.
+    fn load_triangle(world: &mut World, server: &AssetServer) -> usize {
+        let handle = server.load::<OBJAsset>("memory://models/triangle.obj");
+        for _ in 0..500 {
+            handle_asset_load_events(world);
+            if server.status(handle.id()) != Some(AssetStatus::Loading) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(server.status(handle.id()), Some(AssetStatus::Loaded));
+
+        let obj = world
+            .get_resource::<AssetStore<OBJAsset>>()
+            .unwrap()
+            .get(&handle)
+            .unwrap();
+        assert_eq!(obj.meshes().len(), 1);
+        let meshes = world.get_resource::<AssetStore<Mesh>>().unwrap();
+        meshes.get(&obj.meshes()[0].handle).unwrap().indices.len()
+    }
+
+    #[test]
+    fn loads_an_obj_and_its_mtl_from_a_memory_source() {
+        let (mut world, server) = setup();
+        assert_eq!(load_triangle(&mut world, &server), 3);
+    }
+
+    #[test]
+    fn uses_a_fresh_bake_instead_of_the_importer() {
+        let (mut world, server) = setup();
+        let cache_dir = std::env::temp_dir().join(format!("obj-bake-{}", std::process::id()));
+        let cache = BakeCache::new(&cache_dir);
+
+        // Stands in for the triangle, so a load that returns it must have read the bake.
+        let path = AssetPath::new("memory://models/triangle.obj");
+        let key = BakeKey::new(&path, content_hash(TRIANGLE_OBJ.as_bytes())).item("mesh/0");
+        let baked = Mesh {
+            vertices: vec![Vertex::default(); 3],
+            indices: vec![0, 1, 2, 2, 1, 0],
+        };
+        cache.insert(&key, &baked).unwrap();
+        server.set_bake_cache(cache);
+
+        assert_eq!(load_triangle(&mut world, &server), 6);
+        std::fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
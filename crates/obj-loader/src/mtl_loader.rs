@@ -5,7 +5,7 @@ use async_trait::async_trait;
 use color::Color;
 use essential::assets::{
     Asset, AssetPath, LoadableAsset, asset_loader::AssetLoader, asset_server::AssetLoadContext,
-    handle::AssetHandle, utils::load_to_string,
+    handle::AssetHandle,
 };
 
 use render::assets::{
@@ -25,6 +25,10 @@ impl LoadableAsset for MTLMaterial {
     }
 
     fn default_usage_settings() -> Self::UsageSettings {}
+
+    fn extensions() -> &'static [&'static str] {
+        &["mtl"]
+    }
 }
 
 pub(crate) struct MTLLoader;
@@ -40,7 +44,7 @@ impl AssetLoader for MTLLoader {
         load_context: &mut AssetLoadContext,
         _usage_setting: <Self::Asset as LoadableAsset>::UsageSettings,
     ) -> anyhow::Result<Self::Asset> {
-        let obj_text = load_to_string(path.clone()).await?;
+        let obj_text = load_context.read_to_string(&path).await?;
         let obj_cursor = Cursor::new(obj_text);
         let (mats, _) = tobj::load_mtl_buf(&mut BufReader::new(obj_cursor))
             .with_context(|| format!("failed to parse MTL file '{}'", path.to_path().display()))?;
@@ -48,18 +52,16 @@ impl AssetLoader for MTLLoader {
         let mut material = StandardMaterial::new(None, None);
         for m in mats {
             if let Some(diffuse_texture) = m.diffuse_texture {
-                let texture_handle = load_context.asset_server().load::<Texture>(diffuse_texture);
+                let texture_handle = load_context.load::<Texture>(path.resolve(&diffuse_texture));
                 material.set_base_color_texture(texture_handle);
             }
 
             if let Some(normal_texture) = m.normal_texture {
                 // Normal maps store directions, not colors; load linear.
-                let texture_handle = load_context
-                    .asset_server()
-                    .load_with_usage_settings::<Texture>(
-                        normal_texture,
-                        TextureUsageSettings::linear(),
-                    );
+                let texture_handle = load_context.load_with_usage_settings::<Texture>(
+                    path.resolve(&normal_texture),
+                    TextureUsageSettings::linear(),
+                );
                 material.set_normal_texture(texture_handle);
             }
 
@@ -75,7 +77,7 @@ impl AssetLoader for MTLLoader {
         }
 
         Ok(MTLMaterial {
-            material: load_context.asset_server().add(material),
+            material: load_context.add_labeled("material", material),
         })
     }
 }
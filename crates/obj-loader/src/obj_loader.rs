@@ -7,8 +7,12 @@ use ecs::{
 };
 use essential::{
     assets::{
-        Asset, AssetPath, LoadableAsset, asset_loader::AssetLoader, asset_server::AssetLoadContext,
-        asset_store::AssetStore, handle::AssetHandle, utils::load_to_string,
+        Asset, AssetPath, LoadableAsset,
+        asset_loader::AssetLoader,
+        asset_server::AssetLoadContext,
+        asset_store::AssetStore,
+        bake::{BakeKey, content_hash},
+        handle::AssetHandle,
     },
     transform::Transform,
 };
@@ -52,6 +56,10 @@ impl LoadableAsset for OBJAsset {
     }
 
     fn default_usage_settings() -> Self::UsageSettings {}
+
+    fn extensions() -> &'static [&'static str] {
+        &["obj"]
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -66,15 +74,10 @@ impl AssetLoader for OBJLoader {
         load_context: &mut AssetLoadContext,
         _usage_setting: <Self::Asset as LoadableAsset>::UsageSettings,
     ) -> anyhow::Result<Self::Asset> {
-        let obj_text = load_to_string(path.clone()).await?;
+        let obj_text = load_context.read_to_string(&path).await?;
+        let bake_key = BakeKey::new(&path, content_hash(obj_text.as_bytes()));
         let obj_cursor = Cursor::new(obj_text);
 
-        let obj_parent = path
-            .to_path()
-            .parent()
-            .context("OBJ path has no parent directory")?
-            .to_path_buf();
-
         let mat_handles = BufReader::new(obj_cursor.clone())
             .lines()
             .map_while(Result::ok)
@@ -82,8 +85,7 @@ impl AssetLoader for OBJLoader {
                 if line.starts_with("mtllib") {
                     let parts: Vec<&str> = line.split_whitespace().collect();
                     if parts.len() > 1 {
-                        let mtl_path = obj_parent.join(parts[1]);
-                        Some(load_context.asset_server().load::<MTLMaterial>(mtl_path))
+                        Some(load_context.load::<MTLMaterial>(path.resolve(parts[1])))
                     } else {
                         None
                     }
@@ -107,7 +109,17 @@ impl AssetLoader for OBJLoader {
 
         let meshes = models
             .iter()
-            .map(|m: &Model| {
+            .enumerate()
+            .map(|(index, m): (usize, &Model)| {
+                let mesh_label = format!("mesh/{index}");
+                let mesh_key = bake_key.item(&mesh_label);
+                if let Some(mesh) = load_context.baked::<Mesh>(&mesh_key) {
+                    return OBJMesh {
+                        handle: load_context.add_labeled(&mesh_label, mesh),
+                        material_index: m.mesh.material_id,
+                    };
+                }
+
                 let mut requires_normal_computation = false;
                 let mut vertices = (0..m.mesh.positions.len() / 3)
                     .map(|vertex_index| {
@@ -160,7 +172,8 @@ impl AssetLoader for OBJLoader {
                 }
 
                 mesh.compute_tangents();
-                let handle = load_context.asset_server().add(mesh);
+                load_context.bake(&mesh_key, &mesh);
+                let handle = load_context.add_labeled(&mesh_label, mesh);
 
                 OBJMesh {
                     handle,
@@ -10,8 +10,8 @@ pub struct OBJPlugin;
 
 impl Plugin for OBJPlugin {
     fn build(&self, app: &mut app::App) {
-        app.register_asset::<OBJAsset>()
-            .register_asset::<MTLMaterial>();
+        app.register_loadable_asset::<OBJAsset>()
+            .register_loadable_asset::<MTLMaterial>();
         app.add_system(UpdateGroup::Update, spawn_obj_component);
     }
 }
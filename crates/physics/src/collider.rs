@@ -1,4 +1,5 @@
-use ecs::component::{Component, ComponentLifecycleCallback};
+use ecs::component::Component;
+use ecs::observer::{OnRemove, Trigger};
 use ecs::{CommandQueue, Entity, Query, ResMut, Without};
 
 use essential::transform::GlobalTransform;
@@ -75,33 +76,28 @@ impl Component for Collider {
     fn name() -> &'static str {
         "Collider"
     }
+}
 
-    fn on_add() -> Option<ComponentLifecycleCallback> {
-        None
-    }
-
-    fn on_remove() -> Option<ComponentLifecycleCallback> {
-        Some(|mut world, context| {
-            // `remove_component::<Collider>` fires this after the Collider is
-            // gone; the body id survives on its own `BodyId` component.
-            if let Some(&body) = world.get_component_for_entity::<BodyId>(context.entity) {
-                if let Some(state) = world.get_resource_mut::<PhysicsState>() {
-                    state.destroy_body(body);
-                    state.unregister_body_entity(body);
-                }
-
-                world.remove_component::<BodyId>(context.entity, true);
-                // Without a body there are no more fixed-step poses; left in
-                // place the interpolator would keep rewriting the Transform
-                // from stale history.
-                if world
-                    .get_component_for_entity::<TransformInterpolation>(context.entity)
-                    .is_some()
-                {
-                    world.remove_component::<TransformInterpolation>(context.entity, true);
-                }
-            }
-        })
+/// Destroys the body of an entity losing its [`Collider`], whether it is
+/// removed or the entity despawned.
+pub fn collider_removed(
+    trigger: Trigger<OnRemove<Collider>>,
+    bodies: Query<(&BodyId, Option<&TransformInterpolation>)>,
+    mut physics_state: ResMut<PhysicsState>,
+    mut cmd: CommandQueue,
+) {
+    let entity = trigger.entity();
+    let Some((&body, interpolation)) = bodies.get_entity(entity) else {
+        return;
+    };
+    physics_state.destroy_body(body);
+    physics_state.unregister_body_entity(body);
+
+    cmd.remove::<BodyId>(entity);
+    // Without a body there are no more fixed-step poses; left in place the
+    // interpolator would keep rewriting the Transform from stale history.
+    if interpolation.is_some() {
+        cmd.remove::<TransformInterpolation>(entity);
     }
 }
 
@@ -68,7 +68,7 @@ mod tests {
     use essential::{time::Time, transform::Transform};
     use glam::Vec3;
 
-    use crate::collider::{register_colliders, Collider};
+    use crate::collider::{collider_removed, register_colliders, Collider};
     use crate::physics_pipeline::PhysicsPipeline;
     use crate::physics_state::PhysicsState;
     use crate::rigid_body::RigidBody;
@@ -90,7 +90,7 @@ mod tests {
     #[test]
     fn transform_blends_between_fixed_steps() {
         let mut world = World::new();
-        world.register_component_lifetimes::<Collider>();
+        world.add_observer(collider_removed);
         // Inserts the GlobalTransform that `register_colliders` reads.
         world.register_component_lifetimes::<Transform>();
         world.insert_resource(PhysicsState::new());
@@ -124,6 +124,7 @@ mod tests {
             "sphere should have fallen during the fixed step"
         );
 
+        let fixed_delta_time = world.get_resource::<Time>().unwrap().fixed_delta_time();
         let mut y_at = |overstep: f32| {
             world
                 .get_resource_mut::<Time>()
@@ -138,8 +139,8 @@ mod tests {
         };
 
         let y_start = y_at(0.0);
-        let y_mid = y_at(Time::fixed_delta_time() / 2.0);
-        let y_end = y_at(Time::fixed_delta_time());
+        let y_mid = y_at(fixed_delta_time / 2.0);
+        let y_end = y_at(fixed_delta_time);
 
         assert!(
             (y_start - start.y).abs() < 1e-6,
@@ -162,7 +163,7 @@ mod tests {
     #[test]
     fn stepping_preserves_transform_scale() {
         let mut world = World::new();
-        world.register_component_lifetimes::<Collider>();
+        world.add_observer(collider_removed);
         // Inserts the GlobalTransform that `register_colliders` reads.
         world.register_component_lifetimes::<Transform>();
         world.insert_resource(PhysicsState::new());
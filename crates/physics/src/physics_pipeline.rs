@@ -1,5 +1,4 @@
 use ecs::resource::Resource;
-use essential::time::Time;
 
 use crate::backend::PhysicsBackend;
 use crate::physics_state::PhysicsState;
@@ -21,11 +20,11 @@ impl PhysicsPipeline {
         }
     }
 
-    /// Advances `state` by one fixed timestep.
-    pub fn step(&mut self, state: &mut PhysicsState) {
+    /// Advances `state` by one fixed timestep of `fixed_delta_time` seconds.
+    pub fn step(&mut self, state: &mut PhysicsState, fixed_delta_time: f32) {
         state
             .backend_mut()
-            .step(&mut self.stepper, Time::fixed_delta_time());
+            .step(&mut self.stepper, fixed_delta_time);
     }
 }
 
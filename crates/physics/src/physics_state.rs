@@ -58,7 +58,7 @@ impl PhysicsState {
     /// Creates a body with the given shape at `transform`'s position and
     /// rotation and adds it to the simulation: dynamic with `rigid_body`'s
     /// parameters when it is `Some`, static otherwise. Called by
-    /// `Collider::on_add`.
+    /// `register_colliders`.
     pub(crate) fn create_body(
         &mut self,
         collider: &Collider,
@@ -73,7 +73,7 @@ impl PhysicsState {
     }
 
     /// Removes `body` from the simulation and destroys it. Called by
-    /// `Collider::on_remove`.
+    /// the `OnRemove<Collider>` observer.
     pub(crate) fn destroy_body(&mut self, body: BodyId) {
         self.backend.destroy_body(body.0);
     }
@@ -2,7 +2,7 @@ use app::plugins::Plugin;
 use ecs::system::schedule::UpdateGroup;
 
 use crate::{
-    collider::{register_colliders, Collider},
+    collider::{collider_removed, register_colliders},
     ground::probe_ground,
     interpolation::interpolate_body_transforms,
     movement::apply_character_movement,
@@ -18,7 +18,7 @@ pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut app::App) {
-        app.register_component_lifecycle::<Collider>();
+        app.add_observer(collider_removed);
         app.register_reflection::<MeshCollider>();
         app.insert_resource(PhysicsPipeline::new())
             .insert_resource(PhysicsState::new())
@@ -100,11 +100,11 @@ pub(crate) fn generate_mesh_shapes(
 
 pub(crate) fn clean_shapes_for_dropped_meshes(
     mut mesh_shapes: ResMut<PhysicsMeshShapes>,
-    dropped_events: EventReader<AssetLifetimeEvent>,
+    mut dropped_events: EventReader<AssetLifetimeEvent>,
 ) {
     for e in dropped_events.read() {
         match e {
-            AssetLifetimeEvent::Dropped(asset_id, _) => {
+            AssetLifetimeEvent::Dropped(asset_id) => {
                 mesh_shapes.drop_shape(asset_id);
             }
         }
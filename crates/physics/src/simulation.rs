@@ -1,5 +1,8 @@
-use ecs::{query::Query, resource::ResMut};
-use essential::transform::Transform;
+use ecs::{
+    query::Query,
+    resource::{Res, ResMut},
+};
+use essential::{time::Time, transform::Transform};
 
 use crate::{
     body::BodyId, interpolation::TransformInterpolation, physics_pipeline::PhysicsPipeline,
@@ -10,10 +13,11 @@ pub fn step_simulation(
     query: Query<(&BodyId, &mut Transform, Option<&mut TransformInterpolation>)>,
     mut pipeline: ResMut<PhysicsPipeline>,
     mut state: ResMut<PhysicsState>,
+    time: Res<Time>,
 ) {
     {
         profiling::scope!("jolt::step");
-        pipeline.step(&mut state);
+        pipeline.step(&mut state, time.fixed_delta_time());
     }
 
     {
@@ -39,7 +43,7 @@ mod tests {
     use essential::transform::Transform;
     use glam::Vec3;
 
-    use crate::collider::{register_colliders, Collider};
+    use crate::collider::{collider_removed, register_colliders, Collider};
     use crate::physics_pipeline::PhysicsPipeline;
     use crate::physics_state::PhysicsState;
     use crate::rigid_body::RigidBody;
@@ -71,7 +75,7 @@ mod tests {
 
     fn drop_sphere_onto(mesh: mesh::Mesh) -> f32 {
         let mut world = World::new();
-        world.register_component_lifetimes::<Collider>();
+        world.add_observer(collider_removed);
         // Inserts the GlobalTransform that `register_colliders` reads.
         world.register_component_lifetimes::<Transform>();
         world.insert_resource(PhysicsState::new());
@@ -126,7 +130,7 @@ mod tests {
     #[test]
     fn collider_geometry_follows_transform_scale() {
         let mut world = World::new();
-        world.register_component_lifetimes::<Collider>();
+        world.add_observer(collider_removed);
         // Inserts the GlobalTransform that `register_colliders` reads.
         world.register_component_lifetimes::<Transform>();
         world.insert_resource(PhysicsState::new());
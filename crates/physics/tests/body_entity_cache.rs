@@ -31,7 +31,7 @@ fn body_id_tracks_component_lifecycle() {
         "register_colliders should insert a BodyId"
     );
 
-    // Removal still goes through `Collider::on_remove`.
+    // Removal goes through the `OnRemove<Collider>` observer.
     world.remove_component::<Collider>(entity);
     assert!(
         world.get_component_for_entity::<BodyId>(entity).is_none(),
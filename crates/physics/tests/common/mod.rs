@@ -5,18 +5,18 @@ use ecs::system::executor::single_thread::SingleThreadedExecutor;
 use ecs::system::schedule::Schedule;
 use ecs::world::World;
 use essential::transform::Transform;
-use physics::collider::{register_colliders, Collider};
+use physics::collider::{collider_removed, register_colliders};
 use physics::physics_state::PhysicsState;
 
-/// A world with the component lifecycles and resources `PhysicsPlugin` sets
-/// up in a real app.
+/// A world with the observers, component lifecycles and resources
+/// `PhysicsPlugin` sets up in a real app.
 ///
-/// `Transform`'s lifecycle matters as much as `Collider`'s: it creates the
-/// `GlobalTransform` that [`register_bodies`] poses bodies from, and without
-/// it colliders are skipped with no diagnostic.
+/// `Transform`'s lifecycle matters as much as the `Collider` observer: it
+/// creates the `GlobalTransform` that [`register_bodies`] poses bodies from,
+/// and without it colliders are skipped with no diagnostic.
 pub fn physics_world() -> World {
     let mut world = World::new();
-    world.register_component_lifetimes::<Collider>();
+    world.add_observer(collider_removed);
     world.register_component_lifetimes::<Transform>();
     world.insert_resource(PhysicsState::new());
     world
@@ -24,8 +24,8 @@ pub fn physics_world() -> World {
 
 /// Creates the physics bodies for every `Collider` spawned so far.
 ///
-/// Bodies come from the `register_colliders` system rather than `Collider`'s
-/// `on_add`, so spawning a `Collider` alone leaves the entity without a
+/// Bodies come from the `register_colliders` system rather than an
+/// `OnAdd<Collider>` observer, so spawning a `Collider` alone leaves the entity without a
 /// `BodyId`. Call this after spawning and before stepping or querying.
 pub fn register_bodies(world: &mut World) {
     let mut schedule = Schedule::new();
@@ -4,6 +4,7 @@
 
 mod common;
 use common::{physics_world, register_bodies};
+use essential::time::Time;
 
 use essential::transform::Transform;
 use glam::Vec3;
@@ -34,7 +35,10 @@ fn despawned_collider_stops_colliding() {
     // have gone to sleep yet (Jolt sleeps after ~0.5 s at rest, and the drop
     // alone takes ~0.8 s).
     for _ in 0..60 {
-        pipeline.step(world.get_resource_mut::<PhysicsState>().unwrap());
+        pipeline.step(
+            world.get_resource_mut::<PhysicsState>().unwrap(),
+            Time::DEFAULT_FIXED_DELTA_TIME,
+        );
     }
 
     let body = *world
@@ -66,7 +70,10 @@ fn despawned_collider_stops_colliding() {
 
     // With the floor gone, the sphere falls straight through where it was.
     for _ in 0..60 {
-        pipeline.step(world.get_resource_mut::<PhysicsState>().unwrap());
+        pipeline.step(
+            world.get_resource_mut::<PhysicsState>().unwrap(),
+            Time::DEFAULT_FIXED_DELTA_TIME,
+        );
     }
     let end_y = world
         .get_resource::<PhysicsState>()
@@ -2,6 +2,7 @@
 //! fall under gravity and come to rest on top of the floor. Bodies are created
 //! by spawning `Collider` components — never directly.
 
+use essential::time::Time;
 use essential::transform::Transform;
 use glam::{Quat, Vec3};
 mod common;
@@ -46,7 +47,10 @@ fn sphere_falls_and_rests_on_floor() {
 
     // Step ~3 seconds at 60 Hz.
     for _ in 0..180 {
-        pipeline.step(world.get_resource_mut::<PhysicsState>().unwrap());
+        pipeline.step(
+            world.get_resource_mut::<PhysicsState>().unwrap(),
+            Time::DEFAULT_FIXED_DELTA_TIME,
+        );
     }
 
     let end_y = world
@@ -94,7 +98,10 @@ fn capsule_with_locked_rotation_rests_upright() {
     register_bodies(&mut world);
 
     for _ in 0..180 {
-        pipeline.step(world.get_resource_mut::<PhysicsState>().unwrap());
+        pipeline.step(
+            world.get_resource_mut::<PhysicsState>().unwrap(),
+            Time::DEFAULT_FIXED_DELTA_TIME,
+        );
     }
 
     let body = *world
@@ -144,7 +151,10 @@ fn bottom_origin_capsule_rests_with_origin_at_floor_height() {
     register_bodies(&mut world);
 
     for _ in 0..180 {
-        pipeline.step(world.get_resource_mut::<PhysicsState>().unwrap());
+        pipeline.step(
+            world.get_resource_mut::<PhysicsState>().unwrap(),
+            Time::DEFAULT_FIXED_DELTA_TIME,
+        );
     }
 
     let body = *world
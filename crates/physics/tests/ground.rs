@@ -1,6 +1,7 @@
 //! Ground probing (`PhysicsState::probe_ground`) and body velocity control.
 
 use ecs::world::World;
+use essential::time::Time;
 use essential::transform::Transform;
 use glam::{Quat, Vec3};
 mod common;
@@ -18,7 +19,10 @@ const MAX_SLOPE: f32 = std::f32::consts::PI * 50.0 / 180.0;
 
 fn step(world: &mut World, pipeline: &mut PhysicsPipeline, steps: u32) {
     for _ in 0..steps {
-        pipeline.step(world.get_resource_mut::<PhysicsState>().unwrap());
+        pipeline.step(
+            world.get_resource_mut::<PhysicsState>().unwrap(),
+            Time::DEFAULT_FIXED_DELTA_TIME,
+        );
     }
 }
 
@@ -0,0 +1,88 @@
+//! `PhysicsPlugin` driven through a headless app: colliders become bodies, and
+//! `App::step` runs the simulation once per fixed step, whatever the frame rate.
+
+use std::time::Duration;
+
+use app::{
+    plugins::{AssetManagerPlugin, TimePlugin, TransformPlugin},
+    App,
+};
+use ecs::Entity;
+use essential::time::Time;
+use essential::transform::Transform;
+use glam::Vec3;
+use mesh::mesh::Mesh;
+use physics::collider::Collider;
+use physics::plugin::PhysicsPlugin;
+use physics::rigid_body::RigidBody;
+
+/// A sphere of radius 1 dropped from `y = 10` onto a floor whose top is at
+/// `y = 1`.
+fn dropped_sphere() -> (App, Entity) {
+    let mut app = App::new();
+    app.register_plugin(TimePlugin)
+        .register_plugin(TransformPlugin)
+        .register_plugin(AssetManagerPlugin)
+        .register_plugin(PhysicsPlugin);
+    // Mesh colliders read their shapes from the mesh store.
+    app.register_asset::<Mesh>();
+
+    let world = app.world_mut();
+    world.spawn((
+        Collider::cuboid(100.0, 1.0, 100.0),
+        Transform::from_translation(Vec3::ZERO),
+    ));
+    let sphere = world.spawn((
+        RigidBody::default(),
+        Collider::sphere(1.0),
+        Transform::from_translation(Vec3::new(0.0, 10.0, 0.0)),
+    ));
+
+    (app, sphere)
+}
+
+fn height(app: &App, entity: Entity) -> f32 {
+    app.world()
+        .get_component_for_entity::<Transform>(entity)
+        .unwrap()
+        .translation
+        .y
+}
+
+#[test]
+fn sphere_falls_and_rests_on_the_floor() {
+    let (mut app, sphere) = dropped_sphere();
+
+    app.step_fixed(1);
+    assert!((height(&app, sphere) - 10.0).abs() < 0.5);
+
+    // ~3 seconds of fixed steps.
+    app.step_fixed(90);
+    let end_y = height(&app, sphere);
+    assert!(
+        (end_y - 2.0).abs() < 0.5,
+        "sphere should rest near y = 2 (floor top + radius), was {end_y}"
+    );
+}
+
+#[test]
+fn the_fall_depends_on_simulated_time_not_frame_count() {
+    let (mut fixed, fixed_sphere) = dropped_sphere();
+    let (mut halves, halves_sphere) = dropped_sphere();
+    fixed.step_fixed(1);
+    halves.step_fixed(1);
+
+    let fixed_delta = Duration::from_secs_f32(Time::DEFAULT_FIXED_DELTA_TIME);
+    fixed.step_fixed(10);
+    for _ in 0..20 {
+        halves.step(fixed_delta / 2);
+    }
+
+    let fixed_y = height(&fixed, fixed_sphere);
+    let halves_y = height(&halves, halves_sphere);
+    assert!(fixed_y < 9.9, "sphere should be falling, was {fixed_y}");
+    assert!(
+        (fixed_y - halves_y).abs() < 1e-3,
+        "same simulated time should land at the same height: {fixed_y} vs {halves_y}"
+    );
+}
@@ -1,8 +1,9 @@
 use bytemuck::{Pod, Zeroable};
 use color::{Color, LinearRgba};
-use essential::assets::{handle::AssetHandle, Asset};
+use essential::assets::{handle::AssetHandle, Asset, Fingerprint};
 use glam::Vec3;
 use render_macros::AsBindGroup;
+use std::hash::{Hash, Hasher};
 
 use crate::{
     assets::texture::Texture,
@@ -312,6 +313,21 @@ impl StandardMaterial {
     }
 }
 
+impl Fingerprint for StandardMaterial {
+    fn fingerprint<H: Hasher>(&self, state: &mut H) {
+        for texture in [
+            &self.base_color_texture,
+            &self.normal_texture,
+            &self.metallic_roughness_texture,
+            &self.emissive_texture,
+            &self.occlusion_texture,
+        ] {
+            texture.as_ref().map(AssetHandle::id).hash(state);
+        }
+        bytemuck::bytes_of(&self.uniform).hash(state);
+    }
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // MaterialFlags / MaterialUniform (used by StandardMaterial)
 // ────────────────────────────────────────────────────────────────────────────
@@ -1,10 +1,15 @@
 use crate::loaders::texture_loader::TextureLoader;
-use anyhow::Context;
-use essential::assets::{Asset, LoadableAsset};
-use image::{DynamicImage, GenericImageView};
+use anyhow::{bail, Context};
+use essential::assets::{
+    bake::{Bake, BakeReader, BakeWriter},
+    Asset, LoadableAsset,
+};
+use image::DynamicImage;
+use std::hash::{Hash, Hasher};
 use wgpu::TextureUsages;
 use wgpu_types::{Extent3d, TextureDescriptor, TextureFormat, TextureViewDescriptor};
 
+#[derive(Clone)]
 pub struct TextureUsageSettings {
     pub texture_descriptor: TextureDescriptor<Option<&'static str>, &'static [TextureFormat]>,
     pub texture_view_descriptor: TextureViewDescriptor<Option<&'static str>>,
@@ -35,6 +40,24 @@ impl Default for TextureUsageSettings {
     }
 }
 
+// `TextureViewDescriptor` is not `Hash`, so its fields are hashed one by one.
+impl Hash for TextureUsageSettings {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.texture_descriptor.hash(state);
+
+        let view = &self.texture_view_descriptor;
+        view.label.hash(state);
+        view.format.hash(state);
+        view.dimension.hash(state);
+        view.usage.hash(state);
+        view.aspect.hash(state);
+        view.base_mip_level.hash(state);
+        view.mip_level_count.hash(state);
+        view.base_array_layer.hash(state);
+        view.array_layer_count.hash(state);
+    }
+}
+
 impl TextureUsageSettings {
     /// Settings for non-color data textures (normal maps, metallic-roughness,
     /// occlusion).  These must not be sRGB-decoded when sampled.
@@ -52,28 +75,21 @@ pub struct Texture {
 }
 
 impl Texture {
-    pub fn from_bytes(
-        bytes: &[u8],
-        mut usage_settings: TextureUsageSettings,
-    ) -> anyhow::Result<Self> {
+    pub fn from_bytes(bytes: &[u8], usage_settings: TextureUsageSettings) -> anyhow::Result<Self> {
         let img = image::load_from_memory(bytes).context("failed to decode image from bytes")?;
-        let dimensions = img.dimensions();
+        Ok(Self::from_dynamic_image(img).with_usage_settings(usage_settings))
+    }
 
+    /// Replaces the usage settings, keeping the texture's size unless they set one.
+    pub(crate) fn with_usage_settings(mut self, mut usage_settings: TextureUsageSettings) -> Self {
         if usage_settings.texture_descriptor.size.width == 0
             && usage_settings.texture_descriptor.size.height == 0
             && usage_settings.texture_descriptor.size.depth_or_array_layers == 0
         {
-            usage_settings.texture_descriptor.size = Extent3d {
-                width: dimensions.0,
-                height: dimensions.1,
-                depth_or_array_layers: 1,
-            };
+            usage_settings.texture_descriptor.size = *self.size();
         }
-
-        Ok(Texture {
-            data: img.to_rgba8().into_raw(),
-            usage_settings,
-        })
+        self.usage_settings = usage_settings;
+        self
     }
 
     /// Creates a GPU-only render target texture at the given resolution.
@@ -142,6 +158,43 @@ impl Texture {
     }
 }
 
+/// Stores the decoded RGBA8 pixels with their size and format, so restoring one skips image
+/// decoding.
+impl Bake for Texture {
+    fn bake(&self, writer: &mut BakeWriter) -> anyhow::Result<()> {
+        let format = match self.usage_settings.texture_descriptor.format {
+            TextureFormat::Rgba8UnormSrgb => 0,
+            TextureFormat::Rgba8Unorm => 1,
+            format => bail!("cannot bake {format:?} textures"),
+        };
+        let size = self.size();
+        writer.write_u32(format);
+        writer.write_u32(size.width);
+        writer.write_u32(size.height);
+        writer.write_u32(size.depth_or_array_layers);
+        writer.write_bytes(&self.data);
+        Ok(())
+    }
+
+    fn unbake(reader: &mut BakeReader<'_>) -> anyhow::Result<Self> {
+        let mut usage_settings = TextureUsageSettings::default();
+        usage_settings.texture_descriptor.format = match reader.read_u32()? {
+            0 => TextureFormat::Rgba8UnormSrgb,
+            1 => TextureFormat::Rgba8Unorm,
+            format => bail!("unknown baked texture format {format}"),
+        };
+        usage_settings.texture_descriptor.size = Extent3d {
+            width: reader.read_u32()?,
+            height: reader.read_u32()?,
+            depth_or_array_layers: reader.read_u32()?,
+        };
+        Ok(Self {
+            data: reader.read_bytes()?.to_vec(),
+            usage_settings,
+        })
+    }
+}
+
 impl LoadableAsset for Texture {
     type UsageSettings = TextureUsageSettings;
 
@@ -13,11 +13,19 @@ use ecs::{
     resource::Res,
 };
 use glam::{Mat4, Vec3};
+use mesh::bounds::Frustum;
 use wgpu::util::DeviceExt;
 
 use crate::{
-    assets::texture::Texture, components::render_entity::RenderEntity, device::RenderDevice,
-    layouts::CameraLayout, queue::RenderQueue, render_asset::render_texture::RenderTexture,
+    assets::texture::Texture,
+    components::{
+        projection::{Projection, ScalingMode},
+        render_entity::RenderEntity,
+    },
+    device::RenderDevice,
+    layouts::CameraLayout,
+    queue::RenderQueue,
+    render_asset::render_texture::RenderTexture,
     resources::RenderContext,
 };
 
@@ -31,6 +39,10 @@ pub const OPENGL_TO_WGPU_MATRIX: Mat4 = Mat4::from_cols_array(
     ]
 );
 
+/// Format of the HDR target every camera renders the scene into, before
+/// post-processing resolves it into the camera's [`RenderTarget`].
+pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
 #[allow(dead_code)]
 pub enum RenderTarget {
     MainWindow,
@@ -51,7 +63,7 @@ impl RenderTarget {
 #[derive(Component)]
 pub struct Camera {
     pub aspect: f32,
-    pub fovy: f32,
+    pub projection: Projection,
     pub znear: f32,
     pub zfar: f32,
     pub clear_color: Color,
@@ -63,14 +75,41 @@ impl Camera {
     /// Uses sensible defaults for near/far planes and clear colour.
     pub fn perspective(fovy: f32, aspect: f32) -> Self {
         Self {
-            fovy,
+            projection: Projection::perspective(fovy),
             aspect,
             ..Default::default()
         }
     }
 
+    /// Creates an orthographic camera showing the area `scaling` describes,
+    /// with the same defaults as [`Camera::perspective`].
+    pub fn orthographic(scaling: ScalingMode) -> Self {
+        Self {
+            projection: Projection::orthographic(scaling),
+            ..Default::default()
+        }
+    }
+
     pub fn build_projection_matrix(&self) -> Mat4 {
-        Mat4::perspective_rh(self.fovy, self.aspect, self.znear, self.zfar)
+        self.projection
+            .clip_from_view(self.aspect, self.znear, self.zfar)
+    }
+
+    /// Returns the world-space corners of the part of the view frustum between
+    /// `near` and `far` units in front of the camera: the near plane first,
+    /// then the far plane, each as bottom-left, bottom-right, top-right,
+    /// top-left.
+    pub fn frustum_corners(&self, world_from_view: Mat4, near: f32, far: f32) -> [Vec3; 8] {
+        let mut corners = [Vec3::ZERO; 8];
+        for (plane, distance) in [near, far].into_iter().enumerate() {
+            let quad =
+                self.projection
+                    .view_plane_corners(self.aspect, self.znear, self.zfar, distance);
+            for (i, corner) in quad.into_iter().enumerate() {
+                corners[plane * 4 + i] = world_from_view.transform_point3(corner);
+            }
+        }
+        corners
     }
 }
 
@@ -78,7 +117,7 @@ impl Default for Camera {
     fn default() -> Self {
         Self {
             aspect: 1.0,
-            fovy: std::f32::consts::FRAC_PI_4,
+            projection: Projection::default(),
             znear: 0.1,
             zfar: 100.0,
             clear_color: Color::rgba(0.118, 0.831, 0.922, 1.0),
@@ -92,6 +131,11 @@ impl Default for Camera {
 pub struct CameraUniform {
     view_pos: Vec3,
     view_proj: Mat4,
+    // World-space direction the camera looks in. Orthographic cameras view
+    // every point along it, rather than from `view_pos`.
+    view_forward: Vec3,
+    // `1` for an orthographic projection, `0` for a perspective one.
+    is_orthographic: u32,
 }
 
 impl CameraUniform {
@@ -99,12 +143,16 @@ impl CameraUniform {
         Self {
             view_pos: Vec3::ZERO,
             view_proj: Mat4::IDENTITY,
+            view_forward: Vec3::NEG_Z,
+            is_orthographic: 0,
         }
     }
 
     pub fn update_view_proj(&mut self, camera: &Camera, transform: &GlobalTransform) {
         self.view_pos = transform.translation();
         self.view_proj = camera.build_projection_matrix() * transform.matrix().inverse();
+        self.view_forward = transform.forward().normalize();
+        self.is_orthographic = camera.projection.is_orthographic() as u32;
     }
 }
 
@@ -121,14 +169,27 @@ pub struct RenderCamera {
     pub camera_uniform: CameraUniform,
     pub camera_buffer: wgpu::Buffer,
     pub(crate) depth_texture: RenderTexture,
+    // Linear scene color, resolved into `render_target` (or the window) by
+    // post-processing.
+    pub(crate) hdr_target: RenderTexture,
     pub render_target: Option<RenderTexture>,
+    /// World-space view volume, kept in step with `camera_uniform`.
+    pub(crate) frustum: Frustum,
 }
 
 impl RenderCamera {
+    pub fn frustum(&self) -> &Frustum {
+        &self.frustum
+    }
+
     pub fn depth_texture(&self) -> &RenderTexture {
         &self.depth_texture
     }
 
+    pub fn hdr_target(&self) -> &RenderTexture {
+        &self.hdr_target
+    }
+
     pub fn resize_render_target(
         &mut self,
         device: &wgpu::Device,
@@ -141,6 +202,7 @@ impl RenderCamera {
         }
         self.depth_texture =
             RenderTexture::create_depth_texture(device, width, height, "depth_texture");
+        self.hdr_target = create_hdr_target(device, width, height);
     }
 }
 
@@ -201,14 +263,17 @@ pub(crate) fn camera_added(
         };
         let depth_texture =
             RenderTexture::create_depth_texture(&device, depth_w, depth_h, "depth_texture");
+        let hdr_target = create_hdr_target(&device, depth_w, depth_h);
 
         let render_cam = RenderCamera {
             camera_bind_group,
             camera_uniform,
             camera_buffer,
             depth_texture,
+            hdr_target,
             clear_color: camera.clear_color.to_linear(),
             render_target,
+            frustum: Frustum::from_view_proj(camera_uniform.view_proj),
         };
 
         match render_entity {
@@ -250,9 +315,36 @@ pub fn create_rtt(
             | wgpu::TextureUsages::COPY_SRC,
         view_formats: &[],
     });
+    render_texture_with_linear_sampler(device, texture, "RTT Sampler")
+}
+
+/// Creates the [`HDR_FORMAT`] texture a camera renders the scene into.
+pub(crate) fn create_hdr_target(device: &wgpu::Device, width: u32, height: u32) -> RenderTexture {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Camera HDR Target"),
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: HDR_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    render_texture_with_linear_sampler(device, texture, "HDR Target Sampler")
+}
+
+fn render_texture_with_linear_sampler(
+    device: &wgpu::Device,
+    texture: wgpu::Texture,
+    label: &str,
+) -> RenderTexture {
     let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
     let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-        label: Some("RTT Sampler"),
+        label: Some(label),
         address_mode_u: wgpu::AddressMode::ClampToEdge,
         address_mode_v: wgpu::AddressMode::ClampToEdge,
         address_mode_w: wgpu::AddressMode::ClampToEdge,
@@ -299,6 +391,7 @@ pub(crate) fn camera_changed(
             render_camera
                 .camera_uniform
                 .update_view_proj(camera, transform);
+            render_camera.frustum = Frustum::from_view_proj(render_camera.camera_uniform.view_proj);
 
             let mut buffer = UniformBuffer::new(Vec::new());
             buffer.write(&render_camera.camera_uniform).unwrap();
@@ -307,3 +400,58 @@ pub(crate) fn camera_changed(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_corners(actual: [Vec3; 8], expected: [Vec3; 8]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(a.abs_diff_eq(e, 1e-4), "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn perspective_frustum_corners_widen_with_distance() {
+        let camera = Camera::perspective(std::f32::consts::FRAC_PI_2, 2.0);
+        let corners = camera.frustum_corners(Mat4::IDENTITY, 1.0, 10.0);
+        assert_corners(
+            corners,
+            [
+                Vec3::new(-2.0, -1.0, -1.0),
+                Vec3::new(2.0, -1.0, -1.0),
+                Vec3::new(2.0, 1.0, -1.0),
+                Vec3::new(-2.0, 1.0, -1.0),
+                Vec3::new(-20.0, -10.0, -10.0),
+                Vec3::new(20.0, -10.0, -10.0),
+                Vec3::new(20.0, 10.0, -10.0),
+                Vec3::new(-20.0, 10.0, -10.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn frustum_corners_follow_the_camera_transform() {
+        let camera = Camera::orthographic(ScalingMode::Fixed {
+            width: 4.0,
+            height: 2.0,
+        });
+        let world_from_view = Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0))
+            * Mat4::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let corners = camera.frustum_corners(world_from_view, 1.0, 3.0);
+        // Turned to look down -X, the camera's right is -Z.
+        assert_corners(
+            corners,
+            [
+                Vec3::new(4.0, -1.0, 2.0),
+                Vec3::new(4.0, -1.0, -2.0),
+                Vec3::new(4.0, 1.0, -2.0),
+                Vec3::new(4.0, 1.0, 2.0),
+                Vec3::new(2.0, -1.0, 2.0),
+                Vec3::new(2.0, -1.0, -2.0),
+                Vec3::new(2.0, 1.0, -2.0),
+                Vec3::new(2.0, 1.0, 2.0),
+            ],
+        );
+    }
+}
@@ -0,0 +1,401 @@
+//! Cascaded shadow maps for directional lights.
+//!
+//! A directional light with shadows enabled splits the active camera's view
+//! frustum into up to [`MAX_CASCADES`] slices by distance and renders one
+//! shadow map per slice, each fitted to its slice every frame. Near slices are
+//! small, so nearby shadows stay sharp, while far slices still cover the rest
+//! of the view. Add a [`CascadeShadowConfig`] next to the [`Light`] to tune
+//! the split; without one, [`CascadeShadowConfig::default()`] is used.
+
+use ecs::{command::CommandQueue, component::Component, entity::Entity, query::Query};
+use essential::transform::GlobalTransform;
+use glam::{Mat4, Vec3};
+
+use crate::components::{
+    camera::{Camera, RenderTarget},
+    light::{Light, LightType},
+};
+
+/// Upper bound on [`CascadeShadowConfig::cascade_count`]; every directional
+/// caster reserves this many layers in the cascade shadow-map texture.
+pub const MAX_CASCADES: usize = 4;
+
+// How far behind a cascade's bounding sphere, towards the light, its shadow
+// map still captures casters. Without it, anything between the light and the
+// slice — a tall building just out of view — would cast no shadow into it.
+const CASCADE_CASTER_MARGIN: f32 = 100.0;
+
+// Logarithmic splits are multiples of the near distance, so a camera with
+// `znear == 0` would collapse every cascade onto the camera.
+const MIN_SPLIT_NEAR: f32 = 1e-3;
+
+/// How the shadowed part of the view is divided between cascades.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CascadeSplit {
+    /// Every cascade covers the same depth range.
+    Uniform,
+    /// Each cascade covers the same ratio of far to near distance, which
+    /// matches how perspective shrinks texels with distance but leaves the
+    /// first cascade very thin.
+    Logarithmic,
+    /// A blend of [`Uniform`](Self::Uniform) (`lambda = 0`) and
+    /// [`Logarithmic`](Self::Logarithmic) (`lambda = 1`) splits.
+    Practical { lambda: f32 },
+    /// The far distance of each cascade, in world units from the camera.
+    /// Cascades past the end of the list are dropped.
+    Manual(Vec<f32>),
+}
+
+/// Configures the shadow cascades of a directional [`Light`].
+#[derive(Component, Clone, Debug)]
+pub struct CascadeShadowConfig {
+    /// Number of cascades, clamped to `1..=MAX_CASCADES`.
+    pub cascade_count: usize,
+    pub split: CascadeSplit,
+    /// Distance from the camera past which nothing is shadowed. Clamped to
+    /// the camera's far plane.
+    pub max_distance: f32,
+    /// Width and height of each cascade's shadow map, nearest cascade first.
+    /// Every directional light's cascades share one texture array sized for
+    /// the largest, and each renders into its own corner of its layer, so the
+    /// resolution can differ between cascades and between lights.
+    pub resolutions: [u32; MAX_CASCADES],
+    /// Fraction of each cascade, at its far end, over which it fades into the
+    /// next one so the change in resolution doesn't show as a seam.
+    pub blend: f32,
+}
+
+impl Default for CascadeShadowConfig {
+    fn default() -> Self {
+        Self {
+            cascade_count: MAX_CASCADES,
+            split: CascadeSplit::Practical { lambda: 0.6 },
+            max_distance: 100.0,
+            resolutions: [2048; MAX_CASCADES],
+            blend: 0.1,
+        }
+    }
+}
+
+impl CascadeShadowConfig {
+    pub fn with_cascade_count(mut self, cascade_count: usize) -> Self {
+        self.cascade_count = cascade_count;
+        self
+    }
+
+    pub fn with_split(mut self, split: CascadeSplit) -> Self {
+        self.split = split;
+        self
+    }
+
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    /// Gives every cascade the same resolution.
+    pub fn with_resolution(mut self, resolution: u32) -> Self {
+        self.resolutions = [resolution; MAX_CASCADES];
+        self
+    }
+
+    pub fn with_cascade_resolutions(mut self, resolutions: [u32; MAX_CASCADES]) -> Self {
+        self.resolutions = resolutions;
+        self
+    }
+
+    pub fn with_blend(mut self, blend: f32) -> Self {
+        self.blend = blend;
+        self
+    }
+
+    /// Returns the far distance of each cascade for a camera whose view
+    /// starts at `near`. The last one is `max_distance` (or `far`, if that is
+    /// closer) except with [`CascadeSplit::Manual`] splits. A `near` of zero
+    /// is treated as a small positive distance.
+    pub fn split_distances(&self, near: f32, far: f32) -> Vec<f32> {
+        let near = near.max(MIN_SPLIT_NEAR);
+        let far = self.max_distance.min(far).max(near);
+        let count = self.cascade_count.clamp(1, MAX_CASCADES);
+
+        let lambda = match &self.split {
+            CascadeSplit::Uniform => 0.0,
+            CascadeSplit::Logarithmic => 1.0,
+            CascadeSplit::Practical { lambda } => lambda.clamp(0.0, 1.0),
+            CascadeSplit::Manual(splits) => {
+                let mut previous = near;
+                return splits
+                    .iter()
+                    .take(count)
+                    .map(|&split| {
+                        previous = split.clamp(previous, far);
+                        previous
+                    })
+                    .collect();
+            }
+        };
+
+        (1..=count)
+            .map(|i| {
+                let t = i as f32 / count as f32;
+                let uniform = near + (far - near) * t;
+                let logarithmic = near * (far / near).powf(t);
+                uniform + (logarithmic - uniform) * lambda
+            })
+            .collect()
+    }
+}
+
+/// One slice of the camera frustum and the shadow projection fitted to it.
+#[derive(Clone, Copy, Debug)]
+pub struct Cascade {
+    /// Distance from the camera at which the slice starts.
+    pub near: f32,
+    /// Distance from the camera at which the slice ends.
+    pub far: f32,
+    /// The slice's eight world-space corners: the near plane first, then the
+    /// far plane, each as bottom-left, bottom-right, top-right, top-left.
+    pub corners: [Vec3; 8],
+    /// Projects world space into the cascade's shadow map.
+    pub view_proj: Mat4,
+    /// Width and height of the cascade's shadow map, in texels.
+    pub resolution: u32,
+}
+
+/// The cascades of a shadow-casting directional [`Light`], refitted to the
+/// active camera every frame by [`update_directional_cascades`].
+///
+/// The active camera is the first [`Camera`] rendering to the main window, or
+/// the first camera at all when none do.
+#[derive(Component, Clone, Debug)]
+pub struct DirectionalCascades {
+    pub(crate) cascades: Vec<Cascade>,
+    pub(crate) camera_position: Vec3,
+    pub(crate) camera_forward: Vec3,
+    pub(crate) blend: f32,
+}
+
+impl DirectionalCascades {
+    pub fn cascades(&self) -> &[Cascade] {
+        &self.cascades
+    }
+}
+
+/// Returns an orthographic projection along `direction` that contains the
+/// frustum slice `corners` and has `resolution` texels across.
+///
+/// The projection is fitted to the slice's bounding sphere rather than its
+/// box, so it keeps its size as the camera turns, and is moved in whole
+/// texels, so shadow edges don't shimmer as the camera moves.
+pub fn fit_cascade(corners: &[Vec3; 8], direction: Vec3, resolution: u32) -> Mat4 {
+    let center = corners.iter().sum::<Vec3>() / 8.0;
+    let radius = corners
+        .iter()
+        .map(|corner| corner.distance(center))
+        .fold(0.0, f32::max);
+    // Rounding keeps float noise in the corners from changing the texel size.
+    let radius = (radius * 16.0).ceil() / 16.0;
+
+    let view = Mat4::look_at_rh(Vec3::ZERO, direction, shadow_up_vector(direction));
+    let texel = 2.0 * radius / resolution.max(1) as f32;
+    let center = view.transform_point3(center);
+    let x = (center.x / texel).floor() * texel;
+    let y = (center.y / texel).floor() * texel;
+
+    let proj = Mat4::orthographic_rh(
+        x - radius,
+        x + radius,
+        y - radius,
+        y + radius,
+        -center.z - radius - CASCADE_CASTER_MARGIN,
+        -center.z + radius,
+    );
+    proj * view
+}
+
+// `Vec3::Y` degenerates as an up vector once `direction` is nearly parallel
+// to it (straight up/down directional or spot lights), so fall back to Z.
+pub(crate) fn shadow_up_vector(direction: Vec3) -> Vec3 {
+    if direction.dot(Vec3::Y).abs() > 0.999 {
+        Vec3::Z
+    } else {
+        Vec3::Y
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_directional_cascades(
+    lights: Query<(
+        Entity,
+        &Light,
+        &GlobalTransform,
+        Option<&CascadeShadowConfig>,
+        Option<&mut DirectionalCascades>,
+    )>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut cmd: CommandQueue,
+) {
+    let active_camera = cameras
+        .iter()
+        .find(|(camera, _)| matches!(camera.render_target, RenderTarget::MainWindow))
+        .or_else(|| cameras.iter().next());
+    let Some((camera, camera_transform)) = active_camera else {
+        return;
+    };
+
+    let world_from_view = camera_transform.matrix();
+    let camera_position = camera_transform.translation();
+    let camera_forward = (camera_transform.rotation() * Vec3::NEG_Z).normalize();
+
+    let default_config = CascadeShadowConfig::default();
+    for (entity, light, light_transform, config, cascades) in lights.iter() {
+        if !matches!(light.light_type, LightType::Directional) || !light.shadowmaps_enabled {
+            continue;
+        }
+
+        let config = config.unwrap_or(&default_config);
+        let direction = light_transform.rotation() * Vec3::NEG_Z;
+
+        let mut near = camera.znear;
+        let fitted = config
+            .split_distances(camera.znear, camera.zfar)
+            .into_iter()
+            .zip(config.resolutions)
+            .map(|(far, resolution)| {
+                let resolution = resolution.max(1);
+                let corners = camera.frustum_corners(world_from_view, near, far);
+                let cascade = Cascade {
+                    near,
+                    far,
+                    corners,
+                    view_proj: fit_cascade(&corners, direction, resolution),
+                    resolution,
+                };
+                near = far;
+                cascade
+            })
+            .collect();
+
+        let updated = DirectionalCascades {
+            cascades: fitted,
+            camera_position,
+            camera_forward,
+            blend: config.blend.clamp(0.0, 1.0),
+        };
+        match cascades {
+            Some(mut cascades) => **cascades = updated,
+            None => {
+                cmd.insert(updated, entity);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a - e).abs() < 1e-3 * e.abs().max(1.0),
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn uniform_splits_are_evenly_spaced() {
+        let config = CascadeShadowConfig::default().with_split(CascadeSplit::Uniform);
+        assert_close(
+            &config.split_distances(1.0, 1000.0),
+            &[25.75, 50.5, 75.25, 100.0],
+        );
+    }
+
+    #[test]
+    fn logarithmic_splits_keep_a_constant_ratio() {
+        let config = CascadeShadowConfig::default()
+            .with_split(CascadeSplit::Logarithmic)
+            .with_max_distance(10000.0);
+        assert_close(
+            &config.split_distances(1.0, 10000.0),
+            &[10.0, 100.0, 1000.0, 10000.0],
+        );
+    }
+
+    #[test]
+    fn splits_stop_at_the_camera_far_plane() {
+        let config = CascadeShadowConfig::default()
+            .with_split(CascadeSplit::Uniform)
+            .with_cascade_count(2);
+        assert_close(&config.split_distances(0.0, 50.0), &[25.0, 50.0]);
+    }
+
+    #[test]
+    fn zero_near_does_not_collapse_logarithmic_splits() {
+        let config = CascadeShadowConfig::default().with_split(CascadeSplit::Logarithmic);
+        let splits = config.split_distances(0.0, 1000.0);
+        assert_eq!(splits.len(), MAX_CASCADES);
+        assert!(splits[0] > 0.0, "{splits:?}");
+        assert!(
+            splits.windows(2).all(|pair| pair[0] < pair[1]),
+            "{splits:?}"
+        );
+        assert!(
+            (splits[MAX_CASCADES - 1] - 100.0).abs() < 1e-3,
+            "{splits:?}"
+        );
+    }
+
+    #[test]
+    fn manual_splits_are_clamped_and_truncated() {
+        let config = CascadeShadowConfig::default()
+            .with_cascade_count(3)
+            .with_split(CascadeSplit::Manual(vec![5.0, 2.0, 500.0, 50.0]));
+        assert_close(&config.split_distances(1.0, 1000.0), &[5.0, 5.0, 100.0]);
+    }
+
+    #[test]
+    fn resolutions_can_be_set_for_all_or_each_cascade() {
+        let config = CascadeShadowConfig::default().with_resolution(512);
+        assert_eq!(config.resolutions, [512; MAX_CASCADES]);
+
+        let config = config.with_cascade_resolutions([4096, 2048, 1024, 512]);
+        assert_eq!(config.resolutions, [4096, 2048, 1024, 512]);
+    }
+
+    fn slice_corners() -> [Vec3; 8] {
+        Camera::default().frustum_corners(
+            Mat4::from_translation(Vec3::new(3.3, 1.7, -2.9)),
+            0.1,
+            20.0,
+        )
+    }
+
+    #[test]
+    fn fitted_cascade_contains_its_slice() {
+        let direction = Vec3::new(-0.3, -1.0, 0.2).normalize();
+        let view_proj = fit_cascade(&slice_corners(), direction, 1024);
+        for corner in slice_corners() {
+            let clip = view_proj.project_point3(corner);
+            assert!(clip.x.abs() <= 1.0 && clip.y.abs() <= 1.0, "{clip}");
+            assert!((0.0..=1.0).contains(&clip.z), "{clip}");
+        }
+    }
+
+    #[test]
+    fn fitted_cascade_moves_in_whole_texels() {
+        let direction = Vec3::new(-0.3, -1.0, 0.2).normalize();
+        for resolution in [512, 2048] {
+            let view_proj = fit_cascade(&slice_corners(), direction, resolution);
+            // The light view is centred on the world origin, so the origin
+            // sits on a texel boundary exactly when the projection was
+            // snapped to `resolution` texels.
+            let texels = view_proj.project_point3(Vec3::ZERO).truncate() * resolution as f32 * 0.5;
+            let offset = texels - texels.round();
+            assert!(offset.abs().max_element() < 1e-2, "{texels}");
+        }
+    }
+}
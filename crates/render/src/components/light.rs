@@ -18,8 +18,8 @@ use crate::{
     components::{
         render_entity::RenderEntity,
         shadows::{
-            RenderPointShadowMaps, RenderShadowCasterSlot, RenderShadowCasterViewProj,
-            RenderSpotDirectionalShadowMaps,
+            RenderCascadeViewProjs, RenderDirectionalShadowMaps, RenderPointShadowMaps,
+            RenderShadowCasterSlot, RenderShadowCasterViewProj, RenderSpotShadowMaps,
         },
     },
     device::RenderDevice,
@@ -183,17 +183,22 @@ impl Component for RenderLight {
                 .is_some_and(|light| light.shadow_layer == SHADOW_LAYER_REQUESTED);
 
             if casts_shadows {
-                let is_point = world
+                let light_type = world
                     .get_component_for_entity::<RenderLight>(context.entity)
-                    .is_some_and(|light| light.light_type == LightType::Point.index());
+                    .map_or(LightType::Point.index(), |light| light.light_type);
+                let is_directional = light_type == LightType::Directional.index();
 
-                let shadow_slot = if is_point {
+                let shadow_slot = if light_type == LightType::Point.index() {
                     world
                         .get_resource_mut::<RenderPointShadowMaps>()
                         .and_then(|shadow_maps| shadow_maps.push_caster(context.entity))
+                } else if is_directional {
+                    world
+                        .get_resource_mut::<RenderDirectionalShadowMaps>()
+                        .and_then(|shadow_maps| shadow_maps.push_caster(context.entity))
                 } else {
                     world
-                        .get_resource_mut::<RenderSpotDirectionalShadowMaps>()
+                        .get_resource_mut::<RenderSpotShadowMaps>()
                         .and_then(|shadow_maps| shadow_maps.push_caster(context.entity))
                 };
 
@@ -214,11 +219,14 @@ impl Component for RenderLight {
                             world.get_resource::<RenderDevice>(),
                             world.get_resource::<ShadowPipeline>(),
                         ) {
-                            let view_proj = RenderShadowCasterViewProj::new(
-                                device,
-                                &shadow_pipeline.bind_group_layout,
-                            );
-                            world.insert_component(view_proj, context.entity, false);
+                            let layout = &shadow_pipeline.bind_group_layout;
+                            if is_directional {
+                                let view_projs = RenderCascadeViewProjs::new(device, layout);
+                                world.insert_component(view_projs, context.entity, false);
+                            } else {
+                                let view_proj = RenderShadowCasterViewProj::new(device, layout);
+                                world.insert_component(view_proj, context.entity, false);
+                            }
                         }
                     }
                     // Shadow-caster pool exhausted; fall back to unshadowed.
@@ -1,7 +1,11 @@
 use std::marker::PhantomData;
 
 use ecs::component::Component;
-use essential::assets::{handle::AssetHandle, AssetId};
+use essential::assets::{
+    handle::{AssetHandle, SerializedHandle},
+    AssetId,
+};
+use facet::Facet;
 
 use crate::{assets::material::StandardMaterial, Material};
 
@@ -11,11 +15,37 @@ use crate::{assets::material::StandardMaterial, Material};
 /// The type parameter `M` defaults to [`StandardMaterial`] so existing code that writes
 /// `MaterialComponent { handle: … }` with a `StandardMaterial` handle continues to work
 /// without any change.  Custom materials use `MaterialComponent::<MyMaterial> { handle: … }`.
-#[derive(Component)]
+#[derive(Component, Facet)]
+#[facet(opaque, proxy = SerializedMaterialComponent)]
 pub struct MaterialComponent<M: Material + Send + Sync + 'static = StandardMaterial> {
     pub handle: AssetHandle<M>,
 }
 
+/// The reflected form of [`MaterialComponent`].  A derived `Facet` would require the
+/// material type itself to be reflected.
+#[derive(Facet)]
+pub struct SerializedMaterialComponent {
+    pub handle: SerializedHandle,
+}
+
+impl<M: Material> From<&MaterialComponent<M>> for SerializedMaterialComponent {
+    fn from(component: &MaterialComponent<M>) -> Self {
+        Self {
+            handle: component.handle.to_serialized(),
+        }
+    }
+}
+
+impl<M: Material> TryFrom<SerializedMaterialComponent> for MaterialComponent<M> {
+    type Error = String;
+
+    fn try_from(serialized: SerializedMaterialComponent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            handle: serialized.handle.try_into()?,
+        })
+    }
+}
+
 /// Render-world component placed on mesh entities to identify which material
 /// asset they use for a specific material type `M`.
 ///
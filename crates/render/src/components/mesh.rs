@@ -4,9 +4,16 @@ use ecs::{
     resource::Res,
     Added, CommandQueue, Entity, With,
 };
-use essential::{assets::AssetId, transform::GlobalTransform};
+use essential::{
+    assets::AssetId,
+    transform::{GlobalTransform, GlobalTransformRaw},
+};
 use glam::Mat4;
-use mesh::{mesh::MeshComponent, SkeletonComponent};
+use mesh::{
+    bounds::{Aabb, Frustum},
+    mesh::MeshComponent,
+    SkeletonComponent,
+};
 use wgpu::util::DeviceExt;
 
 use crate::{components::render_entity::RenderEntity, device::RenderDevice, queue::RenderQueue};
@@ -15,6 +22,25 @@ use crate::{components::render_entity::RenderEntity, device::RenderDevice, queue
 pub(crate) struct RenderMeshInstance {
     pub(crate) mesh_asset_id: AssetId,
     pub(crate) transform: wgpu::Buffer,
+    /// CPU copy of `transform`'s contents, gathered into shared buffers when
+    /// instances are drawn in batches.
+    pub(crate) raw_transform: GlobalTransformRaw,
+    /// World-space bounds, once `update_mesh_aabbs` has seen the mesh.
+    pub(crate) aabb: Option<Aabb>,
+}
+
+impl RenderMeshInstance {
+    /// Whether the instance may be seen through `frustum`. Instances without
+    /// bounds yet are always drawn.
+    pub(crate) fn is_visible(&self, frustum: &Frustum) -> bool {
+        is_visible(self.aabb, frustum)
+    }
+}
+
+/// Whether something with world-space bounds `aabb` may be seen through
+/// `frustum`; without bounds it always may.
+pub(crate) fn is_visible(aabb: Option<Aabb>, frustum: &Frustum) -> bool {
+    aabb.is_none_or(|aabb| frustum.intersects_aabb(&aabb))
 }
 
 pub(crate) fn mesh_added(
@@ -46,6 +72,8 @@ pub(crate) fn mesh_added(
         let instance = RenderMeshInstance {
             mesh_asset_id: mesh.handle.id(),
             transform: instance_buffer,
+            raw_transform,
+            aabb: None,
         };
 
         match render_entity {
@@ -69,7 +97,7 @@ pub(crate) fn mesh_changed(
     queue: Res<RenderQueue>,
 ) {
     for (transform, skeleton, render_entity) in meshes.iter() {
-        if let Some((render_mesh,)) = render_meshes.get_entity(**render_entity) {
+        if let Some((mut render_mesh,)) = render_meshes.get_entity(**render_entity) {
             let raw_transform = match skeleton {
                 Some(_) => GlobalTransform::new(Mat4::IDENTITY).to_raw(),
                 None => transform.to_raw(),
@@ -79,6 +107,7 @@ pub(crate) fn mesh_changed(
                 0,
                 bytemuck::cast_slice(&[raw_transform]),
             );
+            render_mesh.raw_transform = raw_transform;
         }
     }
 }
@@ -1,6 +1,9 @@
 pub mod camera;
+pub mod cascades;
 pub mod light;
 pub mod material;
+pub mod post_process;
+pub mod projection;
 pub mod render_entity;
 pub mod world_environment;
 
@@ -8,9 +11,13 @@ pub(crate) mod mesh;
 pub(crate) mod shadows;
 pub(crate) mod skeleton;
 pub(crate) mod transform;
+pub(crate) mod visibility;
 
 pub use camera::Camera;
+pub use cascades::{CascadeShadowConfig, CascadeSplit, DirectionalCascades};
 pub use light::Light;
 pub use material::MaterialComponent;
+pub use post_process::{Bloom, ColorGrading, Exposure, Tonemapping, Vignette};
+pub use projection::{Projection, ScalingMode};
 pub use render_entity::RenderEntity;
 pub use world_environment::WorldEnvironment;
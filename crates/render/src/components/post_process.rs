@@ -0,0 +1,546 @@
+//! Post-processing of a camera's HDR image.
+//!
+//! Cameras render the scene into a linear HDR target (see
+//! [`HDR_FORMAT`](crate::components::camera::HDR_FORMAT)). [`PostProcessPlugin`]
+//! then exposes it, adds bloom, tone maps it to displayable range, grades it
+//! and darkens its corners before writing the camera's real target. Each step
+//! is configured by a component next to the [`Camera`]:
+//!
+//! | Component        | Without it                       |
+//! |------------------|----------------------------------|
+//! | [`Tonemapping`]  | [`Tonemapping::Aces`]            |
+//! | [`Exposure`]     | [`Exposure::NEUTRAL`]            |
+//! | [`Bloom`]        | no bloom                         |
+//! | [`ColorGrading`] | no grading                       |
+//! | [`Vignette`]     | no vignette                      |
+//!
+//! [`PostProcessPlugin`]: crate::post_process_pipeline::PostProcessPlugin
+//! [`Camera`]: crate::components::camera::Camera
+
+use ecs::{
+    command::CommandQueue,
+    component::Component,
+    query::{query_filter::With, Query},
+    resource::{Res, ResMut},
+};
+use encase::{ShaderType, UniformBuffer};
+use essential::assets::{handle::AssetHandle, AssetId};
+use wgpu::util::DeviceExt;
+
+use crate::{
+    assets::texture::Texture,
+    components::{
+        camera::{Camera, RenderCamera, HDR_FORMAT},
+        render_entity::RenderEntity,
+    },
+    device::RenderDevice,
+    post_process_pipeline::PostProcessPipeline,
+    queue::RenderQueue,
+    render_asset::{
+        render_texture::{DummyRenderTexture, RenderTexture},
+        render_window::RenderWindow,
+        RenderAssets,
+    },
+};
+
+/// Lumens per watt of an ideal 555 nm source.
+///
+/// Light intensities in this engine are on a radiometric (watt) scale, while
+/// photometric units (lux, candela, EV) are this factor larger. [`Exposure`]
+/// converts between the two, and the glTF loader divides imported photometric
+/// intensities by it.
+pub const LUMINOUS_EFFICACY: f32 = 683.0;
+
+/// The curve mapping exposed HDR color into displayable `0..1` range.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tonemapping {
+    /// Narkowicz's fit of the ACES filmic curve: punchy contrast, but very
+    /// bright saturated colors skew towards yellow and white.
+    #[default]
+    Aces,
+    /// Troy Sobotka's AgX: desaturates highlights smoothly, keeping hues
+    /// stable as they brighten.
+    AgX,
+    /// `c / (1 + c)` per channel: cheap and soft, never fully white.
+    Reinhard,
+    /// Clamp only.
+    None,
+}
+
+impl Tonemapping {
+    fn index(self) -> u32 {
+        match self {
+            Tonemapping::Aces => 0,
+            Tonemapping::AgX => 1,
+            Tonemapping::Reinhard => 2,
+            Tonemapping::None => 3,
+        }
+    }
+}
+
+/// How much light reaches the sensor, as a photographic exposure value at ISO
+/// 100. Each step up halves the brightness of the image.
+///
+/// Scene light is multiplied by `LUMINOUS_EFFICACY / (1.2 * 2^ev100)`, the
+/// usual photometric calibration applied to this engine's watt-scale lights,
+/// so [`Exposure::NEUTRAL`] leaves the image as lit.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Exposure {
+    pub ev100: f32,
+}
+
+impl Exposure {
+    /// Leaves scene light unscaled.
+    pub const NEUTRAL: Self = Self { ev100: 9.152_74 };
+    /// Bright midday sun.
+    pub const SUNLIGHT: Self = Self { ev100: 15.0 };
+    /// Overcast daylight.
+    pub const OVERCAST: Self = Self { ev100: 12.0 };
+    /// A well lit interior.
+    pub const INDOOR: Self = Self { ev100: 7.0 };
+
+    pub fn from_ev100(ev100: f32) -> Self {
+        Self { ev100 }
+    }
+
+    /// The exposure of a camera with the given aperture (f-number), shutter
+    /// speed (seconds) and sensitivity (ISO).
+    pub fn from_physical_camera(aperture: f32, shutter_speed: f32, sensitivity: f32) -> Self {
+        Self {
+            ev100: (aperture * aperture / shutter_speed * 100.0 / sensitivity).log2(),
+        }
+    }
+
+    /// The factor scene light is multiplied by.
+    pub fn multiplier(&self) -> f32 {
+        LUMINOUS_EFFICACY / (1.2 * self.ev100.exp2())
+    }
+}
+
+impl Default for Exposure {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+/// Light bleeding from bright parts of the image, such as emissive surfaces
+/// and specular highlights, into their surroundings.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Bloom {
+    /// How much of the blurred light is added back to the image.
+    pub intensity: f32,
+    /// Exposed brightness above which light blooms.
+    pub threshold: f32,
+    /// Width of the soft transition around `threshold`, as a fraction of it.
+    pub knee: f32,
+    /// Number of half-resolution blur steps, clamped to `1..=MAX_BLOOM_MIPS`.
+    /// More spread the glow wider.
+    pub mip_count: u32,
+}
+
+/// Upper bound on [`Bloom::mip_count`].
+pub const MAX_BLOOM_MIPS: u32 = 8;
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self {
+            intensity: 0.15,
+            threshold: 1.0,
+            knee: 0.5,
+            mip_count: 6,
+        }
+    }
+}
+
+/// Color grading through a lookup table, applied after tone mapping.
+///
+/// `lut` is a strip of `N` tiles of `N`×`N` texels laid out horizontally
+/// (e.g. 256×16 for `N = 16`): red grows along each tile's x axis, green down
+/// its y axis and blue from tile to tile. It is indexed with sRGB-encoded
+/// color; an sRGB texture format is decoded on sampling, any other format is
+/// assumed to hold sRGB-encoded values.
+#[derive(Component, Clone, Debug)]
+pub struct ColorGrading {
+    pub lut: AssetHandle<Texture>,
+    /// Blend between the ungraded (0) and fully graded (1) image.
+    pub strength: f32,
+}
+
+impl ColorGrading {
+    pub fn new(lut: AssetHandle<Texture>) -> Self {
+        Self { lut, strength: 1.0 }
+    }
+
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+}
+
+/// Darkening towards the corners of the image.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Vignette {
+    /// How dark the corners get, from 0 (not at all) to 1 (black).
+    pub intensity: f32,
+    /// Distance from the center, where 1 is a corner, at which darkening is
+    /// complete.
+    pub radius: f32,
+    /// Width of the falloff inside `radius`.
+    pub smoothness: f32,
+}
+
+impl Default for Vignette {
+    fn default() -> Self {
+        Self {
+            intensity: 0.3,
+            radius: 1.0,
+            smoothness: 0.6,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, ShaderType)]
+pub(crate) struct PostProcessUniform {
+    exposure: f32,
+    tonemapping: u32,
+    bloom_intensity: f32,
+    bloom_threshold: f32,
+    bloom_knee: f32,
+    lut_strength: f32,
+    lut_size: f32,
+    lut_srgb: u32,
+    vignette_intensity: f32,
+    vignette_radius: f32,
+    vignette_smoothness: f32,
+}
+
+pub(crate) struct BloomMip {
+    pub(crate) view: wgpu::TextureView,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+// Post-processing state of a `RenderCamera`, on the same render entity.
+#[derive(Component)]
+pub(crate) struct RenderPostProcess {
+    pub(crate) uniform: wgpu::Buffer,
+    // Half resolution first, then halving again each step; empty without
+    // `Bloom`.
+    pub(crate) bloom_mips: Vec<BloomMip>,
+    pub(crate) lut: Option<AssetId>,
+}
+
+impl RenderPostProcess {
+    fn new(device: &wgpu::Device) -> Self {
+        let mut bytes = UniformBuffer::new(Vec::new());
+        bytes.write(&PostProcessUniform::default()).unwrap();
+        let uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("post_process_uniform"),
+            contents: &bytes.into_inner(),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Self {
+            uniform,
+            bloom_mips: Vec::new(),
+            lut: None,
+        }
+    }
+
+    // Recreates the bloom chain when the camera's size or `Bloom::mip_count`
+    // changed.
+    fn resize_bloom(&mut self, device: &wgpu::Device, size: wgpu::Extent3d, mip_count: u32) {
+        let (mut width, mut height) = (size.width / 2, size.height / 2);
+        let matches = self.bloom_mips.len() == mip_count as usize
+            && self
+                .bloom_mips
+                .first()
+                .is_none_or(|mip| (mip.width, mip.height) == (width.max(1), height.max(1)));
+        if matches {
+            return;
+        }
+
+        self.bloom_mips.clear();
+        for _ in 0..mip_count {
+            width = width.max(1);
+            height = height.max(1);
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("bloom_mip"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: HDR_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+            self.bloom_mips.push(BloomMip {
+                view: texture.create_view(&wgpu::TextureViewDescriptor::default()),
+                width,
+                height,
+            });
+            width /= 2;
+            height /= 2;
+        }
+    }
+}
+
+// Turns each camera's post-processing components into its `RenderPostProcess`
+// for this frame.
+#[allow(clippy::type_complexity)]
+pub(crate) fn prepare_post_process(
+    cameras: Query<
+        (
+            &RenderEntity,
+            Option<&Tonemapping>,
+            Option<&Exposure>,
+            Option<&Bloom>,
+            Option<&ColorGrading>,
+            Option<&Vignette>,
+        ),
+        With<Camera>,
+    >,
+    render_cameras: Query<(&RenderCamera, Option<&mut RenderPostProcess>)>,
+    render_textures: Res<RenderAssets<RenderTexture>>,
+    device: Res<RenderDevice>,
+    queue: Res<RenderQueue>,
+    mut cmd: CommandQueue,
+) {
+    for (render_entity, tonemapping, exposure, bloom, grading, vignette) in cameras.iter() {
+        let Some((render_camera, post_process)) = render_cameras.get_entity(**render_entity) else {
+            continue;
+        };
+        let Some(mut post_process) = post_process else {
+            cmd.insert(RenderPostProcess::new(&device), **render_entity);
+            continue;
+        };
+
+        let mut uniform = PostProcessUniform {
+            exposure: exposure.copied().unwrap_or_default().multiplier(),
+            tonemapping: tonemapping.copied().unwrap_or_default().index(),
+            ..Default::default()
+        };
+
+        let mip_count = match bloom {
+            Some(bloom) if bloom.intensity > 0.0 => {
+                uniform.bloom_intensity = bloom.intensity;
+                uniform.bloom_threshold = bloom.threshold;
+                uniform.bloom_knee = bloom.knee;
+                bloom.mip_count.clamp(1, MAX_BLOOM_MIPS)
+            }
+            _ => 0,
+        };
+        post_process.resize_bloom(&device, render_camera.hdr_target.texture.size(), mip_count);
+
+        post_process.lut = None;
+        if let Some(grading) = grading {
+            let lut_id = grading.lut.id();
+            if let Some(lut) = render_textures.get(&lut_id) {
+                uniform.lut_strength = grading.strength.clamp(0.0, 1.0);
+                uniform.lut_size = lut.texture.size().height as f32;
+                uniform.lut_srgb = lut.texture.format().is_srgb() as u32;
+                post_process.lut = Some(lut_id);
+            }
+        }
+
+        if let Some(vignette) = vignette {
+            uniform.vignette_intensity = vignette.intensity.clamp(0.0, 1.0);
+            uniform.vignette_radius = vignette.radius;
+            uniform.vignette_smoothness = vignette.smoothness.max(1e-4);
+        }
+
+        let mut bytes = UniformBuffer::new(Vec::new());
+        bytes.write(&uniform).unwrap();
+        queue.write_buffer(&post_process.uniform, 0, &bytes.into_inner());
+    }
+}
+
+// One fullscreen draw of a post-processing step.
+struct FullscreenPass<'a> {
+    label: &'static str,
+    pipeline: &'a wgpu::RenderPipeline,
+    bind_group: wgpu::BindGroup,
+    target: &'a wgpu::TextureView,
+    // Upsampling adds onto what the downsample left in its target.
+    load: wgpu::LoadOp<wgpu::Color>,
+}
+
+// Blooms and composites each camera's HDR target into its render target or,
+// for window cameras, the swapchain.
+pub(crate) fn render_post_process(
+    pipeline: Res<PostProcessPipeline>,
+    mut device: ResMut<RenderDevice>,
+    render_cameras: Query<(&RenderCamera, &RenderPostProcess)>,
+    render_window: Res<RenderWindow>,
+    render_textures: Res<RenderAssets<RenderTexture>>,
+    dummy_texture: Res<DummyRenderTexture>,
+) {
+    let clear = wgpu::LoadOp::Clear(wgpu::Color::BLACK);
+    let dummy_view = &dummy_texture.inner().view;
+
+    // Bind groups need the device, which the encoder borrows, so every pass
+    // is set up before any is recorded.
+    let mut passes = Vec::new();
+    for (render_camera, post_process) in render_cameras.iter() {
+        let output: &wgpu::TextureView = match &render_camera.render_target {
+            Some(rt) => &rt.view,
+            None => match render_window.get_view() {
+                Some(view) => view,
+                None => continue,
+            },
+        };
+
+        let bloom_bind_group = |source: &wgpu::TextureView| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("bloom_bind_group"),
+                layout: &pipeline.bloom_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(source),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&pipeline.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: post_process.uniform.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+
+        let mips = &post_process.bloom_mips;
+        if let Some(first) = mips.first() {
+            passes.push(FullscreenPass {
+                label: "Bloom Prefilter Pass",
+                pipeline: &pipeline.bloom_prefilter,
+                bind_group: bloom_bind_group(&render_camera.hdr_target.view),
+                target: &first.view,
+                load: clear,
+            });
+            for pair in mips.windows(2) {
+                passes.push(FullscreenPass {
+                    label: "Bloom Downsample Pass",
+                    pipeline: &pipeline.bloom_downsample,
+                    bind_group: bloom_bind_group(&pair[0].view),
+                    target: &pair[1].view,
+                    load: clear,
+                });
+            }
+            for pair in mips.windows(2).rev() {
+                passes.push(FullscreenPass {
+                    label: "Bloom Upsample Pass",
+                    pipeline: &pipeline.bloom_upsample,
+                    bind_group: bloom_bind_group(&pair[1].view),
+                    target: &pair[0].view,
+                    load: wgpu::LoadOp::Load,
+                });
+            }
+        }
+
+        let bloom_view = mips.first().map_or(dummy_view, |mip| &mip.view);
+        let lut_view = post_process
+            .lut
+            .and_then(|lut| render_textures.get(&lut))
+            .map_or(dummy_view, |lut| &lut.view);
+        let composite_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("post_process_bind_group"),
+            layout: &pipeline.composite_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&render_camera.hdr_target.view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&pipeline.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(bloom_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::TextureView(lut_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: post_process.uniform.as_entire_binding(),
+                },
+            ],
+        });
+        passes.push(FullscreenPass {
+            label: "Post-Process Pass",
+            pipeline: &pipeline.composite,
+            bind_group: composite_bind_group,
+            target: output,
+            load: clear,
+        });
+    }
+
+    let encoder = device.command_encoder();
+    for pass in &passes {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(pass.label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: pass.target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: pass.load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(pass.pipeline);
+        render_pass.set_bind_group(0, &pass.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neutral_exposure_leaves_light_unscaled() {
+        assert!((Exposure::NEUTRAL.multiplier() - 1.0).abs() < 1e-4);
+        assert_eq!(Exposure::default(), Exposure::NEUTRAL);
+    }
+
+    #[test]
+    fn physical_camera_exposure_matches_ev100() {
+        // f/4 at 1/16 s is EV 8 at ISO 100; doubling the sensitivity takes a
+        // stop off.
+        let exposure = Exposure::from_physical_camera(4.0, 1.0 / 16.0, 200.0);
+        assert!((exposure.ev100 - 7.0).abs() < 1e-4, "{exposure:?}");
+
+        let brighter = Exposure::from_ev100(exposure.ev100 - 1.0);
+        assert!((brighter.multiplier() / exposure.multiplier() - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn tonemapping_indices_match_the_shader() {
+        let shader = include_str!("../shaders/post_process.wgsl");
+        for (tonemapping, name) in [
+            (Tonemapping::Aces, "ACES"),
+            (Tonemapping::AgX, "AGX"),
+            (Tonemapping::Reinhard, "REINHARD"),
+        ] {
+            let constant = format!("const TONEMAP_{name} = {}u;", tonemapping.index());
+            assert!(shader.contains(&constant), "missing `{constant}`");
+        }
+        // Anything the shader has no constant for falls through to a clamp.
+        assert_eq!(Tonemapping::None.index(), 3);
+        assert!(!shader.contains("= 3u;"));
+    }
+}
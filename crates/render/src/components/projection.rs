@@ -0,0 +1,236 @@
+use facet::Facet;
+use glam::{Mat4, Vec2, Vec3, Vec4};
+
+/// How a [`Camera`](super::Camera) maps view space onto its target.
+///
+/// Every variant shares the camera's `aspect`, `znear` and `zfar`, so window
+/// resizes and shadow cascade splits work the same whichever is picked.
+#[derive(Clone, Copy, Debug, PartialEq, Facet)]
+#[repr(u8)]
+pub enum Projection {
+    /// Vertical field of view in radians; the horizontal one follows the aspect.
+    Perspective { fovy: f32 },
+    /// Parallel rays: the visible area is `scaling` times `scale`, regardless of
+    /// distance, so raising `scale` zooms out.
+    Orthographic { scaling: ScalingMode, scale: f32 },
+    /// A clip-from-view matrix used as-is, column-major like
+    /// [`Mat4::to_cols_array`]. It must map depth to wgpu's 0..1 range; the
+    /// camera's `aspect` doesn't reach it, but `znear`/`zfar` still place the
+    /// shadow cascades, so keep them in step with the matrix.
+    Custom { clip_from_view: [f32; 16] },
+}
+
+impl Projection {
+    pub fn perspective(fovy: f32) -> Self {
+        Projection::Perspective { fovy }
+    }
+
+    pub fn orthographic(scaling: ScalingMode) -> Self {
+        Projection::Orthographic {
+            scaling,
+            scale: 1.0,
+        }
+    }
+
+    pub fn custom(clip_from_view: Mat4) -> Self {
+        Projection::Custom {
+            clip_from_view: clip_from_view.to_cols_array(),
+        }
+    }
+
+    pub fn clip_from_view(&self, aspect: f32, znear: f32, zfar: f32) -> Mat4 {
+        match *self {
+            Projection::Perspective { fovy } => Mat4::perspective_rh(fovy, aspect, znear, zfar),
+            Projection::Orthographic { scaling, scale } => {
+                let half = scaling.half_extents(aspect) * scale;
+                Mat4::orthographic_rh(-half.x, half.x, -half.y, half.y, znear, zfar)
+            }
+            Projection::Custom { clip_from_view } => Mat4::from_cols_array(&clip_from_view),
+        }
+    }
+
+    /// Returns `true` if the projection has parallel view rays, so every
+    /// point is seen from the same direction. A [`Custom`](Self::Custom)
+    /// matrix is orthographic when its clip-space `w` doesn't depend on depth.
+    pub fn is_orthographic(&self) -> bool {
+        match *self {
+            Projection::Perspective { .. } => false,
+            Projection::Orthographic { .. } => true,
+            Projection::Custom { clip_from_view } => Mat4::from_cols_array(&clip_from_view)
+                .row(3)
+                .abs_diff_eq(Vec4::W, 1e-6),
+        }
+    }
+
+    /// Returns the view-space corners of the visible rectangle `distance`
+    /// units in front of the camera, as bottom-left, bottom-right, top-right,
+    /// top-left.
+    pub fn view_plane_corners(
+        &self,
+        aspect: f32,
+        znear: f32,
+        zfar: f32,
+        distance: f32,
+    ) -> [Vec3; 4] {
+        let half = match *self {
+            Projection::Perspective { fovy } => {
+                let half_height = distance * (fovy * 0.5).tan();
+                Vec2::new(half_height * aspect, half_height)
+            }
+            Projection::Orthographic { scaling, scale } => scaling.half_extents(aspect) * scale,
+            Projection::Custom { .. } => {
+                // No parameters to read the shape from: find the depth this
+                // distance lands at and unproject the clip-space rectangle
+                // there.
+                let clip_from_view = self.clip_from_view(aspect, znear, zfar);
+                let view_from_clip = clip_from_view.inverse();
+                let depth = clip_from_view
+                    .project_point3(Vec3::new(0.0, 0.0, -distance))
+                    .z;
+                return [
+                    Vec3::new(-1.0, -1.0, depth),
+                    Vec3::new(1.0, -1.0, depth),
+                    Vec3::new(1.0, 1.0, depth),
+                    Vec3::new(-1.0, 1.0, depth),
+                ]
+                .map(|corner| view_from_clip.project_point3(corner));
+            }
+        };
+        [
+            Vec3::new(-half.x, -half.y, -distance),
+            Vec3::new(half.x, -half.y, -distance),
+            Vec3::new(half.x, half.y, -distance),
+            Vec3::new(-half.x, half.y, -distance),
+        ]
+    }
+}
+
+impl Default for Projection {
+    fn default() -> Self {
+        Projection::perspective(std::f32::consts::FRAC_PI_4)
+    }
+}
+
+/// What an orthographic camera keeps fixed, in world units, as its aspect
+/// changes.
+#[derive(Clone, Copy, Debug, PartialEq, Facet)]
+#[repr(u8)]
+pub enum ScalingMode {
+    /// Always shows `height` units top to bottom; the width follows the aspect.
+    FixedVertical { height: f32 },
+    /// Always shows `width` units across; the height follows the aspect.
+    FixedHorizontal { width: f32 },
+    /// Exactly this area, stretched to whatever the aspect is.
+    Fixed { width: f32, height: f32 },
+    /// At least this area, extended along whichever axis the aspect leaves
+    /// room on.
+    AutoMin { min_width: f32, min_height: f32 },
+}
+
+impl ScalingMode {
+    /// Half the visible width and height at the given aspect.
+    pub fn half_extents(&self, aspect: f32) -> Vec2 {
+        let size = match *self {
+            ScalingMode::FixedVertical { height } => Vec2::new(height * aspect, height),
+            ScalingMode::FixedHorizontal { width } => Vec2::new(width, width / aspect),
+            ScalingMode::Fixed { width, height } => Vec2::new(width, height),
+            ScalingMode::AutoMin {
+                min_width,
+                min_height,
+            } => {
+                if min_width > min_height * aspect {
+                    Vec2::new(min_width, min_width / aspect)
+                } else {
+                    Vec2::new(min_height * aspect, min_height)
+                }
+            }
+        };
+        size * 0.5
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_scaling_modes_keep_their_axis() {
+        let vertical = ScalingMode::FixedVertical { height: 4.0 };
+        assert_eq!(vertical.half_extents(2.0), Vec2::new(4.0, 2.0));
+        let horizontal = ScalingMode::FixedHorizontal { width: 4.0 };
+        assert_eq!(horizontal.half_extents(2.0), Vec2::new(2.0, 1.0));
+        let fixed = ScalingMode::Fixed {
+            width: 4.0,
+            height: 2.0,
+        };
+        assert_eq!(fixed.half_extents(0.5), Vec2::new(2.0, 1.0));
+    }
+
+    #[test]
+    fn auto_min_extends_whichever_axis_has_room() {
+        let auto_min = ScalingMode::AutoMin {
+            min_width: 4.0,
+            min_height: 2.0,
+        };
+        // Wider than 2:1: the height is the binding minimum.
+        assert_eq!(auto_min.half_extents(4.0), Vec2::new(4.0, 1.0));
+        // Narrower: the width is.
+        assert_eq!(auto_min.half_extents(1.0), Vec2::new(2.0, 2.0));
+    }
+
+    #[test]
+    fn clip_from_view_matches_each_variant() {
+        let perspective = Projection::perspective(1.0);
+        assert_eq!(
+            perspective.clip_from_view(1.5, 0.1, 50.0),
+            Mat4::perspective_rh(1.0, 1.5, 0.1, 50.0)
+        );
+
+        let orthographic = Projection::Orthographic {
+            scaling: ScalingMode::FixedVertical { height: 2.0 },
+            scale: 3.0,
+        };
+        let clip_from_view = orthographic.clip_from_view(2.0, 1.0, 11.0);
+        let near_corner = clip_from_view.project_point3(Vec3::new(6.0, 3.0, -1.0));
+        let far_corner = clip_from_view.project_point3(Vec3::new(-6.0, -3.0, -11.0));
+        assert!(near_corner.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), 1e-5));
+        assert!(far_corner.abs_diff_eq(Vec3::new(-1.0, -1.0, 1.0), 1e-5));
+
+        let matrix = Mat4::orthographic_rh(-1.0, 2.0, -3.0, 4.0, 0.5, 5.0);
+        assert_eq!(
+            Projection::custom(matrix).clip_from_view(7.0, 1.0, 2.0),
+            matrix
+        );
+    }
+
+    #[test]
+    fn custom_corners_match_the_equivalent_perspective() {
+        let (aspect, znear, zfar) = (1.6, 0.1, 100.0);
+        let perspective = Projection::perspective(0.9);
+        let custom = Projection::custom(perspective.clip_from_view(aspect, znear, zfar));
+
+        for distance in [znear, 1.0, 37.5, zfar] {
+            let expected = perspective.view_plane_corners(aspect, znear, zfar, distance);
+            let actual = custom.view_plane_corners(aspect, znear, zfar, distance);
+            for (a, e) in actual.iter().zip(expected) {
+                assert!(
+                    a.abs_diff_eq(e, 1e-3 * distance),
+                    "{actual:?} != {expected:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn orthographic_projections_are_detected() {
+        assert!(!Projection::perspective(1.0).is_orthographic());
+        assert!(
+            Projection::orthographic(ScalingMode::FixedVertical { height: 1.0 }).is_orthographic()
+        );
+        assert!(!Projection::custom(Mat4::perspective_rh(1.0, 1.0, 0.1, 10.0)).is_orthographic());
+        assert!(
+            Projection::custom(Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.1, 10.0))
+                .is_orthographic()
+        );
+    }
+}
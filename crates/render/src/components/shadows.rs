@@ -7,8 +7,9 @@ use ecs::{
     resource::{Res, ResMut, Resource},
     Changed, Query,
 };
-use encase::{ShaderSize, UniformBuffer};
-use glam::{Mat4, Vec3};
+use encase::{ShaderSize, ShaderType, UniformBuffer};
+use glam::{Mat4, Vec3, Vec4};
+use mesh::bounds::Frustum;
 use wgpu::{
     util::DeviceExt, BindGroupDescriptor, Operations, RenderPassDepthStencilAttachment,
     RenderPassDescriptor, StoreOp, TextureView,
@@ -16,8 +17,10 @@ use wgpu::{
 
 use crate::{
     components::{
+        cascades::{shadow_up_vector, DirectionalCascades, MAX_CASCADES},
         light::{push_render_light_to_gpu, LightType, RenderLight, RenderLights},
         mesh::RenderMeshInstance,
+        render_entity::RenderEntity,
         skeleton::{RenderSkeletonComponent, SkinUniforms},
     },
     device::RenderDevice,
@@ -27,13 +30,17 @@ use crate::{
     shadow_pipeline::ShadowPipeline,
 };
 
-// Cap on simultaneous shadow-casting lights, per pool (spot+directional
-// share one pool; point lights have their own — see
-// `RenderSpotDirectionalShadowMaps`/`RenderPointShadowMaps`). This is just a
+// Cap on simultaneous shadow-casting spot lights, and separately on point
+// lights — see `RenderSpotShadowMaps`/`RenderPointShadowMaps`. This is just a
 // safety ceiling now, not the allocated size: each pool's actual GPU texture
 // tracks real demand via `reconcile_capacity`.
 pub(crate) const MAX_SHADOW_CASTERS: u32 = 128;
 
+// Cap on shadow-casting directional lights. Far lower than the other pools:
+// each one reserves `MAX_CASCADES` layers, and its cascades are described by
+// `RenderCascadeUniforms`, a fixed-size uniform array sized by this.
+pub(crate) const MAX_CASCADE_CASTERS: u32 = 4;
+
 const SHADOW_MAP_SIZE: u32 = 1024;
 
 // Minimum consecutive frames a pool's usage must stay below its current
@@ -45,9 +52,9 @@ const SHADOW_MAP_SIZE: u32 = 1024;
 const SHADOW_MAP_SHRINK_DELAY_FRAMES: u32 = 300;
 
 // Not `#[derive(Component)]`: needs a custom `on_remove` to free its slot
-// (in `RenderSpotDirectionalShadowMaps` or `RenderPointShadowMaps`, depending
-// on the light's type) and compact that pool, mirroring `RenderLight`
-// (components/light.rs).
+// (in `RenderSpotShadowMaps`, `RenderPointShadowMaps` or
+// `RenderDirectionalShadowMaps`, depending on the light's type) and compact
+// that pool, mirroring `RenderLight` (components/light.rs).
 #[derive(Clone, Copy, Deref)]
 pub struct RenderShadowCasterSlot(pub(crate) u32);
 
@@ -70,21 +77,26 @@ impl Component for RenderShadowCasterSlot {
                 return;
             };
 
-            let is_point = world
+            let light_type = world
                 .get_component_for_entity::<RenderLight>(context.entity)
-                .is_some_and(|light| light.light_type == LightType::Point.index());
+                .map(|light| light.light_type);
 
-            let moved_entity = if is_point {
+            let moved_entity = if light_type == Some(LightType::Point.index()) {
                 let Some(shadow_maps) = world.get_resource_mut::<RenderPointShadowMaps>() else {
                     return;
                 };
                 shadow_maps.swap_remove_caster(&slot)
-            } else {
-                let Some(shadow_maps) = world.get_resource_mut::<RenderSpotDirectionalShadowMaps>()
+            } else if light_type == Some(LightType::Directional.index()) {
+                let Some(shadow_maps) = world.get_resource_mut::<RenderDirectionalShadowMaps>()
                 else {
                     return;
                 };
                 shadow_maps.swap_remove_caster(&slot)
+            } else {
+                let Some(shadow_maps) = world.get_resource_mut::<RenderSpotShadowMaps>() else {
+                    return;
+                };
+                shadow_maps.swap_remove_caster(&slot)
             };
 
             if let Some(render_light) =
@@ -111,37 +123,50 @@ impl Component for RenderShadowCasterSlot {
     }
 }
 
-// Distinguishes the two shadow-map pool "shapes" that `ShadowMapPool<K>`
-// supports. Spot and directional lights each need a single 2D view per
-// caster; point lights are omnidirectional and need a full cube (6 views)
-// per caster. Everything else — slot allocation, swap-remove compaction,
-// grow/shrink-with-hysteresis — is identical between them, so that's all
-// implemented once against this trait instead of twice.
+// Distinguishes the shadow-map pool "shapes" that `ShadowMapPool<K>`
+// supports. Spot lights need a single 2D view per caster; point lights are
+// omnidirectional and need a full cube (6 views) per caster; directional
+// lights need one 2D view per cascade. Everything else — slot allocation,
+// swap-remove compaction, grow/shrink-with-hysteresis — is identical between
+// them, so that's all implemented once against this trait instead of three
+// times.
 pub(crate) trait ShadowMapKind: 'static {
     const VIEWS_PER_CASTER: u32;
+    const MAX_CASTERS: u32;
     const ARRAY_VIEW_DIMENSION: wgpu::TextureViewDimension;
     const LABEL: &'static str;
 }
 
-pub(crate) struct SpotDirectionalShadowKind;
+pub(crate) struct SpotShadowKind;
 
-impl ShadowMapKind for SpotDirectionalShadowKind {
+impl ShadowMapKind for SpotShadowKind {
     const VIEWS_PER_CASTER: u32 = 1;
+    const MAX_CASTERS: u32 = MAX_SHADOW_CASTERS;
     const ARRAY_VIEW_DIMENSION: wgpu::TextureViewDimension = wgpu::TextureViewDimension::D2Array;
-    const LABEL: &'static str = "spot_directional_shadow_maps";
+    const LABEL: &'static str = "spot_shadow_maps";
 }
 
 pub(crate) struct PointShadowKind;
 
 impl ShadowMapKind for PointShadowKind {
     const VIEWS_PER_CASTER: u32 = 6;
+    const MAX_CASTERS: u32 = MAX_SHADOW_CASTERS;
     const ARRAY_VIEW_DIMENSION: wgpu::TextureViewDimension = wgpu::TextureViewDimension::CubeArray;
     const LABEL: &'static str = "point_shadow_maps";
 }
 
+pub(crate) struct DirectionalShadowKind;
+
+impl ShadowMapKind for DirectionalShadowKind {
+    const VIEWS_PER_CASTER: u32 = MAX_CASCADES as u32;
+    const MAX_CASTERS: u32 = MAX_CASCADE_CASTERS;
+    const ARRAY_VIEW_DIMENSION: wgpu::TextureViewDimension = wgpu::TextureViewDimension::D2Array;
+    const LABEL: &'static str = "directional_shadow_maps";
+}
+
 // Slot-allocated shadow-map depth texture — see `ShadowMapKind` for what
-// varies between `RenderSpotDirectionalShadowMaps` and
-// `RenderPointShadowMaps`. Slots are allocated on demand and kept packed via
+// varies between `RenderSpotShadowMaps`, `RenderPointShadowMaps` and
+// `RenderDirectionalShadowMaps`. Slots are allocated on demand and kept packed via
 // swap-remove, same as `RenderLights::slots` (components/light.rs).
 // `capacity` (the actual GPU texture's per-caster view count) tracks real
 // demand: it grows immediately when `slots` outgrows it, but only shrinks
@@ -158,6 +183,10 @@ pub(crate) struct ShadowMapPool<K: ShadowMapKind> {
     pub(crate) views: Vec<wgpu::TextureView>,
     pub(crate) slots: Vec<Entity>,
     capacity: u32,
+    // Width and height of every layer. Fixed at `SHADOW_MAP_SIZE` for spot and
+    // point lights; the largest `Cascade::resolution` for cascades, each of
+    // which renders into the top-left corner of its layer.
+    size: u32,
     frames_below_capacity: u32,
     _kind: PhantomData<fn() -> K>,
 }
@@ -172,13 +201,14 @@ impl<K: ShadowMapKind> Resource for ShadowMapPool<K> {
 
 impl<K: ShadowMapKind> ShadowMapPool<K> {
     pub(crate) fn new(device: &wgpu::Device) -> Self {
-        let (texture, views) = Self::build_gpu_resources(device, 1);
+        let (texture, views) = Self::build_gpu_resources(device, 1, SHADOW_MAP_SIZE);
 
         Self {
             texture,
             views,
             slots: Vec::new(),
             capacity: 1,
+            size: SHADOW_MAP_SIZE,
             frames_below_capacity: 0,
             _kind: PhantomData,
         }
@@ -187,14 +217,15 @@ impl<K: ShadowMapKind> ShadowMapPool<K> {
     fn build_gpu_resources(
         device: &wgpu::Device,
         capacity: u32,
+        size: u32,
     ) -> (wgpu::Texture, Vec<wgpu::TextureView>) {
         let view_count = capacity * K::VIEWS_PER_CASTER;
 
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some(K::LABEL),
             size: wgpu::Extent3d {
-                width: SHADOW_MAP_SIZE,
-                height: SHADOW_MAP_SIZE,
+                width: size,
+                height: size,
                 depth_or_array_layers: view_count,
             },
             mip_level_count: 1,
@@ -233,9 +264,9 @@ impl<K: ShadowMapKind> ShadowMapPool<K> {
         })
     }
 
-    // `None` once all `MAX_SHADOW_CASTERS` casters are in use.
+    // `None` once all `K::MAX_CASTERS` casters are in use.
     pub(crate) fn push_caster(&mut self, entity: Entity) -> Option<u32> {
-        if self.slots.len() as u32 >= MAX_SHADOW_CASTERS {
+        if self.slots.len() as u32 >= K::MAX_CASTERS {
             return None;
         }
         self.slots.push(entity);
@@ -273,12 +304,25 @@ impl<K: ShadowMapKind> ShadowMapPool<K> {
         }
     }
 
+    // Recreates the texture at `size` if it isn't already. Unlike capacity,
+    // there's no hysteresis: size only changes when a config does, not with
+    // casters coming and going. Returns whether it actually resized, like
+    // `reconcile_capacity`.
+    pub(crate) fn set_size(&mut self, device: &wgpu::Device, size: u32) -> bool {
+        if size == self.size {
+            return false;
+        }
+        self.size = size;
+        self.resize_to(device, self.capacity);
+        true
+    }
+
     pub(crate) fn get_view(&self, index: usize) -> Option<&TextureView> {
         self.views.get(index)
     }
 
     fn resize_to(&mut self, device: &wgpu::Device, capacity: u32) {
-        let (texture, views) = Self::build_gpu_resources(device, capacity);
+        let (texture, views) = Self::build_gpu_resources(device, capacity, self.size);
         self.texture = texture;
         self.views = views;
         self.capacity = capacity;
@@ -286,12 +330,13 @@ impl<K: ShadowMapKind> ShadowMapPool<K> {
     }
 }
 
-pub(crate) type RenderSpotDirectionalShadowMaps = ShadowMapPool<SpotDirectionalShadowKind>;
+pub(crate) type RenderSpotShadowMaps = ShadowMapPool<SpotShadowKind>;
 pub(crate) type RenderPointShadowMaps = ShadowMapPool<PointShadowKind>;
+pub(crate) type RenderDirectionalShadowMaps = ShadowMapPool<DirectionalShadowKind>;
 
-// Shared array of shadow view-proj matrices for *spot/directional* casters
-// only, indexed by `RenderLight::shadow_layer` — read at `@group(2)
-// @binding(5)` by the main lighting shader to project a fragment's world
+// Shared array of shadow view-proj matrices for *spot* casters only,
+// indexed by `RenderLight::shadow_layer` — read at `@group(2) @binding(5)`
+// by the main lighting shader to project a fragment's world
 // position into light-clip-space for shadow sampling.
 //
 // Deliberately separate from `RenderShadowCasterViewProj` below, which the
@@ -330,13 +375,92 @@ impl RenderShadowViewProjs {
     }
 }
 
+// Everything the lighting shader needs to pick and sample one directional
+// light's cascades: its per-cascade view-projs, where each cascade ends, and
+// the camera they were fitted to (cascade selection measures depth from that
+// camera, not whichever one is currently rendering). Mirrors
+// `CascadeCaster` in shader.wgsl.
+#[derive(ShaderType, Clone, Copy)]
+pub(crate) struct CascadeCasterUniform {
+    view_projs: [Mat4; MAX_CASCADES],
+    // Far distance of each cascade; unused lanes past `count` are ignored.
+    far_bounds: Vec4,
+    // Texels across each cascade's corner of its shadow-map layer.
+    resolutions: Vec4,
+    camera_position: Vec3,
+    count: u32,
+    camera_forward: Vec3,
+    blend: f32,
+}
+
+impl CascadeCasterUniform {
+    fn new(cascades: &DirectionalCascades) -> Self {
+        let mut uniform = Self::zeroed();
+        for (i, cascade) in cascades.cascades.iter().take(MAX_CASCADES).enumerate() {
+            uniform.view_projs[i] = cascade.view_proj;
+            uniform.far_bounds[i] = cascade.far;
+            uniform.resolutions[i] = cascade.resolution as f32;
+        }
+        uniform.count = cascades.cascades.len().min(MAX_CASCADES) as u32;
+        uniform.camera_position = cascades.camera_position;
+        uniform.camera_forward = cascades.camera_forward;
+        uniform.blend = cascades.blend;
+        uniform
+    }
+
+    fn zeroed() -> Self {
+        Self {
+            view_projs: [Mat4::IDENTITY; MAX_CASCADES],
+            far_bounds: Vec4::ZERO,
+            resolutions: Vec4::ZERO,
+            camera_position: Vec3::ZERO,
+            count: 0,
+            camera_forward: Vec3::NEG_Z,
+            blend: 0.0,
+        }
+    }
+}
+
+// Shared array of `CascadeCasterUniform`s, indexed by a directional light's
+// `RenderLight::shadow_layer` — read at `@group(2) @binding(7)`. The same
+// split as `RenderShadowViewProjs` vs `RenderShadowCasterViewProj`: the depth
+// pass renders each cascade through `RenderCascadeViewProjs` instead.
+#[derive(Resource)]
+pub(crate) struct RenderCascadeUniforms {
+    buffer: wgpu::Buffer,
+}
+
+impl RenderCascadeUniforms {
+    pub(crate) fn new(device: &wgpu::Device) -> Self {
+        let mut bytes = UniformBuffer::new(Vec::new());
+        bytes
+            .write(&[CascadeCasterUniform::zeroed(); MAX_CASCADE_CASTERS as usize])
+            .unwrap();
+
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("shadow_cascades"),
+            contents: &bytes.into_inner(),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Self { buffer }
+    }
+
+    fn write(&self, queue: &wgpu::Queue, slot: u32, cascades: &CascadeCasterUniform) {
+        let offset = CascadeCasterUniform::SHADER_SIZE.get() * slot as u64;
+        let mut bytes = UniformBuffer::new(Vec::new());
+        bytes.write(cascades).unwrap();
+        queue.write_buffer(&self.buffer, offset, &bytes.into_inner());
+    }
+}
+
 // The combined `@group(2)` bind group consumed by any material with
-// `needs_lighting() == true` — the lights uniform, both shadow-map arrays,
-// and the spot/directional shadow view-proj array, merged into one group
-// (see `LightingLayout`'s doc comment for why). Rebuilt whenever either
-// shadow pool actually resizes (see `resize_shadow_maps`) — the lights and
-// shadow-view-proj buffers never resize, so they never force a rebuild on
-// their own.
+// `needs_lighting() == true` — the lights uniform, all three shadow-map
+// arrays, the spot shadow view-proj array and the cascade uniforms, merged
+// into one group (see `LightingLayout`'s doc comment for why). Rebuilt
+// whenever any shadow pool actually resizes (see `resize_shadow_maps`) — the
+// lights, view-proj and cascade buffers never resize, so they never force a
+// rebuild on their own.
 #[derive(Resource)]
 pub(crate) struct RenderLighting {
     pub(crate) bind_group: wgpu::BindGroup,
@@ -347,19 +471,10 @@ impl RenderLighting {
         device: &wgpu::Device,
         layout: &LightingLayout,
         lights: &RenderLights,
-        spot_directional_shadow_maps: &RenderSpotDirectionalShadowMaps,
-        point_shadow_maps: &RenderPointShadowMaps,
-        shadow_view_projs: &RenderShadowViewProjs,
+        shadow_maps: ShadowMaps<'_>,
     ) -> Self {
         Self {
-            bind_group: Self::build_bind_group(
-                device,
-                layout,
-                lights,
-                spot_directional_shadow_maps,
-                point_shadow_maps,
-                shadow_view_projs,
-            ),
+            bind_group: Self::build_bind_group(device, layout, lights, shadow_maps),
         }
     }
 
@@ -368,33 +483,24 @@ impl RenderLighting {
         device: &wgpu::Device,
         layout: &LightingLayout,
         lights: &RenderLights,
-        spot_directional_shadow_maps: &RenderSpotDirectionalShadowMaps,
-        point_shadow_maps: &RenderPointShadowMaps,
-        shadow_view_projs: &RenderShadowViewProjs,
+        shadow_maps: ShadowMaps<'_>,
     ) {
-        self.bind_group = Self::build_bind_group(
-            device,
-            layout,
-            lights,
-            spot_directional_shadow_maps,
-            point_shadow_maps,
-            shadow_view_projs,
-        );
+        self.bind_group = Self::build_bind_group(device, layout, lights, shadow_maps);
     }
 
     fn build_bind_group(
         device: &wgpu::Device,
         layout: &LightingLayout,
         lights: &RenderLights,
-        spot_directional_shadow_maps: &RenderSpotDirectionalShadowMaps,
-        point_shadow_maps: &RenderPointShadowMaps,
-        shadow_view_projs: &RenderShadowViewProjs,
+        shadow_maps: ShadowMaps<'_>,
     ) -> wgpu::BindGroup {
-        let spot_directional_view = spot_directional_shadow_maps.array_view();
-        let point_view = point_shadow_maps.array_view();
+        let spot_view = shadow_maps.spot.array_view();
+        let point_view = shadow_maps.point.array_view();
+        let directional_view = shadow_maps.directional.array_view();
 
-        // Both pools use identical comparison-sampler settings, so one
-        // shared sampler covers both shadow bindings.
+        // All pools use identical comparison-sampler settings, so one
+        // shared sampler covers every shadow binding; the cascade array is
+        // sampled through binding 2's.
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("lighting_shadow_sampler"),
             mag_filter: wgpu::FilterMode::Linear,
@@ -413,7 +519,7 @@ impl RenderLighting {
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::TextureView(&spot_directional_view),
+                    resource: wgpu::BindingResource::TextureView(&spot_view),
                 },
                 wgpu::BindGroupEntry {
                     binding: 2,
@@ -429,67 +535,94 @@ impl RenderLighting {
                 },
                 wgpu::BindGroupEntry {
                     binding: 5,
-                    resource: shadow_view_projs.buffer.as_entire_binding(),
+                    resource: shadow_maps.view_projs.buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&directional_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 7,
+                    resource: shadow_maps.cascades.buffer.as_entire_binding(),
                 },
             ],
         })
     }
 }
 
-// Reconciles both shadow-map pools' GPU texture capacity to actual demand
-// once per frame, and rebuilds `RenderLighting`'s combined bind group if
-// either pool actually resized. Runs after commands from
+// The shadow resources `RenderLighting` binds, bundled so adding a pool
+// doesn't mean threading yet another argument through `new`/`rebuild`.
+#[derive(Clone, Copy)]
+pub(crate) struct ShadowMaps<'a> {
+    pub(crate) spot: &'a RenderSpotShadowMaps,
+    pub(crate) point: &'a RenderPointShadowMaps,
+    pub(crate) directional: &'a RenderDirectionalShadowMaps,
+    pub(crate) view_projs: &'a RenderShadowViewProjs,
+    pub(crate) cascades: &'a RenderCascadeUniforms,
+}
+
+// Reconciles every shadow-map pool's GPU texture capacity to actual demand
+// once per frame — and the cascade pool's resolution to the largest one any
+// directional light asks for — and rebuilds `RenderLighting`'s combined bind
+// group if any pool actually resized. Runs after commands from
 // `RenderLight::on_add`/`RenderShadowCasterSlot::on_remove` have flushed, so
 // if several casters are added or removed in the same tick, this only
 // resizes once for the lot — not once per entity.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn resize_shadow_maps(
-    mut spot_directional_shadow_maps: ResMut<RenderSpotDirectionalShadowMaps>,
+    mut spot_shadow_maps: ResMut<RenderSpotShadowMaps>,
     mut point_shadow_maps: ResMut<RenderPointShadowMaps>,
+    mut directional_shadow_maps: ResMut<RenderDirectionalShadowMaps>,
     mut lighting: ResMut<RenderLighting>,
     device: Res<RenderDevice>,
     lighting_layout: Res<LightingLayout>,
     lights: Res<RenderLights>,
     shadow_view_projs: Res<RenderShadowViewProjs>,
+    cascade_uniforms: Res<RenderCascadeUniforms>,
+    cascades: Query<&DirectionalCascades>,
 ) {
-    let spot_directional_resized = spot_directional_shadow_maps.reconcile_capacity(&device);
+    let spot_resized = spot_shadow_maps.reconcile_capacity(&device);
     let point_resized = point_shadow_maps.reconcile_capacity(&device);
-
-    if spot_directional_resized || point_resized {
+    let mut directional_resized = directional_shadow_maps.reconcile_capacity(&device);
+
+    let resolution = cascades
+        .iter()
+        .flat_map(|cascades| cascades.cascades())
+        .map(|cascade| cascade.resolution)
+        .max()
+        .unwrap_or(SHADOW_MAP_SIZE)
+        .clamp(1, device.limits().max_texture_dimension_2d);
+    directional_resized |= directional_shadow_maps.set_size(&device, resolution);
+
+    if spot_resized || point_resized || directional_resized {
         lighting.rebuild(
             &device,
             &lighting_layout,
             &lights,
-            &spot_directional_shadow_maps,
-            &point_shadow_maps,
-            &shadow_view_projs,
+            ShadowMaps {
+                spot: &spot_shadow_maps,
+                point: &point_shadow_maps,
+                directional: &directional_shadow_maps,
+                view_projs: &shadow_view_projs,
+                cascades: &cascade_uniforms,
+            },
         );
     }
 }
 
-const DIRECTIONAL_SHADOW_DISTANCE: f32 = 50.0;
-const DIRECTIONAL_SHADOW_HALF_EXTENT: f32 = 50.0;
-const DIRECTIONAL_SHADOW_NEAR: f32 = 0.1;
-const DIRECTIONAL_SHADOW_FAR: f32 = 200.0;
-
 // Spot lights have no explicit range in this engine yet, so this is just a
 // generous fixed far plane rather than something derived from the light.
 const SPOT_SHADOW_NEAR: f32 = 0.1;
 const SPOT_SHADOW_FAR: f32 = 100.0;
 
-// `Vec3::Y` degenerates as an up vector once `direction` is nearly parallel
-// to it (straight up/down directional or spot lights), so fall back to Z.
-fn shadow_up_vector(direction: Vec3) -> Vec3 {
-    if direction.dot(Vec3::Y).abs() > 0.999 {
-        Vec3::Z
-    } else {
-        Vec3::Y
-    }
-}
-
 #[derive(Component)]
 pub(crate) struct RenderShadowCasterViewProj {
     buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
+    // The volume the depth pass sees, for culling casters outside it. `None`
+    // until the first view-proj is written, so nothing is culled against the
+    // placeholder identity matrix in the meantime.
+    frustum: Option<Frustum>,
 }
 
 impl RenderShadowCasterViewProj {
@@ -512,10 +645,15 @@ impl RenderShadowCasterViewProj {
             }],
         });
 
-        Self { buffer, bind_group }
+        Self {
+            buffer,
+            bind_group,
+            frustum: None,
+        }
     }
 
-    pub(crate) fn write(&self, queue: &wgpu::Queue, view_proj: Mat4) {
+    pub(crate) fn write(&mut self, queue: &wgpu::Queue, view_proj: Mat4) {
+        self.frustum = Some(Frustum::from_view_proj(view_proj));
         let mut bytes = UniformBuffer::new(Vec::new());
         bytes.write(&view_proj).unwrap();
         queue.write_buffer(&self.buffer, 0, &bytes.into_inner());
@@ -523,18 +661,19 @@ impl RenderShadowCasterViewProj {
 }
 
 pub(crate) fn update_shadow_view_proj(
-    lights: Query<(&RenderLight, &RenderShadowCasterViewProj), Changed<RenderLight>>,
+    lights: Query<(&RenderLight, &mut RenderShadowCasterViewProj), Changed<RenderLight>>,
     shadow_view_projs: Res<RenderShadowViewProjs>,
     queue: Res<RenderQueue>,
 ) {
-    for (light, view_proj) in lights.iter() {
+    for (light, mut view_proj) in lights.iter() {
         let matrix = shadow_view_proj(light);
         view_proj.write(&queue, matrix);
 
         // `shadow_layer` for a point light indexes into the *point* shadow
-        // pool, not the spot/directional one this array covers — writing it
-        // here regardless would clobber an unrelated spot/directional
-        // light's entry sharing that same slot number.
+        // pool, not the spot one this array covers — writing it here
+        // regardless would clobber an unrelated spot light's entry sharing
+        // that same slot number. Directional lights never get here: they
+        // carry `RenderCascadeViewProjs` instead.
         if light.light_type != LightType::Point.index() {
             if let Ok(slot) = u32::try_from(light.shadow_layer) {
                 shadow_view_projs.write(&queue, slot, matrix);
@@ -545,43 +684,96 @@ pub(crate) fn update_shadow_view_proj(
 
 fn shadow_view_proj(light: &RenderLight) -> Mat4 {
     let up = shadow_up_vector(light.direction);
+    let view = Mat4::look_at_rh(light.translation, light.translation + light.direction, up);
+    let fov = 2.0 * light.cos_cone_angle.clamp(-1.0, 1.0).acos();
+    let proj = Mat4::perspective_rh(fov, 1.0, SPOT_SHADOW_NEAR, SPOT_SHADOW_FAR);
+    proj * view
+}
 
-    if light.light_type == LightType::Directional.index() {
-        let eye = -light.direction * DIRECTIONAL_SHADOW_DISTANCE;
-        let view = Mat4::look_at_rh(eye, eye + light.direction, up);
-        let half = DIRECTIONAL_SHADOW_HALF_EXTENT;
-        let proj = Mat4::orthographic_rh(
-            -half,
-            half,
-            -half,
-            half,
-            DIRECTIONAL_SHADOW_NEAR,
-            DIRECTIONAL_SHADOW_FAR,
-        );
-        proj * view
-    } else {
-        let view = Mat4::look_at_rh(light.translation, light.translation + light.direction, up);
-        let fov = 2.0 * light.cos_cone_angle.clamp(-1.0, 1.0).acos();
-        let proj = Mat4::perspective_rh(fov, 1.0, SPOT_SHADOW_NEAR, SPOT_SHADOW_FAR);
-        proj * view
+// One depth-pass view-proj per cascade of a directional light, rendering into
+// layers `shadow_layer * MAX_CASCADES..` of `RenderDirectionalShadowMaps`.
+// Takes the place of `RenderShadowCasterViewProj` for directional lights.
+#[derive(Component)]
+pub(crate) struct RenderCascadeViewProjs {
+    cascades: [RenderShadowCasterViewProj; MAX_CASCADES],
+    // How many of `cascades` the light's `CascadeShadowConfig` asks for.
+    count: usize,
+    // Texels across the corner of its layer each cascade renders into.
+    resolutions: [u32; MAX_CASCADES],
+}
+
+impl RenderCascadeViewProjs {
+    pub(crate) fn new(device: &wgpu::Device, layout: &wgpu::BindGroupLayout) -> Self {
+        Self {
+            cascades: std::array::from_fn(|_| RenderShadowCasterViewProj::new(device, layout)),
+            count: 0,
+            resolutions: [0; MAX_CASCADES],
+        }
+    }
+}
+
+// Uploads the cascades `update_directional_cascades` fitted this frame, both
+// for the depth pass and for the lighting shader. Runs every frame, since
+// cascades follow the camera.
+pub(crate) fn update_cascade_view_projs(
+    lights: Query<(&DirectionalCascades, &RenderEntity)>,
+    render_lights: Query<(&RenderLight, &mut RenderCascadeViewProjs)>,
+    cascade_uniforms: Res<RenderCascadeUniforms>,
+    queue: Res<RenderQueue>,
+) {
+    for (cascades, render_entity) in lights.iter() {
+        let Some((light, mut view_projs)) = render_lights.get_entity(**render_entity) else {
+            continue;
+        };
+        let Ok(slot) = u32::try_from(light.shadow_layer) else {
+            continue;
+        };
+
+        for (view_proj, cascade) in view_projs.cascades.iter_mut().zip(&cascades.cascades) {
+            view_proj.write(&queue, cascade.view_proj);
+        }
+        for (resolution, cascade) in view_projs.resolutions.iter_mut().zip(&cascades.cascades) {
+            *resolution = cascade.resolution;
+        }
+        view_projs.count = cascades.cascades.len().min(MAX_CASCADES);
+
+        cascade_uniforms.write(&queue, slot, &CascadeCasterUniform::new(cascades));
     }
 }
 
-// Renders one depth-only pass per shadow-casting spot/directional light into
-// its slot in `RenderSpotDirectionalShadowMaps`. Every mesh instance is
-// redrawn into every caster with no culling — same as `material_renderpass`,
-// which redraws every instance per camera today.
+// Renders one depth-only pass per shadow-casting spot light into its slot in
+// `RenderSpotShadowMaps`, and one per cascade of each shadow-casting
+// directional light into `RenderDirectionalShadowMaps`. Each pass only draws
+// the mesh instances whose bounds reach into its caster's frustum.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn render_shadow_maps(
     pipeline: Res<ShadowPipeline>,
     mut device: ResMut<RenderDevice>,
-    spot_directional_shadow_maps: Res<RenderSpotDirectionalShadowMaps>,
+    spot_shadow_maps: Res<RenderSpotShadowMaps>,
     _point_shadow_maps: Res<RenderPointShadowMaps>,
-    lights: Query<(&RenderLight, &RenderShadowCasterViewProj)>,
+    directional_shadow_maps: Res<RenderDirectionalShadowMaps>,
+    spot_lights: Query<(&RenderLight, &RenderShadowCasterViewProj)>,
+    directional_lights: Query<(&RenderLight, &RenderCascadeViewProjs)>,
     render_mesh_query: Query<(&RenderMeshInstance, Option<&RenderSkeletonComponent>)>,
     render_meshes: Res<RenderAssets<RenderMesh>>,
     skins: Res<SkinUniforms>,
 ) {
-    for (light, view_proj) in lights.iter() {
+    let encoder = device.command_encoder();
+    let mut draw =
+        |view: &TextureView, viewport: Option<u32>, view_proj: &RenderShadowCasterViewProj| {
+            draw_shadow_casters(
+                encoder,
+                view,
+                viewport,
+                view_proj,
+                &pipeline,
+                &render_mesh_query,
+                &render_meshes,
+                &skins,
+            )
+        };
+
+    for (light, view_proj) in spot_lights.iter() {
         if light.light_type == LightType::Point.index() {
             // TODO: point-light (cube) shadows.
             continue;
@@ -591,39 +783,82 @@ pub(crate) fn render_shadow_maps(
             continue;
         };
 
-        let Some(view) = spot_directional_shadow_maps.get_view(layer) else {
+        let Some(view) = spot_shadow_maps.get_view(layer) else {
             continue;
         };
 
-        let encoder = device.command_encoder();
-        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
-            label: Some("Shadow Depth Render Pass"),
-            color_attachments: &[],
-            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
-                view,
-                depth_ops: Some(Operations {
-                    load: wgpu::LoadOp::Clear(1.0),
-                    store: StoreOp::Store,
-                }),
-                stencil_ops: None,
-            }),
-            timestamp_writes: None,
-            occlusion_query_set: None,
-        });
+        draw(view, None, view_proj);
+    }
+
+    for (light, view_projs) in directional_lights.iter() {
+        let Ok(caster) = usize::try_from(light.shadow_layer) else {
+            continue;
+        };
 
-        render_pass.set_pipeline(&pipeline.pipeline);
-        render_pass.set_bind_group(0, &view_proj.bind_group, &[]);
+        for (cascade, view_proj) in view_projs.cascades[..view_projs.count].iter().enumerate() {
+            let Some(view) = directional_shadow_maps.get_view(caster * MAX_CASCADES + cascade)
+            else {
+                continue;
+            };
 
-        for (mesh_instance, skeleton) in render_mesh_query.iter() {
-            if let Some(mesh) = render_meshes.get(&mesh_instance.mesh_asset_id) {
-                let offset = skeleton.map_or(0, |sk| sk.offset);
-                render_pass.set_bind_group(1, skins.bind_group(), &[offset]);
+            let resolution = view_projs.resolutions[cascade].clamp(1, directional_shadow_maps.size);
+            draw(view, Some(resolution), view_proj);
+        }
+    }
+}
 
-                render_pass.set_vertex_buffer(0, mesh.vertices.slice(..));
-                render_pass.set_index_buffer(mesh.indices.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.set_vertex_buffer(1, mesh_instance.transform.slice(..));
-                render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
-            }
+// Records one depth-only pass into `view`, drawing every mesh instance
+// visible through `view_proj` — into only its top-left `viewport` texels
+// square, if given.
+#[allow(clippy::too_many_arguments)]
+fn draw_shadow_casters(
+    encoder: &mut wgpu::CommandEncoder,
+    view: &TextureView,
+    viewport: Option<u32>,
+    view_proj: &RenderShadowCasterViewProj,
+    pipeline: &ShadowPipeline,
+    render_mesh_query: &Query<(&RenderMeshInstance, Option<&RenderSkeletonComponent>)>,
+    render_meshes: &RenderAssets<RenderMesh>,
+    skins: &SkinUniforms,
+) {
+    let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+        label: Some("Shadow Depth Render Pass"),
+        color_attachments: &[],
+        depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: Some(Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    render_pass.set_pipeline(&pipeline.pipeline);
+    if let Some(size) = viewport {
+        render_pass.set_viewport(0.0, 0.0, size as f32, size as f32, 0.0, 1.0);
+    }
+    render_pass.set_bind_group(0, &view_proj.bind_group, &[]);
+
+    for (mesh_instance, skeleton) in render_mesh_query.iter() {
+        let culled = view_proj
+            .frustum
+            .as_ref()
+            .is_some_and(|frustum| !mesh_instance.is_visible(frustum));
+        if culled {
+            continue;
+        }
+
+        if let Some(mesh) = render_meshes.get(&mesh_instance.mesh_asset_id) {
+            let offset = skeleton.map_or(0, |sk| sk.offset);
+            render_pass.set_bind_group(1, skins.bind_group(), &[offset]);
+
+            render_pass.set_vertex_buffer(0, mesh.vertices.slice(..));
+            render_pass.set_index_buffer(mesh.indices.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.set_vertex_buffer(1, mesh_instance.transform.slice(..));
+            render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
         }
     }
 }
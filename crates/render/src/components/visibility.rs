@@ -0,0 +1,179 @@
+//! Frustum culling.
+//!
+//! Every mesh entity gets a world-space [`Aabb`], refreshed each frame from
+//! its `GlobalTransform` and its mesh's local bounds — or, for skinned meshes,
+//! from its bones, and copied to its [`RenderMeshInstance`]. Material passes
+//! then skip instances whose box lies outside the camera's frustum, and
+//! shadow passes those outside the light's.
+
+use ecs::{
+    command::CommandQueue,
+    component::Component,
+    entity::Entity,
+    query::{change_detection::Mut, Query},
+    resource::Res,
+    Without,
+};
+use essential::{
+    assets::{asset_store::AssetStore, AssetId},
+    transform::GlobalTransform,
+};
+use mesh::{bounds::Aabb, mesh::MeshComponent, skeleton::SkeletonComponent, Mesh, Skeleton};
+
+use crate::{
+    components::{mesh::RenderMeshInstance, render_entity::RenderEntity},
+    render_asset::{render_mesh::RenderMesh, RenderAssets},
+};
+
+// Bind-pose bounds of a skinned mesh's vertices per joint, cached on the mesh
+// entity since finding them walks every vertex. Recomputed if the entity
+// switches mesh or skeleton.
+#[derive(Component)]
+pub(crate) struct SkinnedMeshBounds {
+    mesh: AssetId,
+    skeleton: AssetId,
+    joints: Vec<Option<Aabb>>,
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_mesh_aabbs(
+    meshes: Query<
+        (
+            Entity,
+            &MeshComponent,
+            &GlobalTransform,
+            Option<&mut Aabb>,
+            Option<&RenderEntity>,
+        ),
+        Without<SkeletonComponent>,
+    >,
+    render_instances: Query<&mut RenderMeshInstance>,
+    render_meshes: Res<RenderAssets<RenderMesh>>,
+    mut cmd: CommandQueue,
+) {
+    for (entity, mesh, transform, aabb, render_entity) in meshes.iter() {
+        // Not uploaded yet, so there's nothing to draw — or to bound.
+        let Some(bounds) = render_meshes
+            .get(&mesh.handle.id())
+            .and_then(|render_mesh| render_mesh.bounds)
+        else {
+            continue;
+        };
+
+        let world_aabb = bounds.aabb.transformed(transform.matrix());
+        set_aabb(
+            entity,
+            aabb,
+            render_entity,
+            &render_instances,
+            world_aabb,
+            &mut cmd,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_skinned_mesh_aabbs(
+    meshes: Query<(
+        Entity,
+        &MeshComponent,
+        &SkeletonComponent,
+        Option<&mut SkinnedMeshBounds>,
+        Option<&mut Aabb>,
+        Option<&RenderEntity>,
+    )>,
+    transforms: Query<&GlobalTransform>,
+    render_instances: Query<&mut RenderMeshInstance>,
+    mesh_assets: Res<AssetStore<Mesh>>,
+    skeleton_assets: Res<AssetStore<Skeleton>>,
+    mut cmd: CommandQueue,
+) {
+    for (entity, mesh, skeleton, cached, aabb, render_entity) in meshes.iter() {
+        let mesh_id = mesh.handle.id();
+        let skeleton_id = skeleton.skeleton().id();
+
+        let world_aabb = match cached {
+            Some(cached) if cached.mesh == mesh_id && cached.skeleton == skeleton_id => {
+                skinned_aabb(&cached.joints, skeleton.bones(), &transforms)
+            }
+            cached => {
+                let (Some(mesh_asset), Some(skeleton_asset)) = (
+                    mesh_assets.get(&mesh.handle),
+                    skeleton_assets.get(skeleton.skeleton()),
+                ) else {
+                    continue;
+                };
+                let bounds = SkinnedMeshBounds {
+                    mesh: mesh_id,
+                    skeleton: skeleton_id,
+                    joints: mesh_asset.compute_joint_bounds(&skeleton_asset.inverse_bindposes),
+                };
+                let world_aabb = skinned_aabb(&bounds.joints, skeleton.bones(), &transforms);
+                match cached {
+                    Some(mut cached) => **cached = bounds,
+                    None => {
+                        cmd.insert(bounds, entity);
+                    }
+                }
+                world_aabb
+            }
+        };
+
+        if let Some(world_aabb) = world_aabb {
+            set_aabb(
+                entity,
+                aabb,
+                render_entity,
+                &render_instances,
+                world_aabb,
+                &mut cmd,
+            );
+        }
+    }
+}
+
+// The union of each joint's bounds carried along by its bone. Joints whose
+// bone is missing are skipped, which can only shrink the result, but a
+// skeleton missing bones doesn't skin properly either.
+fn skinned_aabb(
+    joints: &[Option<Aabb>],
+    bones: &[Entity],
+    transforms: &Query<&GlobalTransform>,
+) -> Option<Aabb> {
+    joints
+        .iter()
+        .zip(bones)
+        .filter_map(|(joint, bone)| {
+            let bone_transform = transforms.get_entity(*bone)?;
+            Some(joint.as_ref()?.transformed(bone_transform.matrix()))
+        })
+        .reduce(|a, b| a.union(&b))
+}
+
+// Stores `world_aabb` on the mesh entity and its render instance, touching
+// the component only when the box actually moved.
+fn set_aabb(
+    entity: Entity,
+    aabb: Option<Mut<'_, Aabb>>,
+    render_entity: Option<&RenderEntity>,
+    render_instances: &Query<&mut RenderMeshInstance>,
+    world_aabb: Aabb,
+    cmd: &mut CommandQueue,
+) {
+    match aabb {
+        Some(mut aabb) => {
+            if **aabb != world_aabb {
+                **aabb = world_aabb;
+            }
+        }
+        None => {
+            cmd.insert(world_aabb, entity);
+        }
+    }
+
+    if let Some(mut instance) =
+        render_entity.and_then(|render_entity| render_instances.get_entity(**render_entity))
+    {
+        instance.aabb = Some(world_aabb);
+    }
+}
@@ -61,8 +61,8 @@ impl SkeletonLayout {
 }
 
 // Bind-group layout for `@group(2)` in the default material convention:
-// the lights uniform, both shadow-map arrays, and the spot/directional
-// shadow view-proj array, merged into one group.
+// the lights uniform, all three shadow-map arrays, the spot shadow view-proj
+// array and the directional cascade uniforms, merged into one group.
 // wgpu only guarantees 4 bind groups (`max_bind_groups`); camera(1) +
 // lighting(2) + skeleton(3) fits that without requesting an elevated device
 // limit, whereas splitting lights/spot-directional-shadows/point-shadows
@@ -124,8 +124,8 @@ impl LightingLayout {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
                     count: None,
                 },
-                // Spot/directional shadow view-proj matrices, indexed by
-                // `shadow_layer` in the shader — see `RenderShadowViewProjs`.
+                // Spot shadow view-proj matrices, indexed by `shadow_layer`
+                // in the shader — see `RenderShadowViewProjs`.
                 wgpu::BindGroupLayoutEntry {
                     binding: 5,
                     visibility: wgpu::ShaderStages::FRAGMENT,
@@ -136,6 +136,30 @@ impl LightingLayout {
                     },
                     count: None,
                 },
+                // Directional cascade shadow maps, `MAX_CASCADES` layers per
+                // caster. Sampled through binding 2's comparison sampler.
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                // Per-caster cascade view-projs and split distances — see
+                // `RenderCascadeUniforms`.
+                wgpu::BindGroupLayoutEntry {
+                    binding: 7,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
 
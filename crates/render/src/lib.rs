@@ -9,6 +9,7 @@ pub mod layouts;
 pub mod loaders;
 pub mod material_plugin;
 pub mod plugin;
+pub mod post_process_pipeline;
 pub mod queue;
 pub mod render_asset;
 pub mod resources;
@@ -1,7 +1,9 @@
 use anyhow::Context;
 use essential::assets::{
-    asset_loader::AssetLoader, asset_server::AssetLoadContext, utils::load_binary, AssetPath,
-    LoadableAsset,
+    asset_loader::AssetLoader,
+    asset_server::AssetLoadContext,
+    bake::{content_hash, BakeKey},
+    AssetPath, LoadableAsset,
 };
 
 use async_trait::async_trait;
@@ -18,16 +20,24 @@ impl AssetLoader for TextureLoader {
     async fn load(
         &self,
         path: AssetPath<'static>,
-        _load_context: &mut AssetLoadContext,
+        load_context: &mut AssetLoadContext,
         usage_settings: <Self::Asset as LoadableAsset>::UsageSettings,
     ) -> anyhow::Result<Self::Asset> {
-        let data = load_binary(path.clone()).await?;
+        let data = load_context.read(&path).await?;
 
-        Texture::from_bytes(&data, usage_settings).with_context(|| {
+        let key = BakeKey::new(&path, content_hash(&data))
+            .with_settings(usage_settings.texture_descriptor.format);
+        if let Some(texture) = load_context.baked::<Texture>(&key) {
+            return Ok(texture.with_usage_settings(usage_settings));
+        }
+
+        let texture = Texture::from_bytes(&data, usage_settings).with_context(|| {
             format!(
                 "failed to create texture from '{}'",
                 path.to_path().display()
             )
-        })
+        })?;
+        load_context.bake(&key, &texture);
+        Ok(texture)
     }
 }
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{collections::HashMap, marker::PhantomData, ops::Range};
 
 use app::plugins::Plugin;
 use ecs::{
@@ -7,25 +7,32 @@ use ecs::{
     query::{query_filter::Added, Query},
     resource::{Res, ResMut, Resource},
     system::{input::SystemInputData, schedule::UpdateGroup},
+    IntoSystemConfig,
 };
-use mesh::mesh::MeshComponent;
+use essential::{assets::AssetId, transform::GlobalTransformRaw};
+use mesh::{
+    bounds::{Aabb, Frustum},
+    mesh::MeshComponent,
+};
+use wgpu::util::DeviceExt;
 
 use crate::{
     assets::material::ShaderRef,
     components::{
-        camera::RenderCamera,
+        camera::{RenderCamera, HDR_FORMAT},
         material::{MaterialComponent, RenderMaterialComponent},
-        mesh::RenderMeshInstance,
+        mesh::{is_visible, RenderMeshInstance},
         render_entity::RenderEntity,
         shadows::RenderLighting,
         skeleton::{RenderSkeletonComponent, SkinUniforms},
     },
     device::RenderDevice,
     layouts::{CameraLayout, LightingLayout, SkeletonLayout},
+    post_process_pipeline::ScenePasses,
+    queue::RenderQueue,
     render_asset::{
         render_mesh::RenderMesh,
         render_texture::{DummyRenderTexture, RenderTexture},
-        render_window::RenderWindow,
         AssetPreparationError, RenderAsset, RenderAssetPlugin, RenderAssets,
     },
     resources::RenderContext,
@@ -58,7 +65,7 @@ use crate::{
 // |-------|----------------------------------------|--------------------------------|
 // | 0     | Material's own bindings                | always                         |
 // | 1     | Camera uniform                         | `M::needs_camera()` → true     |
-// | 2     | Lighting uniform + both shadow-map arrays | `M::needs_lighting()` → true |
+// | 2     | Lighting uniform + all shadow-map arrays | `M::needs_lighting()` → true  |
 // | 3     | Skeleton (bone) uniforms               | `M::needs_skeleton()` → true   |
 //
 // Your WGSL only needs to declare the groups (and, within group 2, the
@@ -97,6 +104,48 @@ impl<M: 'static> Resource for MaterialPipeline<M> {
     }
 }
 
+// ─── MaterialBatches ──────────────────────────────────────────────────────────
+
+// Visible unskinned instances of `M`, grouped per camera by (mesh, material)
+// so each group is drawn with a single instanced call.
+//
+// Rebuilt every frame by [`prepare_material_batches<M>`]. The transforms of
+// every camera's batches share one instance buffer, each batch owning a range
+// of it; skinned meshes are left out and drawn one by one, since each needs
+// its own skin offset.
+pub struct MaterialBatches<M: 'static> {
+    instances: Option<wgpu::Buffer>,
+    // Capacity of `instances`, in transforms.
+    capacity: usize,
+    cameras: HashMap<Entity, Vec<MaterialBatch>>,
+    _marker: PhantomData<fn() -> M>,
+}
+
+struct MaterialBatch {
+    mesh_asset_id: AssetId,
+    material_asset_id: AssetId,
+    // Range of `MaterialBatches::instances`, in transforms.
+    instances: Range<u32>,
+}
+
+impl<M: 'static> Default for MaterialBatches<M> {
+    fn default() -> Self {
+        Self {
+            instances: None,
+            capacity: 0,
+            cameras: HashMap::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+// Manual Resource impl — #[derive(Resource)] doesn't handle PhantomData<fn()>.
+impl<M: 'static> Resource for MaterialBatches<M> {
+    fn name() -> &'static str {
+        std::any::type_name::<MaterialBatches<M>>()
+    }
+}
+
 // GPU-side representation of a prepared `M` instance.
 //
 // Stores the `wgpu::BindGroup` built from the material's data and ready to be
@@ -163,22 +212,13 @@ pub(crate) fn material_added<M: Material>(
 pub(crate) fn clear_cameras(
     mut device: ResMut<RenderDevice>,
     render_cameras: Query<&RenderCamera>,
-    render_window: Res<RenderWindow>,
 ) {
     let encoder = device.command_encoder();
     for render_camera in render_cameras.iter() {
-        let swapchain_view = render_window.get_view();
-        let color_view: &wgpu::TextureView = match &render_camera.render_target {
-            Some(rt) => &rt.view,
-            None => match swapchain_view {
-                Some(v) => v,
-                None => continue,
-            },
-        };
         let _pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Clear Pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: color_view,
+                view: &render_camera.hdr_target.view,
                 resolve_target: None,
                 ops: wgpu::Operations {
                     load: wgpu::LoadOp::Clear(wgpu::Color {
@@ -204,11 +244,116 @@ pub(crate) fn clear_cameras(
     }
 }
 
+// What batching needs to know about one instance of a material.
+struct BatchInstance {
+    mesh_asset_id: AssetId,
+    material_asset_id: AssetId,
+    skinned: bool,
+    aabb: Option<Aabb>,
+    transform: GlobalTransformRaw,
+}
+
+// Groups the unskinned `instances` visible through `frustum` by (mesh,
+// material), appending each group's transforms to `transforms`.
+//
+// Batches come out in the order their first instance was seen, so blended
+// materials draw in the same order every frame.
+fn group_batches(
+    instances: impl IntoIterator<Item = BatchInstance>,
+    frustum: &Frustum,
+    transforms: &mut Vec<GlobalTransformRaw>,
+) -> Vec<MaterialBatch> {
+    let mut group_indices: HashMap<(AssetId, AssetId), usize> = HashMap::new();
+    let mut groups: Vec<((AssetId, AssetId), Vec<GlobalTransformRaw>)> = Vec::new();
+    for instance in instances {
+        if instance.skinned || !is_visible(instance.aabb, frustum) {
+            continue;
+        }
+        let key = (instance.mesh_asset_id, instance.material_asset_id);
+        let index = *group_indices.entry(key).or_insert_with(|| {
+            groups.push((key, Vec::new()));
+            groups.len() - 1
+        });
+        groups[index].1.push(instance.transform);
+    }
+
+    groups
+        .into_iter()
+        .map(|((mesh_asset_id, material_asset_id), group)| {
+            let start = transforms.len() as u32;
+            transforms.extend(group);
+            MaterialBatch {
+                mesh_asset_id,
+                material_asset_id,
+                instances: start..transforms.len() as u32,
+            }
+        })
+        .collect()
+}
+
+// Groups each camera's visible, unskinned `M` instances into
+// [`MaterialBatches<M>`] and uploads their transforms.
+#[allow(clippy::type_complexity)]
+pub(crate) fn prepare_material_batches<M: Material>(
+    mut batches: ResMut<MaterialBatches<M>>,
+    render_mesh_query: Query<(
+        &RenderMeshInstance,
+        Option<&RenderSkeletonComponent>,
+        &RenderMaterialComponent<M>,
+    )>,
+    render_cameras: Query<(Entity, &RenderCamera)>,
+    device: Res<RenderDevice>,
+    queue: Res<RenderQueue>,
+) {
+    let batches = &mut *batches;
+    batches.cameras.clear();
+
+    let mut transforms: Vec<GlobalTransformRaw> = Vec::new();
+    for (camera_entity, render_camera) in render_cameras.iter() {
+        let instances =
+            render_mesh_query
+                .iter()
+                .map(|(mesh_instance, skeleton, render_mat_comp)| BatchInstance {
+                    mesh_asset_id: mesh_instance.mesh_asset_id,
+                    material_asset_id: render_mat_comp.material_asset_id,
+                    skinned: skeleton.is_some(),
+                    aabb: mesh_instance.aabb,
+                    transform: mesh_instance.raw_transform,
+                });
+        let camera_batches = group_batches(instances, &render_camera.frustum, &mut transforms);
+        batches.cameras.insert(camera_entity, camera_batches);
+    }
+
+    if transforms.is_empty() {
+        return;
+    }
+
+    // Grow to the next power of two so a slowly growing scene doesn't
+    // reallocate every frame.
+    if transforms.len() > batches.capacity {
+        let capacity = transforms.len().next_power_of_two();
+        let mut contents = bytemuck::cast_slice::<_, u8>(&transforms).to_vec();
+        contents.resize(capacity * std::mem::size_of::<GlobalTransformRaw>(), 0);
+        batches.instances = Some(
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Batched Instance Buffer"),
+                contents: &contents,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            }),
+        );
+        batches.capacity = capacity;
+    } else if let Some(instances) = &batches.instances {
+        queue.write_buffer(instances, 0, bytemuck::cast_slice(&transforms));
+    }
+}
+
 // Render pass for meshes that use material `M`.
 //
 // Only processes entities tagged with [`RenderMaterialComponent<M>`] so
 // multiple `MaterialPlugin` instantiations for different material types can
-// coexist without interfering with each other.
+// coexist without interfering with each other. Unskinned instances are drawn
+// from [`MaterialBatches<M>`], one instanced call per (mesh, material);
+// skinned ones are drawn individually.
 pub(crate) fn material_renderpass<M: Material>(
     pipeline: Res<MaterialPipeline<M>>,
     mut device: ResMut<RenderDevice>,
@@ -217,36 +362,26 @@ pub(crate) fn material_renderpass<M: Material>(
         Option<&RenderSkeletonComponent>,
         &RenderMaterialComponent<M>,
     )>,
-    render_cameras: Query<&RenderCamera>,
+    render_cameras: Query<(Entity, &RenderCamera)>,
+    batches: Res<MaterialBatches<M>>,
     render_meshes: Res<RenderAssets<RenderMesh>>,
     render_materials: Res<RenderAssets<RenderMaterial<M>>>,
-    render_window: Res<RenderWindow>,
     render_lighting: Res<RenderLighting>,
     skins: Res<SkinUniforms>,
 ) {
     let encoder = device.command_encoder();
 
-    for render_camera in render_cameras.iter() {
+    for (camera_entity, render_camera) in render_cameras.iter() {
         let depth_load = if M::clear_depth() {
             wgpu::LoadOp::Clear(1.0)
         } else {
             wgpu::LoadOp::Load
         };
 
-        // Route to RTT for texture-targeted cameras, or to the swapchain for window cameras.
-        let swapchain_view = render_window.get_view();
-        let color_view: &wgpu::TextureView = match &render_camera.render_target {
-            Some(rt) => &rt.view,
-            None => match swapchain_view {
-                Some(v) => v,
-                None => continue,
-            },
-        };
-
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Material Render Pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: color_view,
+                view: &render_camera.hdr_target.view,
                 resolve_target: None,
                 ops: wgpu::Operations {
                     load: wgpu::LoadOp::Load,
@@ -279,7 +414,36 @@ pub(crate) fn material_renderpass<M: Material>(
             render_pass.set_bind_group(2, &render_lighting.bind_group, &[]);
         }
 
+        let camera_batches = batches.cameras.get(&camera_entity);
+        for batch in camera_batches.into_iter().flatten() {
+            let (Some(mesh), Some(render_mat), Some(instances)) = (
+                render_meshes.get(&batch.mesh_asset_id),
+                render_materials.get(&batch.material_asset_id),
+                &batches.instances,
+            ) else {
+                continue;
+            };
+
+            render_pass.set_bind_group(0, &render_mat.bind_group, &[]);
+            if M::needs_skeleton() {
+                render_pass.set_bind_group(3, skins.bind_group(), &[0]);
+            }
+
+            // Offsetting the slice rather than the instance range keeps
+            // `first_instance` at zero, which not every backend can offset by.
+            let stride = std::mem::size_of::<GlobalTransformRaw>() as wgpu::BufferAddress;
+            render_pass.set_vertex_buffer(0, mesh.vertices.slice(..));
+            render_pass.set_index_buffer(mesh.indices.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass
+                .set_vertex_buffer(1, instances.slice(batch.instances.start as u64 * stride..));
+            render_pass.draw_indexed(0..mesh.index_count, 0, 0..batch.instances.len() as u32);
+        }
+
         for (mesh_instance, skeleton, render_mat_comp) in render_mesh_query.iter() {
+            if skeleton.is_none() || !mesh_instance.is_visible(&render_camera.frustum) {
+                continue;
+            }
+
             if let Some(mesh) = render_meshes.get(&mesh_instance.mesh_asset_id) {
                 if let Some(render_mat) = render_materials.get(&render_mat_comp.material_asset_id) {
                     render_pass.set_bind_group(0, &render_mat.bind_group, &[]);
@@ -318,7 +482,8 @@ pub(crate) fn material_renderpass<M: Material>(
 // For materials that have their own custom render pass (e.g. skybox, UI)
 // you can use [`MaterialPlugin::pipeline_only`] to create just the
 // [`MaterialPipeline<M>`] resource without registering the generic mesh
-// rendering systems.
+// rendering systems. Such pipelines target the window's surface format rather
+// than the cameras' HDR targets.
 pub struct MaterialPlugin<M: Material> {
     // When `true`, only the [`MaterialPipeline<M>`] resource is set up.
     // No asset registration and no mesh rendering systems are added.
@@ -371,19 +536,32 @@ impl<M: Material> Plugin for MaterialPlugin<M> {
         // in RenderMaterialComponent<M>.  Transform updates, however, are handled by the
         // shared mesh_changed system already registered by RenderPlugin, which iterates
         // over all entities with RenderEntity regardless of material type.
+        app.insert_resource(MaterialBatches::<M>::default());
         app.add_system(UpdateGroup::LateUpdate, material_added::<M>)
-            .add_system(UpdateGroup::Render, material_renderpass::<M>);
+            .add_system(UpdateGroup::Render, prepare_material_batches::<M>)
+            .add_system(
+                UpdateGroup::Render,
+                material_renderpass::<M>
+                    .after(prepare_material_batches::<M>)
+                    .in_set(ScenePasses),
+            );
     }
 
     fn finish(&self, app: &mut app::App) {
         let device = app
             .get_resource::<RenderDevice>()
             .expect("RenderDevice not found; register RenderPlugin before MaterialPlugin");
-        let surface_format = app
-            .get_resource::<RenderContext>()
-            .expect("RenderContext not found")
-            .surface_config
-            .format;
+        // Generic mesh passes shade in linear HDR (see
+        // `components::post_process`); pipeline-only materials draw in their
+        // own passes, straight to the window.
+        let target_format = if self.pipeline_only {
+            app.get_resource::<RenderContext>()
+                .expect("RenderContext not found")
+                .surface_config
+                .format
+        } else {
+            HDR_FORMAT
+        };
 
         // Build the material's own @group(0) bind-group layout.
         let material_layout = M::bind_group_layout(device);
@@ -452,7 +630,7 @@ impl<M: Material> Plugin for MaterialPlugin<M> {
                 module: &fs_module,
                 entry_point: Some("fs_main"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
+                    format: target_format,
                     blend: M::blend_state(),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -480,3 +658,121 @@ impl<M: Material> Plugin for MaterialPlugin<M> {
         app.insert_resource(MaterialPipeline::<M>::new(pipeline, material_layout));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use essential::transform::GlobalTransform;
+    use glam::{Mat4, Vec3};
+
+    use super::*;
+
+    // Looks down -Z at a 2x2 square around the origin, up to 10 units away.
+    fn frustum() -> Frustum {
+        Frustum::from_view_proj(Mat4::orthographic_rh(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0))
+    }
+
+    fn instance(mesh: AssetId, material: AssetId, x: f32) -> BatchInstance {
+        let center = Vec3::new(x, 0.0, -5.0);
+        BatchInstance {
+            mesh_asset_id: mesh,
+            material_asset_id: material,
+            skinned: false,
+            aabb: Some(Aabb {
+                min: center - 0.1,
+                max: center + 0.1,
+            }),
+            transform: GlobalTransform::new(Mat4::from_translation(center)).to_raw(),
+        }
+    }
+
+    fn x_of(transform: &GlobalTransformRaw) -> f32 {
+        bytemuck::cast_ref::<_, [f32; 16]>(transform)[12]
+    }
+
+    fn batch_xs(batch: &MaterialBatch, transforms: &[GlobalTransformRaw]) -> Vec<f32> {
+        let range = batch.instances.start as usize..batch.instances.end as usize;
+        transforms[range].iter().map(x_of).collect()
+    }
+
+    #[test]
+    fn instances_sharing_mesh_and_material_share_a_batch_in_first_seen_order() {
+        let (mesh_a, mesh_b, material_a, material_b) = (
+            AssetId::new(),
+            AssetId::new(),
+            AssetId::new(),
+            AssetId::new(),
+        );
+        let instances = [
+            instance(mesh_b, material_a, 0.0),
+            instance(mesh_a, material_a, 0.1),
+            instance(mesh_b, material_a, 0.2),
+            instance(mesh_a, material_b, 0.3),
+            instance(mesh_a, material_a, 0.4),
+        ];
+
+        let mut transforms = Vec::new();
+        let batches = group_batches(instances, &frustum(), &mut transforms);
+
+        let keys: Vec<_> = batches
+            .iter()
+            .map(|batch| (batch.mesh_asset_id, batch.material_asset_id))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                (mesh_b, material_a),
+                (mesh_a, material_a),
+                (mesh_a, material_b)
+            ]
+        );
+        assert_eq!(batch_xs(&batches[0], &transforms), [0.0, 0.2]);
+        assert_eq!(batch_xs(&batches[1], &transforms), [0.1, 0.4]);
+        assert_eq!(batch_xs(&batches[2], &transforms), [0.3]);
+    }
+
+    #[test]
+    fn skinned_and_culled_instances_are_left_out() {
+        let (mesh, material) = (AssetId::new(), AssetId::new());
+        let skinned = BatchInstance {
+            skinned: true,
+            ..instance(mesh, material, 0.0)
+        };
+        let culled = instance(mesh, material, 100.0);
+        let unbounded = BatchInstance {
+            aabb: None,
+            ..instance(mesh, material, 200.0)
+        };
+        let visible = instance(mesh, material, 0.5);
+
+        let mut transforms = Vec::new();
+        let batches = group_batches(
+            [skinned, culled, unbounded, visible],
+            &frustum(),
+            &mut transforms,
+        );
+
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batch_xs(&batches[0], &transforms), [200.0, 0.5]);
+        assert_eq!(transforms.len(), 2);
+    }
+
+    #[test]
+    fn each_camera_gets_its_own_instance_ranges() {
+        let (mesh, material) = (AssetId::new(), AssetId::new());
+        let mut transforms = Vec::new();
+        let first = group_batches(
+            [instance(mesh, material, 0.0), instance(mesh, material, 0.5)],
+            &frustum(),
+            &mut transforms,
+        );
+        let second = group_batches(
+            [instance(mesh, material, 0.0), instance(mesh, material, 0.5)],
+            &frustum(),
+            &mut transforms,
+        );
+
+        assert_eq!(first[0].instances, 0..2);
+        assert_eq!(second[0].instances, 2..4);
+        assert_eq!(batch_xs(&second[0], &transforms), [0.0, 0.5]);
+    }
+}
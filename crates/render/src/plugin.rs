@@ -2,19 +2,25 @@ use crate::{
     assets::{mesh::Mesh, skeleton::Skeleton, texture::Texture},
     components::{
         camera::{camera_added, camera_changed, sync_camera_aspect},
+        cascades::update_directional_cascades,
         light::{light_added, light_changed, update_changed_lights, RenderLight, RenderLights},
+        material::MaterialComponent,
         mesh::{mesh_added, mesh_changed},
         render_entity::RenderEntity,
         shadows::{
-            resize_shadow_maps, update_shadow_view_proj, RenderLighting, RenderPointShadowMaps,
-            RenderShadowCasterSlot, RenderShadowViewProjs, RenderSpotDirectionalShadowMaps,
+            resize_shadow_maps, update_cascade_view_projs, update_shadow_view_proj,
+            RenderCascadeUniforms, RenderDirectionalShadowMaps, RenderLighting,
+            RenderPointShadowMaps, RenderShadowCasterSlot, RenderShadowViewProjs,
+            RenderSpotShadowMaps, ShadowMaps,
         },
         skeleton::{skeleton_added, update_skeletons, RenderSkeletonComponent, SkinUniforms},
+        visibility::{update_mesh_aabbs, update_skinned_mesh_aabbs},
         world_environment::WorldEnvironment,
     },
     device::RenderDevice,
     layouts::{CameraLayout, LightingLayout, SkeletonLayout},
     material_plugin::clear_cameras,
+    post_process_pipeline::{PostProcessPipeline, PostProcessPlugin, ScenePasses},
     queue::RenderQueue,
     render_asset::{
         render_mesh::RenderMesh,
@@ -31,6 +37,7 @@ use crate::{
 use app::plugins::Plugin;
 use color::Color;
 use ecs::{resource::Resource, system::schedule::UpdateGroup, IntoSystemConfig};
+use mesh::mesh::MeshComponent;
 use std::sync::{Arc, Mutex};
 use wgpu::{Adapter, Device, Instance, Limits, MemoryHints, Queue};
 
@@ -141,9 +148,14 @@ impl Plugin for RenderPlugin {
 
         app.register_plugin(RenderAssetPlugin::<RenderMesh>::new())
             .register_plugin(RenderAssetPlugin::<RenderTexture>::new());
+        // Without it, nothing cameras render reaches their targets.
+        app.register_plugin(PostProcessPlugin);
         app.register_asset::<Mesh>()
-            .register_asset::<Texture>()
+            .register_loadable_asset::<Texture>()
             .register_asset::<Skeleton>();
+        // Lets scenes and glTF extras refer to meshes and materials.
+        app.register_reflection::<MeshComponent>()
+            .register_reflection::<MaterialComponent>();
 
         // Before camera_changed, which bakes aspect into the projection matrix.
         app.add_system(UpdateGroup::LateUpdate, sync_camera_aspect)
@@ -151,8 +163,11 @@ impl Plugin for RenderPlugin {
             .add_system(UpdateGroup::LateUpdate, camera_changed)
             .add_system(UpdateGroup::LateUpdate, mesh_added)
             .add_system(UpdateGroup::LateUpdate, mesh_changed)
+            .add_system(UpdateGroup::LateUpdate, update_mesh_aabbs)
+            .add_system(UpdateGroup::LateUpdate, update_skinned_mesh_aabbs)
             .add_system(UpdateGroup::LateUpdate, light_added)
             .add_system(UpdateGroup::LateUpdate, light_changed)
+            .add_system(UpdateGroup::LateUpdate, update_directional_cascades)
             .add_system(UpdateGroup::LateUpdate, skeleton_added);
 
         if is_windowed {
@@ -160,13 +175,17 @@ impl Plugin for RenderPlugin {
                 .add_system(UpdateGroup::Render, update_window::update_render_window);
         }
 
-        app.add_system(UpdateGroup::Render, clear_cameras)
+        app.add_system(UpdateGroup::Render, clear_cameras.in_set(ScenePasses))
             .add_system(UpdateGroup::Render, update_skeletons)
             .add_system(UpdateGroup::Render, update_changed_lights)
             .add_system(
                 UpdateGroup::Render,
                 update_shadow_view_proj.after(update_changed_lights),
             )
+            .add_system(
+                UpdateGroup::Render,
+                update_cascade_view_projs.after(update_changed_lights),
+            )
             .add_system(
                 UpdateGroup::Render,
                 resize_shadow_maps.after(update_changed_lights),
@@ -253,18 +272,25 @@ impl Plugin for RenderPlugin {
         app.register_component_lifecycle::<RenderShadowCasterSlot>();
 
         let render_lights = RenderLights::new(&device);
-        let render_spot_directional_shadow_maps = RenderSpotDirectionalShadowMaps::new(&device);
+        let render_spot_shadow_maps = RenderSpotShadowMaps::new(&device);
         let render_point_shadow_maps = RenderPointShadowMaps::new(&device);
+        let render_directional_shadow_maps = RenderDirectionalShadowMaps::new(&device);
         let render_shadow_view_projs = RenderShadowViewProjs::new(&device);
+        let render_cascade_uniforms = RenderCascadeUniforms::new(&device);
         let render_lighting = RenderLighting::new(
             &device,
             &lighting_layout,
             &render_lights,
-            &render_spot_directional_shadow_maps,
-            &render_point_shadow_maps,
-            &render_shadow_view_projs,
+            ShadowMaps {
+                spot: &render_spot_shadow_maps,
+                point: &render_point_shadow_maps,
+                directional: &render_directional_shadow_maps,
+                view_projs: &render_shadow_view_projs,
+                cascades: &render_cascade_uniforms,
+            },
         );
         let skin_uniforms = SkinUniforms::new(&device, &skeleton_layout, &queue);
+        let post_process_pipeline = PostProcessPipeline::new(&device, config.format);
 
         app.insert_resource(DummyRenderTexture::new(&device))
             .insert_resource(RenderContext {
@@ -281,11 +307,14 @@ impl Plugin for RenderPlugin {
             .insert_resource(skeleton_layout)
             .insert_resource(lighting_layout)
             .insert_resource(render_lights)
-            .insert_resource(render_spot_directional_shadow_maps)
+            .insert_resource(render_spot_shadow_maps)
             .insert_resource(render_point_shadow_maps)
+            .insert_resource(render_directional_shadow_maps)
             .insert_resource(render_shadow_view_projs)
+            .insert_resource(render_cascade_uniforms)
             .insert_resource(render_lighting)
             .insert_resource(skin_uniforms)
+            .insert_resource(post_process_pipeline)
             .insert_resource(WorldEnvironment::new(Color::rgba(0.1, 0.1, 0.1, 0.1)));
     }
 }
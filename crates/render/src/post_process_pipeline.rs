@@ -0,0 +1,213 @@
+use app::Plugin;
+use ecs::{system::schedule::UpdateGroup, IntoSystemConfig, Resource, SystemSet};
+use wgpu::{
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, MultisampleState,
+    PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, RenderPipelineDescriptor,
+    ShaderModuleDescriptor, ShaderStages,
+};
+
+use crate::components::{
+    camera::HDR_FORMAT,
+    post_process::{prepare_post_process, render_post_process},
+};
+
+#[derive(Resource)]
+pub(crate) struct PostProcessPipeline {
+    // Thresholds the HDR image into the first bloom mip.
+    pub(crate) bloom_prefilter: wgpu::RenderPipeline,
+    pub(crate) bloom_downsample: wgpu::RenderPipeline,
+    // Adds a blurred mip onto the next larger one.
+    pub(crate) bloom_upsample: wgpu::RenderPipeline,
+    pub(crate) bloom_layout: wgpu::BindGroupLayout,
+    // Exposes, tone maps and grades the HDR image into the camera's target.
+    pub(crate) composite: wgpu::RenderPipeline,
+    pub(crate) composite_layout: wgpu::BindGroupLayout,
+    pub(crate) sampler: wgpu::Sampler,
+}
+
+/// The [`UpdateGroup::Render`] systems drawing the scene into cameras' HDR
+/// targets. [`PostProcessPlugin`] resolves those targets after all of them.
+#[derive(SystemSet, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ScenePasses;
+
+/// Resolves each camera's HDR image into its render target (or the window),
+/// through the steps configured on the camera (see
+/// [`components::post_process`](crate::components::post_process)).
+///
+/// Registered by [`RenderPlugin`](crate::plugin::RenderPlugin), which also
+/// builds the pipelines. The resolve runs after every system in
+/// [`ScenePasses`]. Passes otherwise record into the frame encoder in
+/// registration order, so the systems are added in [`Plugin::finish`], after
+/// every plugin has built its scene passes. Plugins drawing on top of the
+/// final image (UI, debug gizmos) add their passes in their own `finish`,
+/// which runs later since they are registered after `RenderPlugin`.
+pub struct PostProcessPlugin;
+
+impl Plugin for PostProcessPlugin {
+    fn build(&self, _app: &mut app::App) {}
+
+    fn finish(&self, app: &mut app::App) {
+        app.add_system(UpdateGroup::Render, prepare_post_process)
+            .add_system(
+                UpdateGroup::Render,
+                render_post_process.after_set(ScenePasses),
+            );
+    }
+}
+
+impl PostProcessPipeline {
+    pub(crate) fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> Self {
+        let texture_entry = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let sampler_entry = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            count: None,
+        };
+        let uniform_entry = |binding| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let bloom_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Bloom Bind Group Layout"),
+            entries: &[texture_entry(0), sampler_entry(1), uniform_entry(2)],
+        });
+        let composite_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("Post-Process Bind Group Layout"),
+            entries: &[
+                texture_entry(0),
+                sampler_entry(1),
+                texture_entry(2),
+                texture_entry(3),
+                uniform_entry(4),
+            ],
+        });
+
+        let bloom_module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("Bloom Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/bloom.wgsl").into()),
+        });
+        let composite_module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("Post-Process Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/post_process.wgsl").into()),
+        });
+
+        let bloom_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Bloom Pipeline Layout"),
+            bind_group_layouts: &[&bloom_layout],
+            push_constant_ranges: &[],
+        });
+        let composite_pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("Post-Process Pipeline Layout"),
+            bind_group_layouts: &[&composite_layout],
+            push_constant_ranges: &[],
+        });
+
+        let fullscreen_pipeline =
+            |label, layout, module, entry_point, format, blend: Option<wgpu::BlendState>| {
+                device.create_render_pipeline(&RenderPipelineDescriptor {
+                    label: Some(label),
+                    layout: Some(layout),
+                    vertex: wgpu::VertexState {
+                        module,
+                        entry_point: Some("vs_fullscreen"),
+                        buffers: &[],
+                        compilation_options: PipelineCompilationOptions::default(),
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module,
+                        entry_point: Some(entry_point),
+                        targets: &[Some(wgpu::ColorTargetState {
+                            format,
+                            blend,
+                            write_mask: wgpu::ColorWrites::ALL,
+                        })],
+                        compilation_options: PipelineCompilationOptions::default(),
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    multiview: None,
+                    cache: None,
+                })
+            };
+
+        let additive = wgpu::BlendState {
+            color: wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+            alpha: wgpu::BlendComponent::REPLACE,
+        };
+
+        let bloom_prefilter = fullscreen_pipeline(
+            "Bloom Prefilter Pipeline",
+            &bloom_pipeline_layout,
+            &bloom_module,
+            "fs_prefilter",
+            HDR_FORMAT,
+            None,
+        );
+        let bloom_downsample = fullscreen_pipeline(
+            "Bloom Downsample Pipeline",
+            &bloom_pipeline_layout,
+            &bloom_module,
+            "fs_downsample",
+            HDR_FORMAT,
+            None,
+        );
+        let bloom_upsample = fullscreen_pipeline(
+            "Bloom Upsample Pipeline",
+            &bloom_pipeline_layout,
+            &bloom_module,
+            "fs_upsample",
+            HDR_FORMAT,
+            Some(additive),
+        );
+        let composite = fullscreen_pipeline(
+            "Post-Process Pipeline",
+            &composite_pipeline_layout,
+            &composite_module,
+            "fs_main",
+            surface_format,
+            None,
+        );
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Post-Process Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            bloom_prefilter,
+            bloom_downsample,
+            bloom_upsample,
+            bloom_layout,
+            composite,
+            composite_layout,
+            sampler,
+        }
+    }
+}
@@ -6,13 +6,14 @@ use std::collections::HashMap;
 
 use app::plugins::Plugin;
 use ecs::{
+    events::event_reader::EventReader,
     resource::{ResMut, Resource},
     system::{
         input::{StaticSystemInput, SystemInput, SystemInputData},
         schedule::UpdateGroup,
     },
 };
-use essential::assets::{asset_store::AssetStore, Asset, AssetId};
+use essential::assets::{asset_store::AssetStore, status::AssetLoaded, Asset, AssetId};
 
 pub enum AssetPreparationError {
     NotReady,
@@ -32,7 +33,13 @@ pub(crate) fn prepare_render_asset<A: RenderAsset>(
     mut params: StaticSystemInput<<A as RenderAsset>::PreparationParams>,
     asset_store: ResMut<AssetStore<A::SourceAsset>>,
     mut render_assets: ResMut<RenderAssets<A>>,
+    mut loaded: EventReader<AssetLoaded<A::SourceAsset>>,
 ) {
+    // A reloaded source asset is prepared again below.
+    for event in loaded.read() {
+        render_assets.remove(&event.id);
+    }
+
     for (asset_id, asset) in asset_store.into_iter() {
         // TODO: Do something more performant than this
         if render_assets.contains(asset_id) {
@@ -63,6 +70,10 @@ impl<A: RenderAsset + 'static> RenderAssets<A> {
         self.0.get(id)
     }
 
+    pub fn remove(&mut self, id: &AssetId) -> Option<A> {
+        self.0.remove(id)
+    }
+
     pub fn contains(&self, id: &AssetId) -> bool {
         self.0.contains_key(id)
     }
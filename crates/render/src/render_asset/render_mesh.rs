@@ -1,4 +1,5 @@
 use ecs::resource::Res;
+use mesh::bounds::MeshBounds;
 use wgpu::util::DeviceExt;
 
 use crate::{
@@ -11,6 +12,8 @@ pub(crate) struct RenderMesh {
     pub(crate) vertices: wgpu::Buffer,
     pub(crate) indices: wgpu::Buffer,
     pub(crate) index_count: u32,
+    /// Local-space bounds of the vertices; `None` for an empty mesh.
+    pub(crate) bounds: Option<MeshBounds>,
 }
 
 impl RenderAsset for RenderMesh {
@@ -45,6 +48,7 @@ impl RenderAsset for RenderMesh {
             vertices,
             indices,
             index_count,
+            bounds: source_asset.compute_bounds(),
         })
     }
 }
@@ -0,0 +1,101 @@
+// Bloom: the HDR image is thresholded into a half-resolution mip, blurred
+// down a chain of ever smaller mips, then blurred back up, each mip added
+// onto the next larger one. The largest mip ends up holding the glow.
+
+struct PostProcess {
+    exposure: f32,
+    tonemapping: u32,
+    bloom_intensity: f32,
+    bloom_threshold: f32,
+    bloom_knee: f32,
+    lut_strength: f32,
+    lut_size: f32,
+    lut_srgb: u32,
+    vignette_intensity: f32,
+    vignette_radius: f32,
+    vignette_smoothness: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0)
+var t_source: texture_2d<f32>;
+@group(0) @binding(1)
+var s_source: sampler;
+@group(0) @binding(2)
+var<uniform> post: PostProcess;
+
+// One triangle covering the whole target.
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let position = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(position, 0.0, 1.0);
+    out.uv = vec2<f32>(position.x * 0.5 + 0.5, 0.5 - position.y * 0.5);
+    return out;
+}
+
+// 13-tap downsample from Jimenez's "Next Generation Post Processing in Call
+// of Duty: Advanced Warfare": five overlapping 2x2 boxes, which avoids the
+// flickering a single box filter shows on small bright details.
+fn downsample(uv: vec2<f32>) -> vec3<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(t_source));
+    let a = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(-2.0, -2.0), 0.0).rgb;
+    let b = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(0.0, -2.0), 0.0).rgb;
+    let c = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(2.0, -2.0), 0.0).rgb;
+    let d = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(-2.0, 0.0), 0.0).rgb;
+    let e = textureSampleLevel(t_source, s_source, uv, 0.0).rgb;
+    let f = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(2.0, 0.0), 0.0).rgb;
+    let g = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(-2.0, 2.0), 0.0).rgb;
+    let h = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(0.0, 2.0), 0.0).rgb;
+    let i = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(2.0, 2.0), 0.0).rgb;
+    let j = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(-1.0, -1.0), 0.0).rgb;
+    let k = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(1.0, -1.0), 0.0).rgb;
+    let l = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(-1.0, 1.0), 0.0).rgb;
+    let m = textureSampleLevel(t_source, s_source, uv + texel * vec2<f32>(1.0, 1.0), 0.0).rgb;
+
+    return e * 0.125
+        + (a + c + g + i) * 0.03125
+        + (b + d + f + h) * 0.0625
+        + (j + k + l + m) * 0.125;
+}
+
+// Keeps only light brighter than the threshold, with a quadratic ramp of
+// width `knee * threshold` around it so bloom fades in instead of popping.
+fn threshold(color: vec3<f32>) -> vec3<f32> {
+    let brightness = max(color.r, max(color.g, color.b));
+    let knee = post.bloom_threshold * post.bloom_knee + 1e-5;
+    var soft = clamp(brightness - post.bloom_threshold + knee, 0.0, 2.0 * knee);
+    soft = soft * soft / (4.0 * knee);
+    let contribution = max(soft, brightness - post.bloom_threshold) / max(brightness, 1e-5);
+    return color * contribution;
+}
+
+@fragment
+fn fs_prefilter(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(threshold(downsample(in.uv) * post.exposure), 1.0);
+}
+
+@fragment
+fn fs_downsample(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(downsample(in.uv), 1.0);
+}
+
+// 3x3 tent filter.
+@fragment
+fn fs_upsample(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(t_source));
+    var sum = textureSampleLevel(t_source, s_source, in.uv, 0.0).rgb * 4.0;
+    sum += textureSampleLevel(t_source, s_source, in.uv + texel * vec2<f32>(-1.0, 0.0), 0.0).rgb * 2.0;
+    sum += textureSampleLevel(t_source, s_source, in.uv + texel * vec2<f32>(1.0, 0.0), 0.0).rgb * 2.0;
+    sum += textureSampleLevel(t_source, s_source, in.uv + texel * vec2<f32>(0.0, -1.0), 0.0).rgb * 2.0;
+    sum += textureSampleLevel(t_source, s_source, in.uv + texel * vec2<f32>(0.0, 1.0), 0.0).rgb * 2.0;
+    sum += textureSampleLevel(t_source, s_source, in.uv + texel * vec2<f32>(-1.0, -1.0), 0.0).rgb;
+    sum += textureSampleLevel(t_source, s_source, in.uv + texel * vec2<f32>(1.0, -1.0), 0.0).rgb;
+    sum += textureSampleLevel(t_source, s_source, in.uv + texel * vec2<f32>(-1.0, 1.0), 0.0).rgb;
+    sum += textureSampleLevel(t_source, s_source, in.uv + texel * vec2<f32>(1.0, 1.0), 0.0).rgb;
+    return vec4<f32>(sum / 16.0, 1.0);
+}
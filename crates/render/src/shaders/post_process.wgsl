@@ -0,0 +1,163 @@
+// Resolves a camera's HDR image: exposure, bloom, tone mapping, LUT color
+// grading and vignette, written to the camera's (usually sRGB) target.
+
+const TONEMAP_ACES = 0u;
+const TONEMAP_AGX = 1u;
+const TONEMAP_REINHARD = 2u;
+
+struct PostProcess {
+    exposure: f32,
+    tonemapping: u32,
+    bloom_intensity: f32,
+    bloom_threshold: f32,
+    bloom_knee: f32,
+    lut_strength: f32,
+    lut_size: f32,
+    lut_srgb: u32,
+    vignette_intensity: f32,
+    vignette_radius: f32,
+    vignette_smoothness: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0)
+var t_hdr: texture_2d<f32>;
+@group(0) @binding(1)
+var s_linear: sampler;
+@group(0) @binding(2)
+var t_bloom: texture_2d<f32>;
+@group(0) @binding(3)
+var t_lut: texture_2d<f32>;
+@group(0) @binding(4)
+var<uniform> post: PostProcess;
+
+// One triangle covering the whole target.
+@vertex
+fn vs_fullscreen(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let position = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(position, 0.0, 1.0);
+    out.uv = vec2<f32>(position.x * 0.5 + 0.5, 0.5 - position.y * 0.5);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = textureSampleLevel(t_hdr, s_linear, in.uv, 0.0).rgb * post.exposure;
+    // The bloom chain is already exposed (see `fs_prefilter`).
+    color += textureSampleLevel(t_bloom, s_linear, in.uv, 0.0).rgb * post.bloom_intensity;
+
+    color = tonemap(max(color, vec3<f32>(0.0)));
+
+    if post.lut_strength > 0.0 {
+        color = mix(color, grade(color), post.lut_strength);
+    }
+
+    if post.vignette_intensity > 0.0 {
+        // 1 in the corners, whatever the aspect ratio.
+        let distance = length(in.uv - 0.5) * 1.41421356;
+        let falloff = smoothstep(
+            post.vignette_radius - post.vignette_smoothness,
+            post.vignette_radius,
+            distance,
+        );
+        color *= 1.0 - post.vignette_intensity * falloff;
+    }
+
+    // The target's sRGB format applies gamma encoding.
+    return vec4<f32>(color, 1.0);
+}
+
+fn tonemap(color: vec3<f32>) -> vec3<f32> {
+    switch post.tonemapping {
+        case TONEMAP_ACES: {
+            return aces_tonemap(color);
+        }
+        case TONEMAP_AGX: {
+            return agx_tonemap(color);
+        }
+        case TONEMAP_REINHARD: {
+            return color / (1.0 + color);
+        }
+        default: {
+            return clamp(color, vec3<f32>(0.0), vec3<f32>(1.0));
+        }
+    }
+}
+
+// Narkowicz ACES filmic approximation
+fn aces_tonemap(color: vec3<f32>) -> vec3<f32> {
+    let mapped = (color * (2.51 * color + 0.03)) / (color * (2.43 * color + 0.59) + 0.14);
+    return clamp(mapped, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+// AgX with the default look, after Benjamin Wrensch's fitted version of
+// Troy Sobotka's transform: into AgX's log space, through a sigmoid, and back
+// to linear.
+fn agx_tonemap(color: vec3<f32>) -> vec3<f32> {
+    let inset = mat3x3<f32>(
+        0.842479062253094, 0.0423282422610123, 0.0423756549057051,
+        0.0784335999999992, 0.878468636469772, 0.0784336,
+        0.0792237451477643, 0.0791661274605434, 0.879142973793104,
+    );
+    let outset = mat3x3<f32>(
+        1.19687900512017, -0.0528968517574562, -0.0529716355144438,
+        -0.0980208811401368, 1.15190312990417, -0.0980434501171241,
+        -0.0990297440797205, -0.0989611768448433, 1.15107367264116,
+    );
+    let min_ev = -12.47393;
+    let max_ev = 4.026069;
+
+    var c = inset * color;
+    c = clamp(log2(max(c, vec3<f32>(1e-10))), vec3<f32>(min_ev), vec3<f32>(max_ev));
+    c = (c - min_ev) / (max_ev - min_ev);
+
+    let c2 = c * c;
+    let c4 = c2 * c2;
+    c = 15.5 * c4 * c2 - 40.14 * c4 * c + 31.96 * c4 - 6.868 * c2 * c + 0.4298 * c2
+        + 0.1191 * c - 0.00232;
+
+    c = outset * c;
+    return pow(max(c, vec3<f32>(0.0)), vec3<f32>(2.2));
+}
+
+// Looks `color` up in the LUT strip, blending between the two nearest blue
+// slices.
+fn grade(color: vec3<f32>) -> vec3<f32> {
+    let size = post.lut_size;
+    let encoded = clamp(linear_to_srgb(color), vec3<f32>(0.0), vec3<f32>(1.0));
+
+    let blue = encoded.b * (size - 1.0);
+    let slice = floor(blue);
+    let next_slice = min(slice + 1.0, size - 1.0);
+    // Texel centers within a tile.
+    let texel = encoded.rg * (size - 1.0) + 0.5;
+    let uv = vec2<f32>((slice * size + texel.x) / (size * size), texel.y / size);
+    let next_uv = vec2<f32>((next_slice * size + texel.x) / (size * size), texel.y / size);
+
+    var graded = mix(
+        textureSampleLevel(t_lut, s_linear, uv, 0.0).rgb,
+        textureSampleLevel(t_lut, s_linear, next_uv, 0.0).rgb,
+        blue - slice,
+    );
+    if post.lut_srgb == 0u {
+        graded = srgb_to_linear(graded);
+    }
+    return graded;
+}
+
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(color: vec3<f32>) -> vec3<f32> {
+    let low = color / 12.92;
+    let high = pow((color + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, color <= vec3<f32>(0.04045));
+}
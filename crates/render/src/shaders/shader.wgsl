@@ -1,6 +1,8 @@
 const MAX_LIGHT_COUNT: i32 = 128;
 const MAX_BONE_COUNT: i32 = 128;
 const MAX_SHADOW_CASTERS: i32 = 128;
+const MAX_CASCADES: i32 = 4;
+const MAX_CASCADE_CASTERS: i32 = 4;
 
 const HAS_BASE_COLOR_TEXTURE = 1u << 0u;
 const HAS_NORMAL_TEXTURE = 1u << 1u;
@@ -49,16 +51,40 @@ struct Skeleton {
     bones: array<mat4x4<f32>, MAX_BONE_COUNT>,
 };
 
-// Spot/directional shadow view-proj matrices, indexed by `Light::shadow_layer`.
-// Point lights aren't covered here (see `shadow_visibility`) — their
-// `shadow_layer` indexes a different pool that this array doesn't track.
+// Spot shadow view-proj matrices, indexed by `Light::shadow_layer`.
+// Point and directional lights aren't covered here (see `shadow_visibility`)
+// — their `shadow_layer` indexes a different pool that this array doesn't
+// track.
 struct ShadowViewProjs {
     matrices: array<mat4x4<f32>, MAX_SHADOW_CASTERS>,
 };
 
+// One directional light's cascades (`CascadeCasterUniform` in shadows.rs).
+// Cascade `i` covers view depths up to `far_bounds[i]`, measured from the
+// camera the cascades were fitted to.
+struct CascadeCaster {
+    view_projs: array<mat4x4<f32>, MAX_CASCADES>,
+    far_bounds: vec4<f32>,
+    // Texels across the top-left corner of its layer each cascade renders
+    // into; the layers are as large as the largest cascade.
+    resolutions: vec4<f32>,
+    camera_position: vec3<f32>,
+    count: u32,
+    camera_forward: vec3<f32>,
+    blend: f32,
+};
+
+// Indexed by a directional light's `Light::shadow_layer`.
+struct Cascades {
+    casters: array<CascadeCaster, MAX_CASCADE_CASTERS>,
+};
+
 struct CameraUniform {
     view_pos: vec3<f32>,
     view_proj: mat4x4<f32>,
+    view_forward: vec3<f32>,
+    // 1 for orthographic cameras, whose view rays are all parallel.
+    is_orthographic: u32,
 };
 
 struct VertexInput {
@@ -193,10 +219,10 @@ var<uniform> camera: CameraUniform;
 var<uniform> lights: Lights;
 
 @group(2) @binding(1)
-var t_shadow_spot_directional: texture_depth_2d_array;
+var t_shadow_spot: texture_depth_2d_array;
 
 @group(2) @binding(2)
-var sampler_shadow_spot_directional: sampler_comparison;
+var sampler_shadow_spot: sampler_comparison;
 
 @group(2) @binding(3)
 var t_shadow_point: texture_depth_cube_array;
@@ -207,6 +233,12 @@ var sampler_shadow_point: sampler_comparison;
 @group(2) @binding(5)
 var<uniform> shadow_view_projs: ShadowViewProjs;
 
+@group(2) @binding(6)
+var t_shadow_cascades: texture_depth_2d_array;
+
+@group(2) @binding(7)
+var<uniform> cascades: Cascades;
+
 @group(3) @binding(0)
 var<uniform> bones: Skeleton;
 
@@ -234,7 +266,10 @@ fn pbr_fs(in: VertexOutput) -> vec4<f32> {
     let TBN = mat3x3<f32>(in.world_tangent, in.world_bitangent, in.world_normal);
     let mapped_normal = normalize(TBN * normalize(object_normal.xyz * 2.0 - 1.0));
 
-    let view_dir = normalize(camera.view_pos - in.world_position);
+    var view_dir = -camera.view_forward;
+    if camera.is_orthographic == 0u {
+        view_dir = normalize(camera.view_pos - in.world_position);
+    }
     let NdotV = max(dot(mapped_normal, view_dir), 1e-4);
 
     // Dielectrics reflect ~4% at normal incidence; metals reflect base color.
@@ -274,7 +309,7 @@ fn pbr_fs(in: VertexOutput) -> vec4<f32> {
         // get a real shadow factor here.
         var shadow = 1.0;
         if light_type != POINT_LIGHT {
-            shadow = shadow_visibility(light.shadow_layer, in.world_position);
+            shadow = shadow_visibility(light_type, light.shadow_layer, in.world_position);
         }
 
         let radiance = light.color.rgb * light.intensity * attenuation * shadow;
@@ -299,48 +334,120 @@ fn pbr_fs(in: VertexOutput) -> vec4<f32> {
     let ambient = AMBIENT_INTENSITY * base_color.rgb * occlusion;
     let color = ambient + total_light + emissive;
 
-    // Tone map to LDR; the sRGB surface format applies gamma encoding.
-    return vec4<f32>(aces_tonemap(color), base_color.a);
+    // Linear HDR; exposure and tone mapping happen in post-processing.
+    return vec4<f32>(color, base_color.a);
 }
 
 // Visibility factor (0 = fully shadowed, 1 = fully lit) for a spot/directional
 // caster. `shadow_layer < 0` means the light doesn't cast shadows at all.
-fn shadow_visibility(shadow_layer: i32, world_position: vec3<f32>) -> f32 {
+fn shadow_visibility(light_type: u32, shadow_layer: i32, world_position: vec3<f32>) -> f32 {
     if shadow_layer < 0 {
         return 1.0;
     }
 
-    let light_clip = shadow_view_projs.matrices[shadow_layer] * vec4<f32>(world_position, 1.0);
-    if light_clip.w <= 0.0 {
+    if light_type == DIRECTIONAL_LIGHT {
+        return cascade_visibility(shadow_layer, world_position);
+    }
+
+    let light_ndc = project_to_shadow_map(shadow_view_projs.matrices[shadow_layer], world_position);
+    if light_ndc.w == 0.0 {
+        return 1.0; // Outside the light's shadow frustum: treat as unshadowed.
+    }
+
+    // Hardware PCF: the comparison sampler returns a bilinear-filtered
+    // fraction of samples passing `depth_ref <= stored_depth`, giving
+    // softened edges for one tap. A wider multi-tap kernel would soften
+    // further but isn't implemented yet.
+    return textureSampleCompare(
+        t_shadow_spot,
+        sampler_shadow_spot,
+        light_ndc.xy,
+        shadow_layer,
+        light_ndc.z,
+    );
+}
+
+// Picks the cascade of directional caster `caster` that covers
+// `world_position`, by depth from the camera the cascades were fitted to,
+// and samples it. Over the last `blend` fraction of a cascade the result
+// fades into the next one — or, past the last cascade, into no shadow — so
+// the jump in shadow-map resolution doesn't show as a seam.
+fn cascade_visibility(caster: i32, world_position: vec3<f32>) -> f32 {
+    let cascade_caster = cascades.casters[caster];
+    let depth = dot(world_position - cascade_caster.camera_position, cascade_caster.camera_forward);
+
+    var near = 0.0;
+    for (var i: u32 = 0u; i < min(cascade_caster.count, u32(MAX_CASCADES)); i = i + 1u) {
+        let far = cascade_caster.far_bounds[i];
+        if depth < far {
+            var visibility = sample_cascade(caster, i, world_position);
+
+            let blend_start = far - (far - near) * cascade_caster.blend;
+            if depth > blend_start {
+                var next_visibility = 1.0;
+                if i + 1u < cascade_caster.count {
+                    next_visibility = sample_cascade(caster, i + 1u, world_position);
+                }
+                visibility = mix(visibility, next_visibility, smoothstep(blend_start, far, depth));
+            }
+            return visibility;
+        }
+        near = far;
+    }
+
+    return 1.0; // Past the last cascade: nothing is shadowed that far out.
+}
+
+fn sample_cascade(caster: i32, cascade: u32, world_position: vec3<f32>) -> f32 {
+    let view_proj = cascades.casters[caster].view_projs[cascade];
+    let light_ndc = project_to_shadow_map(view_proj, world_position);
+    if light_ndc.w == 0.0 {
         return 1.0;
     }
 
+    // Scale into the cascade's corner of the layer, keeping the filter
+    // footprint off the unused (cleared) texels beyond it.
+    let layer_size = f32(textureDimensions(t_shadow_cascades).x);
+    let scale = min(cascades.casters[caster].resolutions[cascade], layer_size) / layer_size;
+    let uv = min(light_ndc.xy * scale, vec2<f32>(scale - 0.5 / layer_size));
+
+    // The `Level` variant: which cascade gets sampled varies per fragment, so
+    // this isn't in uniform control flow, where implicit-derivative sampling
+    // is invalid. Shadow maps have a single mip anyway.
+    return textureSampleCompareLevel(
+        t_shadow_cascades,
+        sampler_shadow_spot,
+        uv,
+        caster * MAX_CASCADES + i32(cascade),
+        light_ndc.z,
+    );
+}
+
+// Projects `world_position` into the shadow map `view_proj` renders: texture
+// uv in `xy`, depth in `z`, and `w` 1 if the position is inside the map or 0
+// if it isn't.
+fn project_to_shadow_map(view_proj: mat4x4<f32>, world_position: vec3<f32>) -> vec4<f32> {
+    let light_clip = view_proj * vec4<f32>(world_position, 1.0);
+    if light_clip.w <= 0.0 {
+        return vec4<f32>(0.0);
+    }
+
     let light_ndc = light_clip.xyz / light_clip.w;
 
     // wgpu's NDC depth range is already [0, 1] (see the `_rh` — not `_rh_gl`
-    // — projections in shadows.rs), so only x/y need remapping from clip
-    // space [-1, 1] to texture space [0, 1]; y is flipped since NDC +y is up
-    // but texture +v is down.
+    // — projections in shadows.rs and cascades.rs), so only x/y need
+    // remapping from clip space [-1, 1] to texture space [0, 1]; y is flipped
+    // since NDC +y is up but texture +v is down.
     let shadow_uv = light_ndc.xy * vec2<f32>(0.5, -0.5) + vec2<f32>(0.5, 0.5);
 
     let outside = shadow_uv.x < 0.0 || shadow_uv.x > 1.0
         || shadow_uv.y < 0.0 || shadow_uv.y > 1.0
         || light_ndc.z > 1.0;
     if outside {
-        return 1.0; // Outside the light's shadow frustum: treat as unshadowed.
+        return vec4<f32>(0.0);
     }
 
-    // Hardware PCF: the comparison sampler returns a bilinear-filtered
-    // fraction of samples passing `depth_ref <= stored_depth`, giving
-    // softened edges for one tap. A wider multi-tap kernel would soften
-    // further but isn't implemented yet.
-    return textureSampleCompare(
-        t_shadow_spot_directional,
-        sampler_shadow_spot_directional,
-        shadow_uv,
-        shadow_layer,
-        light_ndc.z,
-    );
+    return vec4<f32>(shadow_uv, light_ndc.z, 1.0);
 }
 
 // Trowbridge-Reitz GGX normal distribution
@@ -364,12 +471,6 @@ fn fresnel_schlick(cos_theta: f32, f0: vec3<f32>) -> vec3<f32> {
     return f0 + (vec3<f32>(1.0) - f0) * pow(clamp(1.0 - cos_theta, 0.0, 1.0), 5.0);
 }
 
-// Narkowicz ACES filmic approximation
-fn aces_tonemap(color: vec3<f32>) -> vec3<f32> {
-    let mapped = (color * (2.51 * color + 0.03)) / (color * (2.43 * color + 0.59) + 0.14);
-    return clamp(mapped, vec3<f32>(0.0), vec3<f32>(1.0));
-}
-
 fn sample_base_color(tex_coords: vec2<f32>) -> vec4<f32> {
     let scaled = tex_coords * material.uv_scale;
     if (material.flags & HAS_BASE_COLOR_TEXTURE) != 0u {
@@ -1,5 +1,5 @@
 use app::Plugin;
-use ecs::{system::schedule::UpdateGroup, Resource};
+use ecs::{system::schedule::UpdateGroup, IntoSystemConfig, Resource};
 use essential::transform::GlobalTransformRaw;
 use mesh::Vertex;
 use wgpu::{
@@ -14,6 +14,7 @@ use crate::{
     components::shadows::{render_shadow_maps, update_shadow_view_proj},
     device::RenderDevice,
     layouts::SkeletonLayout,
+    post_process_pipeline::ScenePasses,
 };
 
 #[derive(Resource)]
@@ -27,7 +28,7 @@ pub struct ShadowPipelinePlugin;
 impl Plugin for ShadowPipelinePlugin {
     fn build(&self, app: &mut app::App) {
         app.add_system(UpdateGroup::LateUpdate, update_shadow_view_proj);
-        app.add_system(UpdateGroup::Render, render_shadow_maps);
+        app.add_system(UpdateGroup::Render, render_shadow_maps.in_set(ScenePasses));
     }
 
     fn finish(&self, app: &mut app::App) {
@@ -8,14 +8,14 @@ use ecs::{
 use window::{plugin::Window, winit_events::WindowEvent};
 
 use crate::{
-    components::camera::RenderCamera,
+    components::camera::{create_hdr_target, RenderCamera},
     device::RenderDevice,
     render_asset::{render_texture::RenderTexture, render_window::RenderWindow},
     resources::RenderContext,
 };
 
 pub(crate) fn request_window_resize(
-    window_events: EventReader<WindowEvent>,
+    mut window_events: EventReader<WindowEvent>,
     mut window: ResMut<Window>,
 ) {
     for event in window_events.read() {
@@ -44,10 +44,11 @@ pub(crate) fn update_render_window(
 
         for (mut render_camera,) in render_cameras.iter() {
             if render_camera.render_target.is_some() {
-                continue; // RTT camera: fixed resolution, depth stays in sync with RTT
+                continue; // RTT camera: fixed resolution, depth and HDR stay in sync with RTT
             }
             render_camera.depth_texture =
                 RenderTexture::create_depth_texture(&device, size.0, size.1, "depth_texture");
+            render_camera.hdr_target = create_hdr_target(&device, size.0, size.1);
         }
     }
 
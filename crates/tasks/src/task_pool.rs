@@ -84,6 +84,11 @@ impl TaskPool {
         }
     }
 
+    /// Returns the number of worker threads in the pool.
+    pub fn thread_num(&self) -> usize {
+        self.threads.len()
+    }
+
     pub fn spawn<T>(&self, future: impl Future<Output = T> + Send + 'static) -> Task<T>
     where
         T: Send + 'static,
@@ -145,12 +150,18 @@ impl TaskPool {
 
                 let scope_ticker = scope_executor.ticker().unwrap();
 
-                Self::execute_scope(scope_ticker, get_results).await
+                Self::execute_scope(executor, scope_ticker, get_results).await
             })
         }
     }
 
+    /// Waits for the scope's tasks while helping to run the pool's tasks.
+    ///
+    /// Scopes opened from inside a pool task (e.g. a parallel query in a system run by the
+    /// multithreaded executor) block a worker thread; running pool tasks here keeps nested
+    /// scopes from starving each other when every worker is waiting.
     async fn execute_scope<'scope, 'ticker, T>(
+        executor: &Executor<'_>,
         scope_ticker: ThreadExecutorTicker<'scope, 'ticker>,
         get_results: impl Future<Output = Vec<T>>,
     ) -> Vec<T> {
@@ -158,7 +169,7 @@ impl TaskPool {
             loop {
                 let tick_forever = async {
                     loop {
-                        scope_ticker.tick().await;
+                        executor.tick().or(scope_ticker.tick()).await;
                     }
                 };
 
@@ -13,6 +13,11 @@ impl TaskPool {
         Self::new()
     }
 
+    /// Always `1`: wasm tasks run on the main thread.
+    pub fn thread_num(&self) -> usize {
+        1
+    }
+
     pub fn spawn<T>(&self, future: impl Future<Output = T> + 'static) -> Task<T>
     where
         T: Send + 'static,
@@ -17,14 +17,14 @@ pub struct TerminalResizeEvent {
 }
 
 pub(crate) fn handle_terminal_resize(
-    events: EventReader<TerminalResizeEvent>,
+    mut events: EventReader<TerminalResizeEvent>,
     mut state: ResMut<TerminalRenderState>,
     device: Res<RenderDevice>,
     context: Res<RenderContext>,
     terminal_cameras: Query<&RenderEntity, With<TerminalOutput>>,
     render_cameras: Query<&mut RenderCamera>,
 ) {
-    // Only care about the last resize event this frame
+    // Only care about the latest resize event since the last run
     let Some(ev) = events.read().last() else {
         return;
     };
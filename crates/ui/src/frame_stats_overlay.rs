@@ -74,7 +74,7 @@ fn update_overlay_text(
     mut timer: ResMut<OverlayRefreshTimer>,
     text_nodes: Query<&mut TextComponent, With<FrameStatsText>>,
 ) {
-    timer.0 += time.delta().as_secs_f32();
+    timer.0 += time.real_delta().as_secs_f32();
     if timer.0 < REFRESH_INTERVAL || stats.is_empty() {
         return;
     }
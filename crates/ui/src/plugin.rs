@@ -76,8 +76,7 @@ impl Plugin for UIPlugin {
             .add_system(UpdateGroup::Render, update_text_viewport)
             .add_system(UpdateGroup::Render, prepare_text_renderer)
             // Viewport nodes: create fresh bind groups before ui_renderpass.
-            .add_system(UpdateGroup::Render, extract_viewport_nodes)
-            .add_system(UpdateGroup::Render, ui_renderpass);
+            .add_system(UpdateGroup::Render, extract_viewport_nodes);
     }
 
     fn finish(&self, app: &mut app::App) {
@@ -188,6 +187,10 @@ impl Plugin for UIPlugin {
             .insert_resource(TextViewport(viewport))
             .insert_resource(TextFontSystem(font_system))
             .insert_resource(TextAtlas(atlas));
+
+        // Added here rather than in `build` so the UI is drawn over the
+        // post-processed image, whose systems are added in their own `finish`.
+        app.add_system(UpdateGroup::Render, ui_renderpass);
     }
 }
 
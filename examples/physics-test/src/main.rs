@@ -190,18 +190,14 @@ fn click_ball(
     let ndc_x = 2.0 * cursor.x / context.surface_config.width as f32 - 1.0;
     let ndc_y = 1.0 - 2.0 * cursor.y / context.surface_config.height as f32;
 
-    let tan_half_fovy = (camera.fovy * 0.5).tan();
-    let view_dir = Vec3::new(
-        ndc_x * tan_half_fovy * camera.aspect,
-        ndc_y * tan_half_fovy,
-        -1.0,
-    );
-    let direction = camera_transform
-        .matrix()
-        .transform_vector3(view_dir)
-        .normalize();
+    // Unproject the cursor onto the near and far planes, which works for any
+    // projection — orthographic rays don't start at the camera's position.
+    let world_from_clip =
+        (camera.build_projection_matrix() * camera_transform.matrix().inverse()).inverse();
+    let origin = world_from_clip.project_point3(Vec3::new(ndc_x, ndc_y, 0.0));
+    let far = world_from_clip.project_point3(Vec3::new(ndc_x, ndc_y, 1.0));
+    let direction = (far - origin).normalize();
 
-    let origin = camera_transform.translation();
     let Some(hit) = physics.cast_ray(origin, direction * 100.0) else {
         println!("click: nothing hit");
         return;